@@ -0,0 +1,423 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! The subcommands, one group for each of the typed traits.
+
+use clap::{Args, Subcommand};
+use defichain_rpc::bitcoin::Txid;
+use defichain_rpc::json::governance::{
+    ListGovProposalVotesOptions, ListGovProposalVotesPagination, ListProposalsOptions,
+    ListProposalsPagination, ListProposalsStatus, ListProposalsType,
+};
+use defichain_rpc::json::poolpair::PoolPairPagination;
+use defichain_rpc::json::token::TokenPagination;
+use defichain_rpc::json::vault::{ListVaultOptions, VaultPagination};
+use defichain_rpc::{
+    BlockchainRPC, Error, GovernanceRPC, LoanRPC, PoolPairRPC, Result, RpcApi, TokenRPC, VaultRPC,
+};
+use serde::de::DeserializeOwned;
+
+use crate::output::Output;
+
+/// The columns of the list commands.
+const TOKEN_COLUMNS: &[&str] = &["symbolKey", "name", "isDAT", "isLoanToken", "minted"];
+const POOL_COLUMNS: &[&str] = &["symbol", "reserveA", "reserveB", "totalLiquidity", "status"];
+const VAULT_COLUMNS: &[&str] =
+    &["vaultId", "state", "ownerAddress", "collateralValue", "loanValue", "collateralRatio"];
+const PROPOSAL_COLUMNS: &[&str] =
+    &["proposalId", "type", "status", "title", "amount", "currentCycle", "totalCycles"];
+const VOTE_COLUMNS: &[&str] = &["proposalId", "masternodeId", "cycle", "vote", "valid"];
+const LOAN_SCHEME_COLUMNS: &[&str] = &["id", "mincolratio", "interestrate"];
+
+/// Parse an enum of `defichain-rpc-json` from its JSON name.
+fn parse_json_enum<T: DeserializeOwned>(s: &str) -> std::result::Result<T, String> {
+    serde_json::from_value(s.into()).map_err(|_| format!("invalid value `{}`", s))
+}
+
+/// The pagination flags of the list commands.
+#[derive(Args, Debug, Default)]
+pub struct Pagination {
+    /// The key or index to start listing from.
+    #[arg(long)]
+    pub start: Option<String>,
+    /// Include the entry at `--start` itself.
+    #[arg(long)]
+    pub including_start: bool,
+    /// The maximum number of entries to list.
+    #[arg(long)]
+    pub limit: Option<u64>,
+}
+
+impl Pagination {
+    fn is_set(&self) -> bool {
+        self.start.is_some() || self.including_start || self.limit.is_some()
+    }
+
+    /// The start as a numeric index, for the RPCs that take one.
+    fn start_index(&self) -> Result<u64> {
+        match self.start {
+            Some(ref start) => start
+                .parse()
+                .map_err(|_| Error::Custom(format!("--start must be a number, got {}", start))),
+            None => Ok(0),
+        }
+    }
+
+    fn limit(&self) -> Option<usize> {
+        self.limit.map(|l| l as usize)
+    }
+
+    /// The node's default of including the start applies when there is none.
+    fn including_start(&self) -> bool {
+        self.including_start || self.start.is_none()
+    }
+}
+
+/// A group of subcommands.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Blockchain state.
+    #[command(subcommand)]
+    Chain(ChainCommand),
+    /// Tokens.
+    #[command(subcommand)]
+    Token(TokenCommand),
+    /// Pool pairs.
+    #[command(subcommand)]
+    Pool(PoolCommand),
+    /// Vaults.
+    #[command(subcommand)]
+    Vault(VaultCommand),
+    /// Loan schemes.
+    #[command(subcommand)]
+    Loan(LoanCommand),
+    /// On-chain governance.
+    #[command(subcommand)]
+    Gov(GovCommand),
+}
+
+impl Command {
+    pub async fn run<R: RpcApi + Sync>(self, rpc: &R) -> Result<Output> {
+        match self {
+            Command::Chain(cmd) => cmd.run(rpc).await,
+            Command::Token(cmd) => cmd.run(rpc).await,
+            Command::Pool(cmd) => cmd.run(rpc).await,
+            Command::Vault(cmd) => cmd.run(rpc).await,
+            Command::Loan(cmd) => cmd.run(rpc).await,
+            Command::Gov(cmd) => cmd.run(rpc).await,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ChainCommand {
+    /// Show the state of the chain.
+    Info,
+    /// Show the height of the best block.
+    Count,
+    /// Show the hash of the block at a height.
+    Hash {
+        height: u32,
+    },
+}
+
+impl ChainCommand {
+    async fn run<R: RpcApi + Sync>(self, rpc: &R) -> Result<Output> {
+        match self {
+            ChainCommand::Info => Output::object(&rpc.get_blockchain_info().await?),
+            ChainCommand::Count => Output::value(&rpc.get_block_count().await?),
+            ChainCommand::Hash {
+                height,
+            } => Output::value(&BlockchainRPC::get_block_hash(rpc, height).await?),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TokenCommand {
+    /// List the tokens.
+    List {
+        #[command(flatten)]
+        pagination: Pagination,
+    },
+    /// Show a token by id, symbol or creation tx.
+    Get {
+        key: String,
+    },
+}
+
+impl TokenCommand {
+    async fn run<R: RpcApi + Sync>(self, rpc: &R) -> Result<Output> {
+        match self {
+            TokenCommand::List {
+                pagination,
+            } => {
+                let pagination = if pagination.is_set() {
+                    Some(TokenPagination {
+                        start: pagination.start_index()?,
+                        including_start: pagination.including_start(),
+                        limit: pagination.limit.unwrap_or(100),
+                    })
+                } else {
+                    None
+                };
+                Output::map(&rpc.list_tokens(pagination, Some(true)).await?, TOKEN_COLUMNS)
+            }
+            TokenCommand::Get {
+                key,
+            } => Output::map(&rpc.get_token(&key).await?, TOKEN_COLUMNS),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PoolCommand {
+    /// List the pool pairs.
+    List {
+        #[command(flatten)]
+        pagination: Pagination,
+    },
+    /// Show a pool pair by id or symbol.
+    Get {
+        key: String,
+    },
+}
+
+impl PoolCommand {
+    async fn run<R: RpcApi + Sync>(self, rpc: &R) -> Result<Output> {
+        match self {
+            PoolCommand::List {
+                pagination,
+            } => {
+                let pagination = if pagination.is_set() {
+                    Some(PoolPairPagination {
+                        start: pagination.start_index()?,
+                        including_start: pagination.including_start(),
+                        limit: pagination.limit.unwrap_or(100),
+                    })
+                } else {
+                    None
+                };
+                Output::map(&rpc.list_pool_pairs(pagination, Some(true)).await?, POOL_COLUMNS)
+            }
+            PoolCommand::Get {
+                key,
+            } => Output::map(&rpc.get_pool_pair(key, Some(true)).await?, POOL_COLUMNS),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VaultCommand {
+    /// List the vaults.
+    List {
+        /// Only list the vaults of this address.
+        #[arg(long)]
+        owner: Option<String>,
+        /// Only list the vaults of this loan scheme.
+        #[arg(long)]
+        scheme: Option<String>,
+        #[command(flatten)]
+        pagination: Pagination,
+    },
+    /// Show a vault.
+    Get {
+        id: String,
+    },
+}
+
+impl VaultCommand {
+    async fn run<R: RpcApi + Sync>(self, rpc: &R) -> Result<Output> {
+        match self {
+            VaultCommand::List {
+                owner,
+                scheme,
+                pagination,
+            } => {
+                let options = ListVaultOptions {
+                    owner_address: owner,
+                    loan_scheme_id: scheme,
+                    state: None,
+                    // The results only deserialize in the verbose format.
+                    verbose: Some(true),
+                };
+                let pagination = VaultPagination {
+                    including_start: Some(pagination.including_start()),
+                    limit: pagination.limit(),
+                    start: pagination.start,
+                };
+                Output::list(&rpc.list_vaults(options, pagination).await?, VAULT_COLUMNS)
+            }
+            VaultCommand::Get {
+                id,
+            } => Output::object(&rpc.get_vault(id, Some(true)).await?),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LoanCommand {
+    /// List the loan schemes.
+    Schemes,
+}
+
+impl LoanCommand {
+    async fn run<R: RpcApi + Sync>(self, rpc: &R) -> Result<Output> {
+        match self {
+            LoanCommand::Schemes => {
+                Output::list(&rpc.list_loan_schemes().await?, LOAN_SCHEME_COLUMNS)
+            }
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GovCommand {
+    /// List the proposals.
+    Proposals {
+        /// Only list proposals of this type: cfp, voc or all.
+        #[arg(long = "type", value_parser = parse_json_enum::<ListProposalsType>)]
+        kind: Option<ListProposalsType>,
+        /// Only list proposals with this status: voting, rejected, completed or all.
+        #[arg(long, value_parser = parse_json_enum::<ListProposalsStatus>)]
+        status: Option<ListProposalsStatus>,
+        /// Only list proposals of this cycle, 0 for the current one.
+        #[arg(long)]
+        cycle: Option<i64>,
+        #[command(flatten)]
+        pagination: Pagination,
+    },
+    /// Show a proposal.
+    Proposal {
+        id: Txid,
+    },
+    /// List the votes on a proposal.
+    Votes {
+        id: Txid,
+        /// Only list the votes of this masternode, or of all or mine.
+        #[arg(long)]
+        masternode: Option<String>,
+        /// Only list the votes of this cycle, -1 for all.
+        #[arg(long)]
+        cycle: Option<i64>,
+        #[command(flatten)]
+        pagination: Pagination,
+    },
+}
+
+impl GovCommand {
+    async fn run<R: RpcApi + Sync>(self, rpc: &R) -> Result<Output> {
+        match self {
+            GovCommand::Proposals {
+                kind,
+                status,
+                cycle,
+                pagination,
+            } => {
+                let options = ListProposalsOptions {
+                    r#type: kind,
+                    status,
+                    cycle,
+                    pagination: if pagination.is_set() {
+                        Some(ListProposalsPagination {
+                            including_start: Some(pagination.including_start()),
+                            limit: pagination.limit(),
+                            start: pagination.start,
+                        })
+                    } else {
+                        None
+                    },
+                };
+                Output::list(&rpc.list_gov_proposals(Some(options)).await?, PROPOSAL_COLUMNS)
+            }
+            GovCommand::Proposal {
+                id,
+            } => Output::object(&rpc.get_gov_proposal(id).await?),
+            GovCommand::Votes {
+                id,
+                masternode,
+                cycle,
+                pagination,
+            } => {
+                let options = ListGovProposalVotesOptions {
+                    proposal_id: Some(id),
+                    masternode,
+                    cycle,
+                    pagination: if pagination.is_set() {
+                        Some(ListGovProposalVotesPagination {
+                            start: match pagination.start {
+                                Some(_) => Some(pagination.start_index()? as usize),
+                                None => None,
+                            },
+                            including_start: Some(pagination.including_start()),
+                            limit: pagination.limit(),
+                        })
+                    } else {
+                        None
+                    },
+                    ..Default::default()
+                };
+                Output::list(&rpc.list_gov_proposal_votes(Some(options)).await?, VOTE_COLUMNS)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Format;
+    use crate::Cli;
+    use clap::Parser;
+    use defichain_rpc::mock::MockNode;
+    use serde_json::json;
+
+    async fn run(node: &MockNode, args: &[&str]) -> Result<Output> {
+        let cli = Cli::try_parse_from(Some("defi-rpc").iter().chain(args)).unwrap();
+        cli.command.run(node).await
+    }
+
+    #[tokio::test]
+    async fn runs_commands() {
+        let node = MockNode::new();
+        node.state().add_token("BTC");
+        let output = run(&node, &["token", "list"]).await.unwrap();
+        assert!(output.render(Format::Table).starts_with("id  symbolKey  name"));
+        assert_eq!(output.json()["1"]["symbol"], "BTC");
+        assert_eq!(run(&node, &["chain", "count"]).await.unwrap().render(Format::Table), "0");
+
+        node.state().vaults.insert("v1".into(), json!({"vaultId": "v1"}));
+        // Bad responses are caught by the typed results.
+        assert!(run(&node, &["vault", "get", "v1"]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn passes_filters_and_pagination() {
+        let node = MockNode::new().respond("listgovproposals", json!([]));
+        let args = ["gov", "proposals", "--status", "voting", "--start", "abc", "--limit", "2"];
+        run(&node, &args).await.unwrap();
+        node.assert_last_request(
+            "listgovproposals",
+            json!([{
+                "status": "voting",
+                "pagination": {"start": "abc", "including_start": false, "limit": 2},
+            }]),
+        );
+
+        let node = MockNode::new();
+        run(&node, &["pool", "list", "--limit", "10"]).await.unwrap();
+        node.assert_last_request(
+            "listpoolpairs",
+            json!([{"start": 0, "including_start": true, "limit": 10}, true]),
+        );
+        assert!(run(&node, &["pool", "list", "--start", "x"]).await.is_err());
+        assert!(Cli::try_parse_from(["defi-rpc", "gov", "proposals", "--status", "x"]).is_err());
+    }
+}
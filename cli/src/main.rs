@@ -0,0 +1,143 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! `defi-rpc`, a command-line tool for DeFiChain nodes.
+//!
+//! It exposes the typed traits of `defichain-rpc` as subcommands, and unlike
+//! `defi-cli` deserializes every result into its typed result, so responses
+//! the types don't match are reported instead of printed:
+//!
+//! ```text
+//! defi-rpc vault get <id>
+//! defi-rpc pool list --limit 10
+//! defi-rpc --json gov proposals --status voting
+//! ```
+//!
+//! The connection settings are read from `defi.conf` and the cookie file like
+//! `defi-cli` does, and can be overridden with flags.
+
+use std::path::PathBuf;
+use std::process;
+
+use clap::Parser;
+use defichain_rpc::config::{Chain, RpcConfig};
+use defichain_rpc::{Auth, Client, Result};
+
+mod commands;
+mod output;
+
+use crate::commands::Command;
+use crate::output::Format;
+
+#[derive(Parser, Debug)]
+#[command(name = "defi-rpc", version, about = "Typed command-line client for DeFiChain nodes")]
+pub struct Cli {
+    /// Print the results as JSON instead of tables.
+    #[arg(long, global = true)]
+    pub json: bool,
+    #[command(flatten)]
+    pub connection: Connection,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// The connection settings, by default those of `defi.conf`.
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Connection")]
+pub struct Connection {
+    /// The data directory of the node, containing `defi.conf`.
+    #[arg(long, global = true)]
+    pub datadir: Option<PathBuf>,
+    /// The chain: main, test, changi, devnet or regtest.
+    #[arg(long, global = true)]
+    pub chain: Option<Chain>,
+    /// The URL of the node, e.g. http://127.0.0.1:8554.
+    #[arg(long, global = true)]
+    pub rpcconnect: Option<String>,
+    /// The username for the RPC.
+    #[arg(long, global = true, requires = "rpcpassword")]
+    pub rpcuser: Option<String>,
+    /// The password for the RPC.
+    #[arg(long, global = true, requires = "rpcuser")]
+    pub rpcpassword: Option<String>,
+    /// The cookie file of the node.
+    #[arg(long, global = true, conflicts_with = "rpcuser")]
+    pub rpccookiefile: Option<PathBuf>,
+}
+
+impl Connection {
+    /// The settings of `defi.conf`, overridden by the flags.
+    fn config(&self) -> Result<RpcConfig> {
+        let mut config = RpcConfig::discover(self.datadir.as_deref(), self.chain)?;
+        if let Some(ref url) = self.rpcconnect {
+            config.url = url.clone();
+        }
+        if let (Some(user), Some(password)) = (&self.rpcuser, &self.rpcpassword) {
+            config.auth = Auth::UserPass(user.clone(), password.clone());
+        } else if let Some(ref path) = self.rpccookiefile {
+            config.auth = Auth::CookieFile(path.clone());
+        }
+        Ok(config)
+    }
+
+    async fn connect(&self) -> Result<Client> {
+        let config = self.config()?;
+        Client::new(&config.url, config.auth).await
+    }
+}
+
+async fn run(cli: Cli) -> Result<String> {
+    let client = cli.connection.connect().await?;
+    let format = if cli.json {
+        Format::Json
+    } else {
+        Format::Table
+    };
+    Ok(cli.command.run(&client).await?.render(format))
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    match run(Cli::parse()).await {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            // The errors of calls already name their causes.
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn reads_connection_flags() {
+        Cli::command().debug_assert();
+
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(tempdir.path().join("defi.conf"), "regtest=1\nrpcuser=a\nrpcpassword=b\n")
+            .unwrap();
+        let datadir = tempdir.path().to_str().unwrap();
+        let cli = Cli::parse_from(["defi-rpc", "chain", "count", "--datadir", datadir]);
+        let config = cli.connection.config().unwrap();
+        assert_eq!(config.chain, Chain::Regtest);
+        assert_eq!(config.url, "http://127.0.0.1:19554");
+        assert!(matches!(config.auth, Auth::UserPass(ref u, ref p) if u == "a" && p == "b"));
+
+        let args = ["defi-rpc", "--datadir", datadir, "--rpcconnect", "http://node:1234"];
+        let cli = Cli::parse_from(args.iter().chain(&["--rpccookiefile", "/c", "pool", "list"]));
+        let config = cli.connection.config().unwrap();
+        assert_eq!(config.url, "http://node:1234");
+        assert!(matches!(config.auth, Auth::CookieFile(ref p) if p.to_str() == Some("/c")));
+    }
+}
@@ -0,0 +1,178 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Table and JSON output.
+//!
+//! Commands deserialize the results into the typed results of
+//! `defichain-rpc-json` and serialize them back to JSON, so both formats only
+//! show what the types know, and a result the types don't match fails the
+//! command instead of printing garbage.
+
+use std::fmt;
+
+use defichain_rpc::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+/// The output format of the commands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+/// The result of a command.
+#[derive(Debug)]
+pub struct Output {
+    value: Value,
+    table: Option<Table>,
+}
+
+impl Output {
+    /// A result that is printed as a plain value in table format.
+    pub fn value<T: Serialize>(result: &T) -> Result<Output> {
+        Ok(Output {
+            value: serde_json::to_value(result)?,
+            table: None,
+        })
+    }
+
+    /// A list of objects, with one row for each and the given columns.
+    pub fn list<T: Serialize>(result: &T, columns: &[&str]) -> Result<Output> {
+        let value = serde_json::to_value(result)?;
+        let mut table = Table::new(columns);
+        for item in value.as_array().into_iter().flatten() {
+            table.push(columns.iter().map(|c| cell(&item[*c])).collect());
+        }
+        Ok(Output {
+            value,
+            table: Some(table),
+        })
+    }
+
+    /// A map of objects keyed by id, with one row for each and the given
+    /// columns after the id.
+    pub fn map<T: Serialize>(result: &T, columns: &[&str]) -> Result<Output> {
+        let value = serde_json::to_value(result)?;
+        let mut table = Table::new(&[&["id"], columns].concat());
+        for (id, item) in value.as_object().into_iter().flatten() {
+            let row = Some(id.clone()).into_iter().chain(columns.iter().map(|c| cell(&item[*c])));
+            table.push(row.collect());
+        }
+        Ok(Output {
+            value,
+            table: Some(table),
+        })
+    }
+
+    /// A single object, with one row for each of its fields.
+    pub fn object<T: Serialize>(result: &T) -> Result<Output> {
+        let value = serde_json::to_value(result)?;
+        let mut table = Table::new(&["field", "value"]);
+        for (key, field) in value.as_object().into_iter().flatten() {
+            table.push(vec![key.clone(), cell(field)]);
+        }
+        Ok(Output {
+            value,
+            table: Some(table),
+        })
+    }
+
+    /// The result as JSON.
+    pub fn json(&self) -> &Value {
+        &self.value
+    }
+
+    /// Render the result in the given format.
+    pub fn render(&self, format: Format) -> String {
+        match (format, &self.table) {
+            (Format::Json, _) => serde_json::to_string_pretty(&self.value).unwrap(),
+            (Format::Table, Some(table)) => table.to_string(),
+            (Format::Table, None) => cell(&self.value),
+        }
+    }
+}
+
+/// Format a JSON value for a table cell.
+fn cell(value: &Value) -> String {
+    match *value {
+        Value::Null => "-".to_owned(),
+        Value::String(ref s) => s.clone(),
+        ref v => v.to_string(),
+    }
+}
+
+/// A table with left-aligned columns.
+#[derive(Debug, Default)]
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// An empty table with the given header.
+    pub fn new(header: &[&str]) -> Table {
+        Table {
+            header: header.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row.
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut widths: Vec<usize> = self.header.iter().map(|h| h.chars().count()).collect();
+        for row in self.rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let lines = std::iter::once(&self.header).chain(self.rows.iter());
+        for (i, row) in lines.enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let cells: Vec<_> =
+                row.iter().zip(widths.iter()).map(|(c, w)| format!("{:1$}", c, w)).collect();
+            f.write_str(cells.join("  ").trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_tables() {
+        let pairs = json!({
+            "4": {"symbol": "BTC-DFI", "status": true, "reserveA": 1.5},
+            "5": {"symbol": "ETH-DFI", "status": false},
+        });
+        let output = Output::map(&pairs, &["symbol", "reserveA"]).unwrap();
+        assert_eq!(
+            output.render(Format::Table),
+            "id  symbol   reserveA\n4   BTC-DFI  1.5\n5   ETH-DFI  -"
+        );
+        assert_eq!(output.json(), &pairs);
+
+        let output = Output::object(&json!({"blocks": 10, "chain": "regtest"})).unwrap();
+        assert_eq!(output.render(Format::Table), "field   value\nblocks  10\nchain   regtest");
+        assert_eq!(Output::value(&"abc").unwrap().render(Format::Table), "abc");
+        assert_eq!(Output::value(&"abc").unwrap().render(Format::Json), "\"abc\"");
+    }
+}
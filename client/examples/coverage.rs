@@ -0,0 +1,36 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Print which RPCs of a node have typed wrappers in this library.
+extern crate defichain_rpc;
+
+use defichain_rpc::coverage::check_coverage;
+use defichain_rpc::{Auth, Client, Error};
+
+async fn main_result() -> Result<(), Error> {
+    let mut args = std::env::args();
+
+    let _exe_name = args.next().unwrap();
+
+    let url = args.next().expect("Usage: <rpc_url> <username> <password>");
+    let user = args.next().expect("no user given");
+    let pass = args.next().expect("no pass given");
+
+    let client = Client::new(&url, Auth::UserPass(user, pass)).await?;
+    let report = check_coverage(&client).await?;
+    print!("{}", report);
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    main_result().await.unwrap();
+}
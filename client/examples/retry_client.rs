@@ -8,42 +8,42 @@
 // If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
 //
 
+//! Retry calls while the node is warming up or restarting.
 extern crate defichain_rpc;
-extern crate jsonrpc_async;
-extern crate serde;
-extern crate serde_json;
 
-use async_trait::async_trait;
-use defichain_rpc::{Client, Error, Result, RpcApi};
+use std::time::Duration;
 
-pub struct RetryClient {
-    client: Client,
-}
+use defichain_rpc::{Auth, Client, Error, RetryClient, RetryPolicy, RpcApi};
 
 const INTERVAL: u64 = 1000;
-const RETRY_ATTEMPTS: u8 = 10;
-
-#[async_trait]
-impl RpcApi for RetryClient {
-    async fn call<T: for<'a> serde::de::Deserialize<'a>>(
-        &self,
-        cmd: &str,
-        args: &[serde_json::Value],
-    ) -> Result<T> {
-        for _ in 0..RETRY_ATTEMPTS {
-            match self.client.call(cmd, args).await {
-                Ok(ret) => return Ok(ret),
-                Err(Error::JsonRpc(jsonrpc_async::error::Error::Rpc(ref rpcerr)))
-                    if rpcerr.code == -28 =>
-                {
-                    ::std::thread::sleep(::std::time::Duration::from_millis(INTERVAL));
-                    continue;
-                }
-                Err(e) => return Err(e),
-            }
-        }
-        self.client.call(cmd, args).await
-    }
+const RETRY_ATTEMPTS: u32 = 10;
+
+async fn main_result() -> Result<(), Error> {
+    let mut args = std::env::args();
+
+    let _exe_name = args.next().unwrap();
+
+    let url = args.next().expect("Usage: <rpc_url> <username> <password>");
+    let user = args.next().expect("no user given");
+    let pass = args.next().expect("no pass given");
+
+    let client = Client::new(&url, Auth::UserPass(user, pass)).await?;
+    let rpc = RetryClient::with_policy(
+        client,
+        RetryPolicy {
+            max_attempts: RETRY_ATTEMPTS,
+            initial_backoff: Duration::from_millis(INTERVAL),
+            ..Default::default()
+        },
+    );
+
+    rpc.wait_until_ready(Duration::from_secs(60)).await?;
+    println!("best block height: {}", rpc.get_block_count().await?);
+
+    Ok(())
 }
 
-fn main() {}
+#[tokio::main]
+async fn main() {
+    main_result().await.unwrap();
+}
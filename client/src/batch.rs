@@ -0,0 +1,488 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! JSON-RPC batch requests.
+//!
+//! A [Batch] collects many calls and sends them to the node as a single
+//! JSON-RPC array, which saves one HTTP round trip per call.
+//!
+//! Calls can be queued by method name with [Batch::queue], as
+//! [requests](crate::requests) with [Batch::request], or through any of the
+//! typed trait methods with [Batch::add]:
+//!
+//! ```no_run
+//! # async fn example(client: &defichain_rpc::Client) -> defichain_rpc::Result<()> {
+//! use defichain_rpc::{BlockchainRPC, VaultRPC};
+//!
+//! let mut batch = client.batch();
+//! let count = batch.add(|rpc| BlockchainRPC::get_block_count(rpc));
+//! let vault = batch.add(|rpc| rpc.get_vault("0123...".into(), None));
+//! let hash = batch.queue::<String>("getblockhash", vec![1.into()]);
+//!
+//! let mut results = batch.send().await?;
+//! let count = results.take(count)?;
+//! let vault = results.take(vault)?;
+//! let hash = results.take(hash)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::value::RawValue;
+
+use crate::client::{parse_raw, redact_params, Client, Result, RpcApi};
+use crate::error::Error;
+use crate::requests::RpcRequest;
+
+/// The future returned by the async trait methods of [RpcApi] and the domain
+/// traits built on top of it.
+pub type RpcFuture<'r, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'r>>;
+
+type AnyResult = Result<Box<dyn Any + Send>>;
+
+/// A call as it is captured before the batch is sent.
+struct Captured {
+    cmd: String,
+    args: Vec<serde_json::Value>,
+}
+
+/// The response of a single call in the batch.
+struct Served {
+    cmd: String,
+    args: Vec<serde_json::Value>,
+    result: Result<Box<RawValue>>,
+}
+
+enum Mode {
+    /// Record the calls made by an entry, without sending anything.
+    Capture(Vec<Captured>),
+    /// Answer the calls made by an entry from the batch response.
+    Replay(VecDeque<Served>),
+}
+
+/// The [RpcApi] implementation handed to the closures passed to [Batch::add].
+///
+/// The closure is run twice: once to capture the calls it makes, and once,
+/// after the batch was sent, to build its result from the batch response. Calls
+/// that were not captured before the batch was sent (e.g. a call that depends
+/// on the result of a previous one) fail instead of being sent on their own.
+pub struct BatchRpc<'a> {
+    client: &'a Client,
+    mode: Mutex<Mode>,
+}
+
+impl<'a> BatchRpc<'a> {
+    fn capture(client: &'a Client) -> BatchRpc<'a> {
+        BatchRpc {
+            client,
+            mode: Mutex::new(Mode::Capture(Vec::new())),
+        }
+    }
+
+    fn replay(client: &'a Client, served: VecDeque<Served>) -> BatchRpc<'a> {
+        BatchRpc {
+            client,
+            mode: Mutex::new(Mode::Replay(served)),
+        }
+    }
+
+    fn into_captured(self) -> Vec<Captured> {
+        match self.mode.into_inner().unwrap() {
+            Mode::Capture(captured) => captured,
+            Mode::Replay(_) => Vec::new(),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for BatchRpc<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::BatchRpc({:?})", self.client)
+    }
+}
+
+#[async_trait]
+impl<'a> RpcApi for BatchRpc<'a> {
+    async fn call<T: for<'de> serde::de::Deserialize<'de>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        let served = match *self.mode.lock().unwrap() {
+            Mode::Capture(ref mut captured) => {
+                captured.push(Captured {
+                    cmd: cmd.to_owned(),
+                    args: args.to_vec(),
+                });
+                // The result of a captured call is never observed by the caller.
+                return Err(Error::Custom("call captured for batch".into()));
+            }
+            Mode::Replay(ref mut served) => match served.front() {
+                Some(s) if s.cmd == cmd && s.args == args => served.pop_front(),
+                _ => None,
+            },
+        };
+        match served {
+            // Like direct calls, keep the method and params, and the location
+            // of a result that can't be deserialized.
+            Some(s) => s.result.and_then(|r| parse_raw(r.get())).map_err(|e| Error::Call {
+                method: cmd.to_owned(),
+                params: redact_params(cmd, args),
+                source: Box::new(e),
+            }),
+            None => Err(Error::Call {
+                method: cmd.to_owned(),
+                params: redact_params(cmd, args),
+                source: Box::new(Error::Custom("batch call does not match captured call".into())),
+            }),
+        }
+    }
+}
+
+/// An entry of the batch with its result type erased.
+trait Entry<'a>: Send + Sync {
+    fn run<'r>(
+        &'r self,
+        rpc: &'r BatchRpc<'a>,
+    ) -> Pin<Box<dyn Future<Output = AnyResult> + Send + 'r>>;
+}
+
+struct TypedEntry<F, T> {
+    f: F,
+    _result: PhantomData<fn() -> T>,
+}
+
+impl<'a, F, T> Entry<'a> for TypedEntry<F, T>
+where
+    F: for<'r> Fn(&'r BatchRpc<'a>) -> RpcFuture<'r, T> + Send + Sync,
+    T: Send + 'static,
+{
+    fn run<'r>(
+        &'r self,
+        rpc: &'r BatchRpc<'a>,
+    ) -> Pin<Box<dyn Future<Output = AnyResult> + Send + 'r>> {
+        let fut = (self.f)(rpc);
+        Box::pin(async move { fut.await.map(|t| Box::new(t) as Box<dyn Any + Send>) })
+    }
+}
+
+/// A handle to the result of a call queued in a [Batch].
+#[derive(Debug)]
+pub struct BatchItem<T> {
+    index: usize,
+    _result: PhantomData<fn() -> T>,
+}
+
+/// A set of calls to be sent to the node in a single JSON-RPC batch request.
+///
+/// Create one with [Client::batch].
+pub struct Batch<'a> {
+    client: &'a Client,
+    entries: Vec<Box<dyn Entry<'a> + 'a>>,
+}
+
+impl<'a> fmt::Debug for Batch<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::Batch({:?}, {} entries)", self.client, self.entries.len())
+    }
+}
+
+impl<'a> Batch<'a> {
+    pub(crate) fn new(client: &'a Client) -> Batch<'a> {
+        Batch {
+            client,
+            entries: Vec::new(),
+        }
+    }
+
+    /// The number of entries queued in this batch.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no entries were queued in this batch.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Queue a call of any typed method of [RpcApi] or the domain traits.
+    ///
+    /// The closure may be called more than once.
+    pub fn add<T, F>(&mut self, f: F) -> BatchItem<T>
+    where
+        F: for<'r> Fn(&'r BatchRpc<'a>) -> RpcFuture<'r, T> + Send + Sync + 'a,
+        T: Send + 'static,
+    {
+        self.entries.push(Box::new(TypedEntry {
+            f,
+            _result: PhantomData,
+        }));
+        BatchItem {
+            index: self.entries.len() - 1,
+            _result: PhantomData,
+        }
+    }
+
+    /// Queue a `cmd` rpc with given `args` list.
+    pub fn queue<T>(&mut self, cmd: &'a str, args: Vec<serde_json::Value>) -> BatchItem<T>
+    where
+        T: for<'de> serde::de::Deserialize<'de> + Send + 'static,
+    {
+        self.add(move |rpc| {
+            let args = args.clone();
+            Box::pin(async move { rpc.call(cmd, &args).await })
+        })
+    }
+
+    /// Queue a [request](crate::requests).
+    pub fn request<R>(&mut self, request: R) -> BatchItem<R::Response>
+    where
+        R: RpcRequest + 'a,
+        R::Response: 'static,
+    {
+        let request = Arc::new(request);
+        self.add(move |rpc| {
+            let request = request.clone();
+            Box::pin(async move { rpc.request(&*request).await })
+        })
+    }
+
+    /// Send all queued calls as a single JSON-RPC batch request.
+    ///
+    /// Only fails if the batch as a whole could not be sent. Errors of the
+    /// individual calls are returned by [BatchResults::take].
+    pub async fn send(self) -> Result<BatchResults> {
+        // Capture the calls made by each entry.
+        let mut captured = Vec::with_capacity(self.entries.len());
+        for entry in self.entries.iter() {
+            let rpc = BatchRpc::capture(self.client);
+            let _ = entry.run(&rpc).await;
+            captured.push(rpc.into_captured());
+        }
+
+        let mut served = send_captured(self.client, &captured).await?;
+
+        // Run every entry again, this time answering its calls from the batch response.
+        let mut results = Vec::with_capacity(self.entries.len());
+        for entry in self.entries.iter() {
+            let rpc = BatchRpc::replay(self.client, served.pop_front().unwrap_or_default());
+            results.push(Some(entry.run(&rpc).await));
+        }
+        Ok(BatchResults {
+            results,
+        })
+    }
+}
+
+/// Send the captured calls of all entries in one request, and split the
+/// responses back up per entry.
+async fn send_captured(
+    client: &Client,
+    captured: &[Vec<Captured>],
+) -> Result<VecDeque<VecDeque<Served>>> {
+    let calls: Vec<&Captured> = captured.iter().flatten().collect();
+    if calls.is_empty() {
+        return Ok(VecDeque::new());
+    }
+
+    let raw_args = calls
+        .iter()
+        .map(|c| {
+            c.args
+                .iter()
+                .map(|a| RawValue::from_string(serde_json::to_string(a)?))
+                .collect::<serde_json::Result<Vec<_>>>()
+        })
+        .collect::<serde_json::Result<Vec<_>>>()?;
+    let jsonrpc = client.get_jsonrpc_client();
+    let requests: Vec<_> =
+        calls.iter().zip(raw_args.iter()).map(|(c, a)| jsonrpc.build_request(&c.cmd, a)).collect();
+    if log_enabled!(log::Level::Debug) {
+        debug!(target: "defichain_rpc", "JSON-RPC batch request: {} calls", requests.len());
+    }
+    let mut responses = jsonrpc.send_batch(&requests).await?.into_iter();
+
+    Ok(captured
+        .iter()
+        .map(|entry| {
+            entry
+                .iter()
+                .map(|c| Served {
+                    cmd: c.cmd.clone(),
+                    args: c.args.clone(),
+                    result: match responses.next().and_then(|r| r) {
+                        Some(resp) => resp.result::<Box<RawValue>>().map_err(Error::from),
+                        None => Err(Error::Custom(format!("no response for batched {}", c.cmd))),
+                    },
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// The results of a [Batch] that was sent.
+pub struct BatchResults {
+    results: Vec<Option<AnyResult>>,
+}
+
+impl fmt::Debug for BatchResults {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::BatchResults({} results)", self.results.len())
+    }
+}
+
+impl BatchResults {
+    /// The number of results, one for every entry of the batch.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether the batch had no entries.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Take the result of the given call out of the batch results.
+    ///
+    /// Panics if the result was already taken or if the item belongs to another batch.
+    pub fn take<T: 'static>(&mut self, item: BatchItem<T>) -> Result<T> {
+        let result = self.results[item.index].take().expect("batch result was already taken");
+        result.map(|any| *any.downcast::<T>().expect("batch item belongs to another batch"))
+    }
+}
+
+impl Client {
+    /// Start a new [Batch] of calls to be sent in a single request.
+    pub fn batch(&self) -> Batch<'_> {
+        Batch::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::hashes::Hash;
+    use crate::bitcoin::BlockHash;
+    use crate::{BlockchainRPC, RpcErrorCode, VaultRPC};
+    use jsonrpc_async::{Request, Response, Transport};
+
+    /// Answers every request with its method name and params, except for
+    /// `getblockcount` which returns a number and `fail` which returns an error.
+    struct EchoTransport;
+
+    fn respond(req: &Request) -> Response {
+        let params: Vec<serde_json::Value> =
+            req.params.iter().map(|p| serde_json::from_str(p.get()).unwrap()).collect();
+        let result = match req.method {
+            "getblockcount" => Ok(serde_json::json!(42)),
+            "fail" => Err(jsonrpc_async::error::standard_error(
+                jsonrpc_async::error::StandardError::MethodNotFound,
+                None,
+            )),
+            m => Ok(serde_json::json!({ "method": m, "params": params })),
+        };
+        jsonrpc_async::error::result_to_response(result, req.id.clone())
+    }
+
+    #[async_trait]
+    impl Transport for EchoTransport {
+        async fn send_request(
+            &self,
+            r: Request<'_>,
+        ) -> std::result::Result<Response, jsonrpc_async::Error> {
+            Ok(respond(&r))
+        }
+        async fn send_batch(
+            &self,
+            rs: &[Request<'_>],
+        ) -> std::result::Result<Vec<Response>, jsonrpc_async::Error> {
+            // Answer in reverse to make sure responses are matched by id.
+            Ok(rs.iter().rev().map(respond).collect())
+        }
+        fn fmt_target(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "echo")
+        }
+    }
+
+    fn client() -> Client {
+        Client::from_jsonrpc(jsonrpc_async::Client::with_transport(EchoTransport))
+    }
+
+    #[tokio::test]
+    async fn batch_returns_results_per_call() {
+        let client = client();
+        let mut batch = client.batch();
+        let count = batch.add(|rpc| BlockchainRPC::get_block_count(rpc));
+        let vault = batch.add(|rpc| rpc.get_vault("vault".into(), None));
+        let echo = batch.queue::<serde_json::Value>("echo", vec![1.into()]);
+        let fail = batch.queue::<serde_json::Value>("fail", vec![]);
+        assert_eq!(batch.len(), 4);
+
+        let mut results = batch.send().await.unwrap();
+        assert_eq!(results.take(count).unwrap(), 42);
+        let err = results.take(vault).unwrap_err();
+        assert_eq!(err.method(), Some("getvault"));
+        assert!(matches!(err.root(), Error::Deserialize { .. }));
+        assert_eq!(results.take(echo).unwrap()["params"], serde_json::json!([1]));
+        let err = results.take(fail).unwrap_err();
+        assert_eq!(err.method(), Some("fail"));
+        assert_eq!(err.rpc_code(), Some(RpcErrorCode::MethodNotFound));
+    }
+
+    #[tokio::test]
+    async fn batch_keeps_errors_raised_before_the_call() {
+        let client = client();
+        let mut batch = client.batch();
+        let block = batch.add(|rpc| BlockchainRPC::get_block(rpc, BlockHash::all_zeros(), 3));
+        let mut results = batch.send().await.unwrap();
+        assert!(matches!(results.take(block), Err(Error::Custom(_))));
+    }
+
+    #[tokio::test]
+    async fn batch_fails_calls_that_were_not_captured() {
+        let client = client();
+        let mut batch = client.batch();
+        let hash = batch.add(|rpc| {
+            Box::pin(async move {
+                let count = BlockchainRPC::get_block_count(rpc).await?;
+                rpc.call::<serde_json::Value>("getblockhash", &[count.into()]).await
+            })
+        });
+        let mut results = batch.send().await.unwrap();
+        let err = results.take(hash).unwrap_err();
+        assert_eq!(err.method(), Some("getblockhash"));
+        assert!(matches!(err.root(), Error::Custom(_)));
+    }
+
+    #[tokio::test]
+    async fn batch_sends_requests() {
+        use crate::requests::{blockchain, vault};
+
+        let client = client();
+        let mut batch = client.batch();
+        let count = batch.request(blockchain::GetBlockCount {});
+        let vault = batch.request(vault::GetVault {
+            vault_id: "vault".into(),
+            verbose: None,
+        });
+        let mut results = batch.send().await.unwrap();
+        assert_eq!(results.take(count).unwrap(), 42);
+        let err = results.take(vault).unwrap_err();
+        assert!(matches!(err, Error::Call { ref params, .. } if params[0] == "vault"));
+        assert!(matches!(err.root(), Error::Deserialize { .. }));
+    }
+}
@@ -0,0 +1,88 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::{account::*, common::UTXO};
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::AccountRPC].
+    pub trait AccountRPC: crate::AccountRPC {
+        fn account_to_account(
+            &self,
+            from: String,
+            payload: BalanceTransferPayload,
+            options: BalanceTransferAccountOptions,
+        ) -> Result<String>;
+        fn account_to_utxos(
+            &self,
+            from: String,
+            payload: BalanceTransferPayload,
+            options: BalanceTransferAccountOptions,
+        ) -> Result<String>;
+        fn future_swap(&self, future: FutureSwap, utxos: Option<UTXO>) -> Result<String>;
+        fn get_account(
+            &self,
+            owner: &str,
+            pagination: Option<GetAccountPagination>,
+            indexed_amounts: Option<bool>,
+        ) -> Result<AccountAmount>;
+        fn get_account_history(
+            &self,
+            owner: &str,
+            block_height: u32,
+            txn: u32,
+        ) -> Result<AccountHistory>;
+        fn get_burn_info(&self) -> Result<BurnInfo>;
+        fn get_pending_dusd_swaps(&self, address: String) -> Result<DusdSwapsInfo>;
+        fn get_pending_future_swaps(&self, address: String) -> Result<GetFutureInfo>;
+        fn get_token_balances(
+            &self,
+            pagination: Option<GetAccountPagination>,
+            indexed_amounts: Option<bool>,
+            options: Option<GetTokenBalancesOptions>,
+        ) -> Result<()>;
+        fn history_count(
+            &self,
+            owner: Option<String>,
+            options: AccountHistoryCountOptions,
+        ) -> Result<u64>;
+        fn list_account_history(
+            &self,
+            owner: Option<String>,
+            options: AccountHistoryOptions,
+        ) -> Result<Vec<AccountHistory>>;
+        fn list_accounts(
+            &self,
+            pagination: Option<ListAccountsPagination>,
+            verbose: Option<bool>,
+            indexed_amounts: Option<bool>,
+            is_mine_only: Option<bool>,
+        ) -> Result<Vec<AccountsResult>>;
+        fn list_burn_history(&self, options: BurnHistoryOptions) -> Result<Vec<BurnHistory>>;
+        fn list_community_balances(&self) -> Result<CommunityBalanceData>;
+        fn list_pending_dusd_swaps(&self) -> Result<Vec<DusdSwapsInfo>>;
+        fn list_pending_future_swaps(&self) -> Result<Vec<ListFutureInfo>>;
+        fn send_tokens_to_address(
+            &self,
+            from: AddressBalances,
+            to: AddressBalances,
+            options: SendTokensOptions,
+        ) -> Result<String>;
+        fn transfer_domain(&self, payload: Vec<TransferDomain>) -> Result<String>;
+        fn utxos_to_account(
+            &self,
+            payload: BalanceTransferPayload,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn withdraw_future_swap(&self, future: FutureSwap, utxos: Option<UTXO>) -> Result<String>;
+    }
+}
@@ -0,0 +1,51 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::bitcoin::Txid;
+use defichain_rpc_json::blockchain::*;
+
+use super::Client;
+use crate::bitcoin::BlockHash;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::BlockchainRPC].
+    pub trait BlockchainRPC: crate::BlockchainRPC {
+        fn get_best_block_hash(&self) -> Result<BlockHash>;
+        fn get_block(&self, hash: BlockHash, verbosity: u8) -> Result<GetBlockResult>;
+        fn get_block_count(&self) -> Result<u64>;
+        fn get_block_hash(&self, height: u32) -> Result<BlockHash>;
+        fn get_block_header(&self, hash: BlockHash) -> Result<BlockHeader>;
+        fn get_block_stats(&self, height: u32, stats: Option<Vec<String>>) -> Result<BlockStats>;
+        fn get_blockchain_info(&self) -> Result<BlockchainInfo>;
+        fn get_chain_tips(&self) -> Result<Vec<ChainTip>>;
+        fn get_chain_tx_stats(
+            &self,
+            n_blocks: Option<u64>,
+            block_hash: Option<BlockHash>,
+        ) -> Result<ChainTxStats>;
+        fn get_difficulty(&self) -> Result<u64>;
+        fn get_mempool_ancestors(&self, tx_id: Txid) -> Result<()>;
+        fn get_mempool_descendants(&self, tx_id: Txid) -> Result<()>;
+        fn get_mempool_entry(&self, tx_id: Txid) -> Result<MempoolTx>;
+        fn get_mempool_info(&self) -> Result<MempoolInfo>;
+        fn get_raw_mempool(&self) -> Result<()>;
+        fn get_tx_out(
+            &self,
+            tx_id: Txid,
+            index: u64,
+            include_mempool: Option<bool>,
+        ) -> Result<UTXODetails>;
+        fn get_tx_out_set_info(&self) -> Result<TxOutSetInfo>;
+        fn wait_for_block(&self, blockhash: BlockHash) -> Result<WaitBlockResult>;
+        fn wait_for_block_height(&self, height: u64) -> Result<WaitBlockResult>;
+        fn wait_for_new_block(&self) -> Result<WaitBlockResult>;
+    }
+}
@@ -0,0 +1,520 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use std::{fmt, future::Future};
+
+use tokio::runtime::Runtime;
+
+use crate::bitcoin::address::{NetworkChecked, NetworkUnchecked};
+use crate::bitcoin::secp256k1::ecdsa::Signature;
+use crate::bitcoin::{
+    self, Address, Amount, Block, OutPoint, PrivateKey, PublicKey, Script, Transaction,
+};
+use crate::config::Chain;
+use crate::json;
+use crate::queryable::Queryable;
+use crate::timeout::WithTimeout;
+use crate::{Auth, Raw, RawTx, Result, RpcRequest};
+
+/// A client that makes the calls of an async [RpcApi](crate::RpcApi)
+/// implementation on its own runtime, blocking until they are done.
+///
+/// The methods are provided by the traits of the [blocking](crate::blocking)
+/// module. They must not be called from within an async runtime.
+pub struct Client<C = crate::Client> {
+    inner: C,
+    runtime: Arc<Runtime>,
+}
+
+impl<C: fmt::Debug> fmt::Debug for Client<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::blocking::Client({:?})", self.inner)
+    }
+}
+
+impl Client {
+    /// Creates a client to a node, see [crate::Client::new].
+    pub fn new(url: &str, auth: Auth) -> Result<Self> {
+        let runtime = Arc::new(runtime()?);
+        let inner = runtime.block_on(crate::Client::new(url, auth))?;
+        Ok(Client {
+            inner,
+            runtime,
+        })
+    }
+
+    /// Create a client to a local node, using the settings from `defi.conf`.
+    ///
+    /// See [crate::Client::from_conf].
+    pub fn from_conf(data_dir: Option<&Path>, chain: Option<Chain>) -> Result<Self> {
+        let runtime = Arc::new(runtime()?);
+        let inner = runtime.block_on(crate::Client::from_conf(data_dir, chain))?;
+        Ok(Client {
+            inner,
+            runtime,
+        })
+    }
+
+    /// Create a client that sends its calls to the endpoint of the loaded
+    /// wallet `name`, see [crate::Client::wallet].
+    ///
+    /// The client shares the runtime with this one.
+    pub fn wallet(&self, name: &str) -> Result<Self> {
+        Ok(Client {
+            inner: self.inner.wallet(name)?,
+            runtime: self.runtime.clone(),
+        })
+    }
+}
+
+impl<C> Client<C> {
+    /// Wrap an async client.
+    pub fn from_async(inner: C) -> Result<Self> {
+        Ok(Client {
+            inner,
+            runtime: Arc::new(runtime()?),
+        })
+    }
+
+    /// Run a future on the runtime of the client, for instance to make a
+    /// batch call with the wrapped client.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Get the wrapped client.
+    pub fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C: crate::RpcApi + Sync> Client<C> {
+    /// Call a `cmd` rpc with given `args` list
+    pub fn call<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        self.block_on(crate::RpcApi::call(&self.inner, cmd, args))
+    }
+
+    /// Call a `cmd` rpc with given `args` list, returning the result as raw
+    /// JSON.
+    pub fn call_raw(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<Box<serde_json::value::RawValue>> {
+        self.block_on(crate::RpcApi::call_raw(&self.inner, cmd, args))
+    }
+
+    /// Query an object implementing [Queryable], see [crate::RpcApi::get_by_id].
+    pub fn get_by_id<T: Queryable<C> + Sync + Send>(&self, id: &T::Id) -> Result<T> {
+        self.block_on(crate::RpcApi::get_by_id::<T>(&self.inner, id))
+    }
+
+    /// Send `request`, see the [requests](crate::requests) module.
+    pub fn request<R: RpcRequest>(&self, request: &R) -> Result<R::Response> {
+        self.block_on(crate::RpcApi::request(&self.inner, request))
+    }
+
+    /// Send `request`, keeping the raw JSON of the result.
+    pub fn request_raw<R: RpcRequest>(&self, request: &R) -> Result<Raw<R::Response>> {
+        self.block_on(crate::RpcApi::request_raw(&self.inner, request))
+    }
+
+    /// Make calls with the given timeout instead of the default one.
+    ///
+    /// See the [timeout](crate::timeout) module.
+    pub fn with_timeout(&self, timeout: Duration) -> Client<WithTimeout<'_, C>> {
+        Client {
+            inner: crate::RpcApi::with_timeout(&self.inner, timeout),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// A runtime for the calls of one client and those derived from it.
+fn runtime() -> Result<Runtime> {
+    Ok(tokio::runtime::Builder::new_current_thread().enable_all().build()?)
+}
+
+blocking_trait! {
+    /// The blocking version of [crate::RpcApi].
+    pub trait RpcApi: crate::RpcApi {
+        fn get_network_info(&self) -> Result<json::GetNetworkInfoResult>;
+        fn get_index_info(&self) -> Result<json::GetIndexInfoResult>;
+        fn version(&self) -> Result<usize>;
+        fn add_multisig_address(
+            &self,
+            nrequired: usize,
+            keys: &[json::PubKeyOrAddress],
+            label: Option<&str>,
+            address_type: Option<json::AddressType>,
+        ) -> Result<json::AddMultiSigAddressResult>;
+        fn load_wallet(&self, wallet: &str) -> Result<json::LoadWalletResult>;
+        fn unload_wallet(&self, wallet: Option<&str>) -> Result<Option<json::UnloadWalletResult>>;
+        fn create_wallet(
+            &self,
+            wallet: &str,
+            disable_private_keys: Option<bool>,
+            blank: Option<bool>,
+            passphrase: Option<&str>,
+            avoid_reuse: Option<bool>,
+        ) -> Result<json::LoadWalletResult>;
+        fn list_wallets(&self) -> Result<Vec<String>>;
+        fn list_wallet_dir(&self) -> Result<Vec<String>>;
+        fn get_wallet_info(&self) -> Result<json::GetWalletInfoResult>;
+        fn backup_wallet(&self, destination: Option<&str>) -> Result<()>;
+        fn dump_private_key(&self, address: &Address) -> Result<PrivateKey>;
+        fn encrypt_wallet(&self, passphrase: &str) -> Result<()>;
+        fn get_difficulty(&self) -> Result<f64>;
+        fn get_connection_count(&self) -> Result<usize>;
+        fn get_block(&self, hash: &bitcoin::BlockHash) -> Result<Block>;
+        fn get_block_hex(&self, hash: &bitcoin::BlockHash) -> Result<String>;
+        fn get_block_info(&self, hash: &bitcoin::BlockHash) -> Result<json::GetBlockResult>;
+        fn get_block_header(&self, hash: &bitcoin::BlockHash) -> Result<bitcoin::block::Header>;
+        fn get_block_header_info(
+            &self,
+            hash: &bitcoin::BlockHash,
+        ) -> Result<json::GetBlockHeaderResult>;
+        fn get_mining_info(&self) -> Result<json::GetMiningInfoResult>;
+        fn get_block_template(
+            &self,
+            mode: json::GetBlockTemplateModes,
+            rules: &[json::GetBlockTemplateRules],
+            capabilities: &[json::GetBlockTemplateCapabilities],
+        ) -> Result<json::GetBlockTemplateResult>;
+        fn get_blockchain_info(&self) -> Result<json::GetBlockchainInfoResult>;
+        fn get_block_count(&self) -> Result<u64>;
+        fn get_best_block_hash(&self) -> Result<bitcoin::BlockHash>;
+        fn get_block_hash(&self, height: u64) -> Result<bitcoin::BlockHash>;
+        fn get_block_stats(&self, height: u64) -> Result<json::GetBlockStatsResult>;
+        fn get_block_stats_fields(
+            &self,
+            height: u64,
+            fields: &[json::BlockStatsFields],
+        ) -> Result<json::GetBlockStatsResultPartial>;
+        fn get_raw_transaction(
+            &self,
+            txid: &bitcoin::Txid,
+            block_hash: Option<&bitcoin::BlockHash>,
+        ) -> Result<Transaction>;
+        fn get_raw_transaction_hex(
+            &self,
+            txid: &bitcoin::Txid,
+            block_hash: Option<&bitcoin::BlockHash>,
+        ) -> Result<String>;
+        fn get_raw_transaction_info(
+            &self,
+            txid: &bitcoin::Txid,
+            block_hash: Option<&bitcoin::BlockHash>,
+        ) -> Result<json::GetRawTransactionResult>;
+        fn get_block_filter(
+            &self,
+            block_hash: &bitcoin::BlockHash,
+        ) -> Result<json::GetBlockFilterResult>;
+        fn get_balance(
+            &self,
+            minconf: Option<usize>,
+            include_watchonly: Option<bool>,
+        ) -> Result<Amount>;
+        fn get_balances(&self) -> Result<json::GetBalancesResult>;
+        fn get_received_by_address(
+            &self,
+            address: &Address,
+            minconf: Option<u32>,
+        ) -> Result<Amount>;
+        fn get_transaction(
+            &self,
+            txid: &bitcoin::Txid,
+            include_watchonly: Option<bool>,
+        ) -> Result<json::GetTransactionResult>;
+        fn list_transactions(
+            &self,
+            label: Option<&str>,
+            count: Option<usize>,
+            skip: Option<usize>,
+            include_watchonly: Option<bool>,
+        ) -> Result<Vec<json::ListTransactionResult>>;
+        fn list_since_block(
+            &self,
+            blockhash: Option<&bitcoin::BlockHash>,
+            target_confirmations: Option<usize>,
+            include_watchonly: Option<bool>,
+            include_removed: Option<bool>,
+        ) -> Result<json::ListSinceBlockResult>;
+        fn get_tx_out(
+            &self,
+            txid: &bitcoin::Txid,
+            vout: u32,
+            include_mempool: Option<bool>,
+        ) -> Result<Option<json::GetTxOutResult>>;
+        fn get_tx_out_proof(
+            &self,
+            txids: &[bitcoin::Txid],
+            block_hash: Option<&bitcoin::BlockHash>,
+        ) -> Result<Vec<u8>>;
+        fn import_public_key(
+            &self,
+            pubkey: &PublicKey,
+            label: Option<&str>,
+            rescan: Option<bool>,
+        ) -> Result<()>;
+        fn import_private_key(
+            &self,
+            privkey: &PrivateKey,
+            label: Option<&str>,
+            rescan: Option<bool>,
+        ) -> Result<()>;
+        fn import_address(
+            &self,
+            address: &Address,
+            label: Option<&str>,
+            rescan: Option<bool>,
+        ) -> Result<()>;
+        fn import_address_script(
+            &self,
+            script: &Script,
+            label: Option<&str>,
+            rescan: Option<bool>,
+            p2sh: Option<bool>,
+        ) -> Result<()>;
+        fn import_multi(
+            &self,
+            requests: &[json::ImportMultiRequest],
+            options: Option<&json::ImportMultiOptions>,
+        ) -> Result<Vec<json::ImportMultiResult>>;
+        fn import_descriptors(
+            &self,
+            req: json::ImportDescriptors,
+        ) -> Result<Vec<json::ImportMultiResult>>;
+        fn set_label(&self, address: &Address, label: &str) -> Result<()>;
+        fn key_pool_refill(&self, new_size: Option<usize>) -> Result<()>;
+        fn list_unspent(
+            &self,
+            minconf: Option<usize>,
+            maxconf: Option<usize>,
+            addresses: Option<&[&Address<NetworkChecked>]>,
+            include_unsafe: Option<bool>,
+            query_options: Option<json::ListUnspentQueryOptions>,
+        ) -> Result<Vec<json::ListUnspentResultEntry>>;
+        fn lock_unspent(&self, outputs: &[OutPoint]) -> Result<bool>;
+        fn unlock_unspent(&self, outputs: &[OutPoint]) -> Result<bool>;
+        fn unlock_unspent_all(&self) -> Result<bool>;
+        fn list_received_by_address(
+            &self,
+            address_filter: Option<&Address>,
+            minconf: Option<u32>,
+            include_empty: Option<bool>,
+            include_watchonly: Option<bool>,
+        ) -> Result<Vec<json::ListReceivedByAddressResult>>;
+        fn create_psbt(
+            &self,
+            inputs: &[json::CreateRawTransactionInput],
+            outputs: &HashMap<String, Amount>,
+            locktime: Option<i64>,
+            replaceable: Option<bool>,
+        ) -> Result<String>;
+        fn create_raw_transaction_hex(
+            &self,
+            utxos: &[json::CreateRawTransactionInput],
+            outs: &HashMap<String, Amount>,
+            locktime: Option<i64>,
+            replaceable: Option<bool>,
+        ) -> Result<String>;
+        fn create_raw_transaction(
+            &self,
+            utxos: &[json::CreateRawTransactionInput],
+            outs: &HashMap<String, Amount>,
+            locktime: Option<i64>,
+            replaceable: Option<bool>,
+        ) -> Result<Transaction>;
+        fn decode_raw_transaction<R: RawTx>(
+            &self,
+            tx: R,
+            is_witness: Option<bool>,
+        ) -> Result<json::DecodeRawTransactionResult>;
+        fn fund_raw_transaction<R: RawTx>(
+            &self,
+            tx: R,
+            options: Option<&json::FundRawTransactionOptions>,
+            is_witness: Option<bool>,
+        ) -> Result<json::FundRawTransactionResult>;
+        #[deprecated]
+        fn sign_raw_transaction<R: RawTx>(
+            &self,
+            tx: R,
+            utxos: Option<&[json::SignRawTransactionInput]>,
+            private_keys: Option<&[PrivateKey]>,
+            sighash_type: Option<json::SigHashType>,
+        ) -> Result<json::SignRawTransactionResult>;
+        fn sign_raw_transaction_with_wallet<R: RawTx>(
+            &self,
+            tx: R,
+            utxos: Option<&[json::SignRawTransactionInput]>,
+            sighash_type: Option<json::SigHashType>,
+        ) -> Result<json::SignRawTransactionResult>;
+        fn sign_raw_transaction_with_key<R: RawTx>(
+            &self,
+            tx: R,
+            privkeys: &[PrivateKey],
+            prevtxs: Option<&[json::SignRawTransactionInput]>,
+            sighash_type: Option<json::SigHashType>,
+        ) -> Result<json::SignRawTransactionResult>;
+        fn test_mempool_accept<R: RawTx + Send + Sync>(
+            &self,
+            rawtxs: &[R],
+            max_fee_rate: Option<u64>,
+        ) -> Result<Vec<json::TestMempoolAcceptResult>>;
+        fn stop(&self) -> Result<String>;
+        fn verify_message(
+            &self,
+            address: &Address,
+            signature: &Signature,
+            message: &str,
+        ) -> Result<bool>;
+        fn get_new_address(
+            &self,
+            label: Option<&str>,
+            address_type: Option<json::AddressType>,
+        ) -> Result<Address<NetworkUnchecked>>;
+        fn get_raw_change_address(
+            &self,
+            address_type: Option<json::AddressType>,
+        ) -> Result<Address<NetworkUnchecked>>;
+        fn get_address_info(&self, address: &Address) -> Result<json::GetAddressInfoResult>;
+        fn generate_to_address(
+            &self,
+            block_num: u64,
+            address: &Address<NetworkChecked>,
+        ) -> Result<Vec<bitcoin::BlockHash>>;
+        fn generate(
+            &self,
+            block_num: u64,
+            maxtries: Option<u64>,
+        ) -> Result<Vec<bitcoin::BlockHash>>;
+        fn invalidate_block(&self, block_hash: &bitcoin::BlockHash) -> Result<()>;
+        fn reconsider_block(&self, block_hash: &bitcoin::BlockHash) -> Result<()>;
+        fn get_mempool_info(&self) -> Result<json::GetMempoolInfoResult>;
+        fn get_raw_mempool(&self) -> Result<Vec<bitcoin::Txid>>;
+        fn get_raw_mempool_verbose(
+            &self,
+        ) -> Result<HashMap<bitcoin::Txid, json::GetMempoolEntryResult>>;
+        fn get_mempool_entry(&self, txid: &bitcoin::Txid) -> Result<json::GetMempoolEntryResult>;
+        fn get_chain_tips(&self) -> Result<json::GetChainTipsResult>;
+        fn send_to_address(
+            &self,
+            address: &Address<NetworkChecked>,
+            amount: Amount,
+            comment: Option<&str>,
+            comment_to: Option<&str>,
+            subtract_fee: Option<bool>,
+            replaceable: Option<bool>,
+            confirmation_target: Option<u32>,
+            estimate_mode: Option<json::EstimateMode>,
+        ) -> Result<bitcoin::Txid>;
+        fn add_node(&self, addr: &str) -> Result<()>;
+        fn remove_node(&self, addr: &str) -> Result<()>;
+        fn onetry_node(&self, addr: &str) -> Result<()>;
+        fn disconnect_node(&self, addr: &str) -> Result<()>;
+        fn disconnect_node_by_id(&self, node_id: u32) -> Result<()>;
+        fn get_added_node_info(
+            &self,
+            node: Option<&str>,
+        ) -> Result<Vec<json::GetAddedNodeInfoResult>>;
+        fn get_node_addresses(
+            &self,
+            count: Option<usize>,
+        ) -> Result<Vec<json::GetNodeAddressesResult>>;
+        fn list_banned(&self) -> Result<Vec<json::ListBannedResult>>;
+        fn clear_banned(&self) -> Result<()>;
+        fn add_ban(&self, subnet: &str, bantime: u64, absolute: bool) -> Result<()>;
+        fn remove_ban(&self, subnet: &str) -> Result<()>;
+        fn set_network_active(&self, state: bool) -> Result<bool>;
+        fn get_peer_info(&self) -> Result<Vec<json::GetPeerInfoResult>>;
+        fn ping(&self) -> Result<()>;
+        fn send_raw_transaction<R: RawTx>(
+            &self,
+            tx: R,
+            max_fee_rate: Option<u64>,
+        ) -> Result<bitcoin::Txid>;
+        fn estimate_smart_fee(
+            &self,
+            conf_target: u16,
+            estimate_mode: Option<json::EstimateMode>,
+        ) -> Result<json::EstimateSmartFeeResult>;
+        fn wait_for_new_block(&self, timeout: u64) -> Result<json::BlockRef>;
+        fn wait_for_block(
+            &self,
+            blockhash: &bitcoin::BlockHash,
+            timeout: u64,
+        ) -> Result<json::BlockRef>;
+        fn wallet_create_funded_psbt(
+            &self,
+            inputs: &[json::CreateRawTransactionInput],
+            outputs: &HashMap<String, Amount>,
+            locktime: Option<i64>,
+            options: Option<json::WalletCreateFundedPsbtOptions>,
+            bip32derivs: Option<bool>,
+        ) -> Result<json::WalletCreateFundedPsbtResult>;
+        fn wallet_process_psbt(
+            &self,
+            psbt: &str,
+            sign: Option<bool>,
+            sighash_type: Option<json::SigHashType>,
+            bip32derivs: Option<bool>,
+        ) -> Result<json::WalletProcessPsbtResult>;
+        fn get_descriptor_info(&self, desc: &str) -> Result<json::GetDescriptorInfoResult>;
+        fn join_psbt(&self, psbts: &[String]) -> Result<String>;
+        fn combine_psbt(&self, psbts: &[String]) -> Result<String>;
+        fn combine_raw_transaction(&self, hex_strings: &[String]) -> Result<String>;
+        fn finalize_psbt(
+            &self,
+            psbt: &str,
+            extract: Option<bool>,
+        ) -> Result<json::FinalizePsbtResult>;
+        fn derive_addresses(
+            &self,
+            descriptor: &str,
+            range: Option<[u32; 2]>,
+        ) -> Result<Vec<Address<NetworkUnchecked>>>;
+        fn rescan_blockchain(
+            &self,
+            start_from: Option<usize>,
+            stop_height: Option<usize>,
+        ) -> Result<(usize, Option<usize>)>;
+        fn get_tx_out_set_info(
+            &self,
+            hash_type: Option<json::TxOutSetHashType>,
+            hash_or_height: Option<json::HashOrHeight>,
+            use_index: Option<bool>,
+        ) -> Result<json::GetTxOutSetInfoResult>;
+        fn get_net_totals(&self) -> Result<json::GetNetTotalsResult>;
+        fn get_network_hash_ps(&self, nblocks: Option<u64>, height: Option<u64>) -> Result<f64>;
+        fn uptime(&self) -> Result<u64>;
+        fn submit_block(&self, block: &bitcoin::Block) -> Result<()>;
+        fn submit_block_bytes(&self, block_bytes: &[u8]) -> Result<()>;
+        fn submit_block_hex(&self, block_hex: &str) -> Result<()>;
+        fn scan_tx_out_set_blocking(
+            &self,
+            descriptors: &[json::ScanTxOutRequest],
+        ) -> Result<json::ScanTxOutResult>;
+    }
+}
@@ -0,0 +1,35 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::{bitcoin::Txid, governance::*};
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::GovernanceRPC].
+    pub trait GovernanceRPC: crate::GovernanceRPC {
+        fn create_gov_cfp(&self, data: CFPData, utxos: Option<UTXO>) -> Result<String>;
+        fn create_gov_voc(&self, data: VOCData, utxos: Option<UTXO>) -> Result<String>;
+        fn get_gov_proposal(&self, proposal_id: Txid) -> Result<ProposalInfo>;
+        fn get_gov_proposal_fees(&self) -> Result<ProposalFees>;
+        fn list_gov_proposal_votes(
+            &self,
+            options: Option<ListGovProposalVotesOptions>,
+        ) -> Result<Vec<ListVotesResult>>;
+        fn list_gov_proposals(
+            &self,
+            opts: Option<ListProposalsOptions>,
+        ) -> Result<Vec<ProposalInfo>>;
+        fn vote_gov(&self, data: VoteData, utxos: Option<UTXO>) -> Result<String>;
+        fn vote_gov_batch(&self, votes: Vec<VoteData>, sleep_time: Option<u64>) -> Result<Vec<String>>;
+    }
+}
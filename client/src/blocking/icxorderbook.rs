@@ -0,0 +1,36 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::icxorderbook::*;
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::ICXOrderBookRPC].
+    pub trait ICXOrderBookRPC: crate::ICXOrderBookRPC {
+        fn claim_dfchtlc(
+            &self,
+            dfchtlc_tx: String,
+            seed: String,
+            utxos: Option<UTXO>,
+        ) -> Result<ICXGenericResult>;
+        fn close_offer(&self, offer_tx: String, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+        fn close_order(&self, order_tx: String, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+        fn create_order(&self, order: ICXOrder, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+        fn get_order(&self, order_tx: String) -> Result<ICXOrders>;
+        fn list_htlcs(&self, options: ICXListHTLCOptions) -> Result<ICXHTLCs>;
+        fn list_orders(&self, options: Option<ICXListOrderOptions>) -> Result<ICXOrders>;
+        fn make_offer(&self, offer: ICXOffer, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+        fn submit_dfchtlc(&self, htlc: HTLC, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+        fn submit_ext_htlc(&self, htlc: ExtHTLC, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+    }
+}
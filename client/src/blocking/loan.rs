@@ -0,0 +1,60 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::common::{Interest, UTXO};
+use defichain_rpc_json::loan::*;
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::LoanRPC].
+    pub trait LoanRPC: crate::LoanRPC {
+        fn create_loan_scheme(
+            &self,
+            scheme: CreateLoanScheme,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn destroy_loan_scheme(
+            &self,
+            scheme: DestroyLoanScheme,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn get_collateral_token(&self, token: String) -> Result<CollateralTokenDetail>;
+        fn get_interest(&self, id: String, token: Option<String>) -> Result<Vec<Interest>>;
+        fn get_loan_info(&self) -> Result<GetLoanInfoResult>;
+        fn get_loan_scheme(&self, id: String) -> Result<LoanSchemeResult>;
+        fn get_loan_token(&self, token: String) -> Result<LoanTokenResult>;
+        fn list_collateral_tokens(&self) -> Result<Vec<CollateralTokenDetail>>;
+        fn list_loan_schemes(&self) -> Result<Vec<LoanSchemeResult>>;
+        fn list_loan_tokens(&self) -> Result<Vec<LoanTokenResult>>;
+        fn payback_loan(&self, utxos: Option<UTXO>) -> Result<String>;
+        fn payback_with_collateral(&self, vault_id: String) -> Result<String>;
+        fn set_collateral_token(
+            &self,
+            collateral_token: SetCollateralToken,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn set_default_loan_scheme(&self, id: String, utxos: Option<UTXO>) -> Result<String>;
+        fn set_loan_token(&self, loan_token: SetLoanToken, utxos: Option<UTXO>) -> Result<String>;
+        fn take_loan(&self, metadata: TakeLoanMetadata, utxos: Option<UTXO>) -> Result<String>;
+        fn update_loan_scheme(
+            &self,
+            scheme: UpdateLoanScheme,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn update_loan_token(
+            &self,
+            old_token: String,
+            new_token_details: UpdateLoanToken,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+    }
+}
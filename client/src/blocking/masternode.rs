@@ -0,0 +1,66 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use std::collections::HashMap;
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::masternode::*;
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::MasternodeRPC].
+    pub trait MasternodeRPC: crate::MasternodeRPC {
+        fn clear_mempool(&self) -> Result<Vec<String>>;
+        fn create_masternode(
+            &self,
+            owner_address: String,
+            operator_address: Option<String>,
+            utxos: Option<UTXO>,
+            timelock: Option<MasternodeTimeLock>,
+        ) -> Result<String>;
+        fn get_active_masternode_count(&self, block_count: Option<u64>) -> Result<u64>;
+        fn get_anchor_teams(&self, block_height: Option<u64>) -> Result<AnchorTeamResult>;
+        fn get_gov(&self, name: String) -> Result<HashMap<String, serde_json::Value>>;
+        fn get_masternode(&self, masternode_id: String) -> Result<MasternodeResult>;
+        fn get_masternode_blocks(
+            &self,
+            identifier: MasternodeBlock,
+            depth: Option<u64>,
+        ) -> Result<HashMap<String, String>>;
+        fn is_applied_custom_transaction(
+            &self,
+            transaction_id: String,
+            block_height: u64,
+        ) -> Result<bool>;
+        fn list_govs(&self, prefix: Option<String>) -> Result<Vec<GovVariable>>;
+        fn list_masternodes(
+            &self,
+            pagination: Option<MasternodePagination>,
+            verbose: Option<bool>,
+        ) -> Result<ListMasternodesResult>;
+        fn resign_masternode(&self, masternode_id: String, utxos: Option<UTXO>) -> Result<String>;
+        fn set_gov(&self, variables: HashMap<String, serde_json::Value>, utxos: Option<UTXO>) -> Result<String>;
+        fn set_gov_height(
+            &self,
+            variables: HashMap<String, serde_json::Value>,
+            activation_height: u64,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn unset_gov(&self, variables: HashMap<String, Vec<String>>, utxos: Option<UTXO>) -> Result<String>;
+        fn update_masternode(
+            &self,
+            masternode_id: String,
+            values: UpdateMasternodeValues,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+    }
+}
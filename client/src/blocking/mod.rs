@@ -0,0 +1,124 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! A blocking client, for programs that don't use an async runtime.
+//!
+//! Requires the `blocking` feature. [Client] wraps an async client with a
+//! runtime of its own, and the traits of this module provide the methods of
+//! [RpcApi](crate::RpcApi) and the DeFi traits with the same names and
+//! arguments, but synchronously:
+//!
+//! ```no_run
+//! use defichain_rpc::blocking::{Client, RpcApi, TokenRPC};
+//! use defichain_rpc::Auth;
+//!
+//! let client = Client::new("http://localhost:8554", Auth::CookieFile("/root/.defi/.cookie".into()))?;
+//! let best_block_hash = client.get_best_block_hash()?;
+//! let tokens = client.list_tokens(None, Some(true))?;
+//! # Ok::<(), defichain_rpc::Error>(())
+//! ```
+//!
+//! Any async [RpcApi](crate::RpcApi) implementation can be wrapped with
+//! [Client::from_async], for instance a [RetryClient](crate::RetryClient).
+
+/// Define a blocking trait with the given methods of an async trait, and
+/// implement it for [Client] by running the async methods on its runtime.
+macro_rules! blocking_trait {
+    (
+        $(#[$attr:meta])*
+        pub trait $name:ident: $async:path {
+            $(
+                $(#[$method_attr:meta])*
+                fn $method:ident $(<$gen:ident: $bound:ident $(+ $more:ident)*>)?(
+                    &self $(, $arg:ident: $ty:ty)* $(,)?
+                ) -> $ret:ty;
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        #[allow(clippy::too_many_arguments)]
+        pub trait $name {
+            $(
+                $(#[$method_attr])*
+                fn $method $(<$gen: $bound $(+ $more)*>)?(&self $(, $arg: $ty)*) -> $ret;
+            )*
+        }
+
+        // The methods have the arguments of the async ones, and deprecated
+        // ones are still forwarded.
+        #[allow(clippy::too_many_arguments, deprecated)]
+        impl<C: crate::RpcApi + Sync> $name for Client<C> {
+            $(
+                fn $method $(<$gen: $bound $(+ $more)*>)?(&self $(, $arg: $ty)*) -> $ret {
+                    self.block_on(<C as $async>::$method(self.get_ref() $(, $arg)*))
+                }
+            )*
+        }
+    };
+}
+
+mod account;
+mod blockchain;
+mod client;
+mod governance;
+mod icxorderbook;
+mod loan;
+mod masternode;
+mod oracle;
+mod poolpair;
+mod spv;
+mod token;
+mod vault;
+
+pub use self::account::AccountRPC;
+pub use self::blockchain::BlockchainRPC;
+pub use self::client::{Client, RpcApi};
+pub use self::governance::GovernanceRPC;
+pub use self::icxorderbook::ICXOrderBookRPC;
+pub use self::loan::LoanRPC;
+pub use self::masternode::MasternodeRPC;
+pub use self::oracle::OracleRPC;
+pub use self::poolpair::PoolPairRPC;
+pub use self::spv::SpvRPC;
+pub use self::token::TokenRPC;
+pub use self::vault::VaultRPC;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockNode;
+    use crate::Error;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn blocks_on_calls() {
+        let client = Client::from_async(MockNode::new()).unwrap();
+        client.get_ref().state().add_token("BTC");
+        assert_eq!(RpcApi::get_block_count(&client).unwrap(), 0);
+
+        let tokens = client.list_tokens(None, Some(true)).unwrap();
+        assert_eq!(tokens.0["1"].symbol, "BTC");
+        client.get_ref().assert_last_request("listtokens", json!([{}, true]));
+
+        let hash = BlockchainRPC::get_block_hash(&client.with_timeout(Duration::from_secs(1)), 0);
+        assert_eq!(hash.unwrap(), BlockchainRPC::get_best_block_hash(&client).unwrap());
+        let err = client.call::<u64>("nosuchmethod", &[]).unwrap_err();
+        assert!(matches!(err.root(), Error::JsonRpc(_)));
+
+        let client = Client::from_async(MockNode::new().respond("gettxout", json!(null))).unwrap();
+        let outpoint = crate::bitcoin::OutPoint::default();
+        let txout = client.get_by_id::<Option<crate::json::GetTxOutResult>>(&outpoint).unwrap();
+        assert!(txout.is_none());
+        client
+            .get_ref()
+            .assert_last_request("gettxout", json!([outpoint.txid, outpoint.vout, true]));
+    }
+}
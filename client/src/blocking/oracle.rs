@@ -0,0 +1,59 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::oracle::*;
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::OracleRPC].
+    pub trait OracleRPC: crate::OracleRPC {
+        fn appoint_oracle(
+            &self,
+            address: String,
+            price_feeds: Vec<OraclePriceFeed>,
+            weightage: u8,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn get_fixed_interval_price(&self, id: String) -> Result<FixedIntervalPrice>;
+        fn get_future_swap_block(&self) -> Result<u64>;
+        fn get_oracle_data(&self, oracle_id: String) -> Result<OracleData>;
+        fn get_price(&self, price_feed: OraclePriceFeed) -> Result<f64>;
+        fn list_fixed_interval_prices(
+            &self,
+            pagination: Option<FixedIntervalPricePagination>,
+        ) -> Result<Vec<ListFixedIntervalPrice>>;
+        fn list_latest_raw_prices(
+            &self,
+            price_feed: Option<OraclePriceFeed>,
+            pagination: Option<OraclePagination>,
+        ) -> Result<Vec<OracleRawPrice>>;
+        fn list_oracles(&self, pagination: Option<OraclePagination>) -> Result<Vec<String>>;
+        fn list_prices(&self, pagination: Option<ListPricesPagination>) -> Result<Vec<ListPricesData>>;
+        fn remove_oracle(&self, oracle_id: String, utxos: Option<UTXO>) -> Result<String>;
+        fn set_oracle_data(
+            &self,
+            oracle_id: String,
+            timestamp: u64,
+            prices: Vec<OraclePrice>,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn update_oracle(
+            &self,
+            oracle_id: String,
+            address: String,
+            price_feeds: Vec<OraclePriceFeed>,
+            weightage: u8,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+    }
+}
@@ -0,0 +1,58 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::poolpair::*;
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::PoolPairRPC].
+    pub trait PoolPairRPC: crate::PoolPairRPC {
+        fn add_pool_liquidity(
+            &self,
+            from: AddPoolLiquiditySource,
+            share_address: String,
+            options: PoolLiquidityOptions,
+        ) -> Result<String>;
+        fn composite_swap(&self, metadata: PoolSwapMetadata, utxos: Option<UTXO>) -> Result<String>;
+        fn create_pool_pair(
+            &self,
+            metadata: CreatePoolPairMetadata,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn get_pool_pair(&self, symbol: String, verbose: Option<bool>) -> Result<PoolPairsResult>;
+        fn list_pool_pairs(
+            &self,
+            pagination: Option<PoolPairPagination>,
+            verbose: Option<bool>,
+        ) -> Result<PoolPairsResult>;
+        fn list_pool_shares(
+            &self,
+            pagination: PoolPairPagination,
+            verbose: Option<bool>,
+            options: PoolShareOptions,
+        ) -> Result<PoolSharesResult>;
+        fn pool_swap(&self, metadata: PoolSwapMetadata, utxos: Option<UTXO>) -> Result<String>;
+        fn remove_pool_liquidity(
+            &self,
+            address: String,
+            pool_account: String,
+            options: PoolLiquidityOptions,
+        ) -> Result<String>;
+        fn test_pool_swap(&self, metadata: PoolSwapMetadata) -> Result<String>;
+        fn update_pool_pair(
+            &self,
+            metadata: UpdatePoolPairMetadata,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+    }
+}
@@ -0,0 +1,68 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::spv::*;
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::SpvRPC].
+    pub trait SpvRPC: crate::SpvRPC {
+        fn claim_htlc(
+            &self,
+            script_address: String,
+            destination_address: String,
+            options: ClaimHtlcOptions,
+        ) -> Result<SendMessageResult>;
+        fn create_anchor(
+            &self,
+            inputs: Vec<CreateAnchorInput>,
+            reward_address: String,
+            options: Option<CreateAnchorOptions>,
+        ) -> Result<CreateAnchorResult>;
+        fn create_htlc(
+            &self,
+            receiver_pub_key: String,
+            owner_pub_key: String,
+            options: CreateHtlcOptions,
+        ) -> Result<CreateHtlcResult>;
+        fn decode_htlc_script(&self, redeem_script: String) -> Result<DecodeHtlcResult>;
+        fn get_address_pub_key(&self, address: String) -> Result<String>;
+        fn get_htlc_seed(&self, address: String) -> Result<String>;
+        fn get_new_address(&self) -> Result<String>;
+        fn list_anchor_auths(&self) -> Result<Vec<ListAnchorAuthsResult>>;
+        fn list_anchor_reward_confirms(&self) -> Result<Vec<ListAnchorRewardConfirmsResult>>;
+        fn list_anchor_rewards(&self) -> Result<Vec<ListAnchorRewardsResult>>;
+        fn list_anchors(&self, options: Option<ListAnchorsOptions>) -> Result<Vec<ListAnchorsResult>>;
+        fn list_anchors_pending(&self) -> Result<Vec<ListAnchorsResult>>;
+        fn list_anchors_unrewarded(&self) -> Result<Vec<ListAnchorsResult>>;
+        fn list_htlc_outputs(&self, script_address: Option<String>) -> Result<Vec<ListHtlcsOutputsResult>>;
+        fn list_received_by_address(
+            &self,
+            min_confirmations: Option<u32>,
+            address: Option<String>,
+        ) -> Result<Vec<ReceivedByAddressInfo>>;
+        fn refund_htlc(
+            &self,
+            script_address: String,
+            destination_address: String,
+            fee_rate: Option<u64>,
+        ) -> Result<SendMessageResult>;
+        fn refund_htlc_all(&self, destination_address: String, fee_rate: Option<u64>) -> Result<Vec<String>>;
+        fn send_to_address(
+            &self,
+            address: String,
+            amount: f64,
+            fee_rate: Option<u64>,
+        ) -> Result<SendMessageResult>;
+        fn set_last_height(&self, height: u32) -> Result<()>;
+    }
+}
@@ -0,0 +1,46 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::{common::UTXO, token::*};
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::TokenRPC].
+    pub trait TokenRPC: crate::TokenRPC {
+        fn burn_tokens(
+            &self,
+            amounts: String,
+            from: Option<String>,
+            context: Option<String>,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn create_token(
+            &self,
+            metadata: CreateTokenMetadata,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn decode_custom_tx(&self, hexstring: String, iswitness: Option<bool>) -> Result<()>;
+        fn get_custom_tx(&self, txid: String, blockhash: Option<String>) -> Result<()>;
+        fn get_token(&self, symbol_key: &str) -> Result<TokenResult>;
+        fn list_tokens(
+            &self,
+            pagination: Option<TokenPagination>,
+            verbose: Option<bool>,
+        ) -> Result<TokenResult>;
+        fn mint_tokens(&self, options: MintTokensOptions) -> Result<String>;
+        fn update_token(
+            &self,
+            token: String,
+            metadata: Option<UpdateTokenMetadata>,
+        ) -> Result<String>;
+    }
+}
@@ -0,0 +1,70 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use defichain_rpc_json::{common::UTXO, vault::*};
+
+use super::Client;
+use crate::Result;
+
+blocking_trait! {
+    /// The blocking version of [crate::VaultRPC].
+    pub trait VaultRPC: crate::VaultRPC {
+        fn close_vault(&self, close_vault: CloseVault, utxos: Option<UTXO>) -> Result<String>;
+        fn create_vault(&self, vault: CreateVault, utxos: Option<UTXO>) -> Result<String>;
+        fn deposit_to_vault(
+            &self,
+            deposit_vault: DepositVault,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn estimate_collateral(
+            &self,
+            target_ratio: u64,
+            token_split: TokenPercentageSplit,
+        ) -> Result<()>;
+        fn estimate_loan(
+            &self,
+            vault_id: String,
+            token_split: TokenPercentageSplit,
+            target_ratio: Option<u64>,
+        ) -> Result<()>;
+        fn estimate_vault(&self) -> Result<VaultEstimation>;
+        fn get_vault(&self, vault_id: String, verbose: Option<bool>) -> Result<VaultResult>;
+        fn list_auction_history(
+            &self,
+            owner: Option<String>,
+            pagination: Option<ListAuctionHistoryPagination>,
+        ) -> Result<Vec<ListAuctionHistoryDetail>>;
+        fn list_auctions(
+            &self,
+            pagination: Option<AuctionPagination>,
+        ) -> Result<Vec<VaultLiquidation>>;
+        fn list_vaults(
+            &self,
+            options: ListVaultOptions,
+            pagination: VaultPagination,
+        ) -> Result<Vec<VaultResult>>;
+        fn place_auction_bid(
+            &self,
+            place_auction_bid: PlaceAuctionBid,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn update_vault(
+            &self,
+            vault_id: String,
+            vault: UpdateVault,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+        fn withdraw_from_vault(
+            &self,
+            withdraw_vault: WithdrawVault,
+            utxos: Option<UTXO>,
+        ) -> Result<String>;
+    }
+}
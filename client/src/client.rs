@@ -13,6 +13,8 @@ use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::iter::FromIterator;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use std::{fmt, result};
 
 use crate::bitcoin::address::{NetworkChecked, NetworkUnchecked};
@@ -31,8 +33,13 @@ use serde::{self, Serialize};
 use serde_json::{self, Map};
 
 use crate::error::*;
+use crate::http::HttpConfig;
 use crate::json;
 use crate::queryable;
+use crate::raw::Raw;
+use crate::requests::RpcRequest;
+use crate::timeout::WithTimeout;
+use crate::transport::{self, Connector, Transport};
 
 /// Crate-specific Result type, shorthand for `std::result::Result` with our
 /// crate-specific Error type;
@@ -208,6 +215,10 @@ impl RawTx for String {
 pub enum Auth {
     None,
     UserPass(String, String),
+    /// Read the credentials from the cookie file of the node.
+    ///
+    /// The file is read again when the node rejects the credentials, for
+    /// instance after it restarted and rotated the cookie.
     CookieFile(PathBuf),
 }
 
@@ -238,6 +249,35 @@ pub trait RpcApi: Sized {
         args: &[serde_json::Value],
     ) -> Result<T>;
 
+    /// Call a `cmd` rpc with given `args` list, returning the result as raw
+    /// JSON.
+    ///
+    /// See [Raw](crate::Raw) for keeping the raw JSON next to a typed result.
+    async fn call_raw(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<Box<serde_json::value::RawValue>> {
+        self.call(cmd, args).await
+    }
+
+    /// Send `request`, see the [requests](crate::requests) module.
+    async fn request<R: RpcRequest>(&self, request: &R) -> Result<R::Response> {
+        self.call(R::METHOD, &request.params()?).await
+    }
+
+    /// Send `request`, keeping the raw JSON of the result.
+    async fn request_raw<R: RpcRequest>(&self, request: &R) -> Result<Raw<R::Response>> {
+        self.call(R::METHOD, &request.params()?).await
+    }
+
+    /// Make calls with the given timeout instead of the default one.
+    ///
+    /// See the [timeout](crate::timeout) module.
+    fn with_timeout(&self, timeout: Duration) -> WithTimeout<'_, Self> {
+        WithTimeout::new(self, timeout)
+    }
+
     /// Query an object implementing `Querable` type
     async fn get_by_id<T: queryable::Queryable<Self> + Sync + Send>(
         &self,
@@ -1321,6 +1361,12 @@ pub trait RpcApi: Sized {
 /// Client implements a JSON-RPC client for the Bitcoin Core daemon or compatible APIs.
 pub struct Client {
     client: jsonrpc_async::client::Client,
+    /// The default timeout of calls.
+    timeout: Duration,
+    /// The connector that created the transport, if any.
+    connector: Option<Arc<dyn Connector>>,
+    /// The wallet the calls are scoped to.
+    wallet: Option<String>,
 }
 
 impl fmt::Debug for Client {
@@ -1330,23 +1376,48 @@ impl fmt::Debug for Client {
 }
 
 impl Client {
+    /// The default timeout of calls, see [Client::set_timeout].
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+
     /// Creates a client to a bitcoind JSON-RPC server.
     ///
     /// Can only return [Err] when using cookie authentication.
     pub async fn new(url: &str, auth: Auth) -> Result<Self> {
-        let (user, pass) = auth.get_user_pass()?;
-        jsonrpc_async::client::Client::simple_http(url, user, pass)
-            .await
-            .map(|client| Client {
-                client,
-            })
-            .map_err(|e| super::error::Error::JsonRpc(e.into()))
+        Ok(Client::with_connector(HttpConfig::new(url, auth).await?))
+    }
+
+    /// Create a client using the transports of `connector`.
+    ///
+    /// See the [transport] module.
+    pub fn with_connector<C: Connector>(connector: C) -> Client {
+        let connector: Arc<dyn Connector> = Arc::new(connector);
+        Client {
+            client: transport::jsonrpc_client(&*connector, Client::DEFAULT_TIMEOUT, None),
+            timeout: Client::DEFAULT_TIMEOUT,
+            connector: Some(connector),
+            wallet: None,
+        }
+    }
+
+    /// Create a client using `transport`.
+    ///
+    /// The timeout of the client is enforced on top of the timeout of the
+    /// transport, so calls can't take longer than either. Use
+    /// [Client::with_connector] for clients that can be scoped to a wallet.
+    pub fn with_transport<T: Transport>(transport: T) -> Client {
+        Client::from_jsonrpc(jsonrpc_async::client::Client::with_transport(transport))
     }
 
     /// Create a new Client using the given [jsonrpc_async::Client].
+    ///
+    /// The timeout of the client is enforced on top of the timeout of its
+    /// transport, so calls can't take longer than either.
     pub fn from_jsonrpc(client: jsonrpc_async::client::Client) -> Client {
         Client {
             client,
+            timeout: Client::DEFAULT_TIMEOUT,
+            connector: None,
+            wallet: None,
         }
     }
 
@@ -1354,6 +1425,87 @@ impl Client {
     pub fn get_jsonrpc_client(&self) -> &jsonrpc_async::client::Client {
         &self.client
     }
+
+    /// The default timeout of calls.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Set the default timeout of calls, which is [Client::DEFAULT_TIMEOUT]
+    /// unless changed.
+    ///
+    /// Use [RpcApi::with_timeout] to override it for single calls.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+        if let Some(ref connector) = self.connector {
+            self.client = transport::jsonrpc_client(&**connector, timeout, self.wallet.as_deref());
+        }
+    }
+
+    /// Create a client that sends its calls to the endpoint of the loaded
+    /// wallet `name`, for nodes with more than one wallet loaded.
+    ///
+    /// The client shares the connection settings and credentials with this
+    /// one, and uses the same default timeout.
+    ///
+    /// ```no_run
+    /// # async fn example(client: &defichain_rpc::Client) -> defichain_rpc::Result<()> {
+    /// use defichain_rpc::RpcApi;
+    ///
+    /// client.load_wallet("savings").await?;
+    /// let savings = client.wallet("savings")?;
+    /// let balance = savings.get_balance(None, None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Returns [Err] for clients created with [Client::from_jsonrpc] or
+    /// [Client::with_transport], whose transport can't be scoped to a wallet.
+    pub fn wallet(&self, name: &str) -> Result<Client> {
+        let connector = self.connector.as_ref().ok_or_else(|| {
+            Error::Custom("only clients with a connector can be scoped to a wallet".into())
+        })?;
+        Ok(Client {
+            client: transport::jsonrpc_client(&**connector, self.timeout, Some(name)),
+            timeout: self.timeout,
+            connector: Some(connector.clone()),
+            wallet: Some(name.to_owned()),
+        })
+    }
+
+    /// The wallet the calls are scoped to, see [Client::wallet].
+    pub fn wallet_name(&self) -> Option<&str> {
+        self.wallet.as_deref()
+    }
+
+    /// Send a request, within the timeout set for the call.
+    async fn send_request(
+        &self,
+        cmd: &str,
+        args: &[Box<serde_json::value::RawValue>],
+    ) -> Result<jsonrpc_async::Response> {
+        let timeout = crate::timeout::call_timeout().unwrap_or(self.timeout);
+        match self.connector {
+            // The transport would abort calls after the default timeout, so
+            // calls with a different one need their own.
+            Some(ref connector) if timeout != self.timeout => {
+                let client =
+                    transport::jsonrpc_client(&**connector, timeout, self.wallet.as_deref());
+                let req = client.build_request(cmd, args);
+                crate::timeout::timeout(timeout, async {
+                    client.send_request(req).await.map_err(Error::from)
+                })
+                .await
+            }
+            _ => {
+                let req = self.client.build_request(cmd, args);
+                crate::timeout::timeout(timeout, async {
+                    self.client.send_request(req).await.map_err(Error::from)
+                })
+                .await
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -1372,15 +1524,110 @@ impl RpcApi for Client {
             })
             .map(|a| a.map_err(|e| Error::Json(e)))
             .collect::<Result<Vec<_>>>()?;
-        let req = self.client.build_request(&cmd, &raw_args);
         if log_enabled!(Debug) {
-            debug!(target: "defichain_rpc", "JSON-RPC request: {} {}", cmd, serde_json::Value::from(args));
+            debug!(target: "defichain_rpc", "JSON-RPC request: {} {}", cmd, serde_json::Value::from(redact_params(cmd, args)));
         }
 
-        let resp = self.client.send_request(req).await.map_err(Error::from);
+        let resp = self.send_request(cmd, &raw_args).await;
         log_response(cmd, &resp);
-        Ok(resp?.result()?)
+        resp.and_then(|resp| parse_result(&resp)).map_err(|e| Error::Call {
+            method: cmd.to_owned(),
+            params: redact_params(cmd, args),
+            source: Box::new(e),
+        })
+    }
+}
+
+/// The parameters that hold secrets, by method.
+const SECRET_PARAMS: &[(&str, &[usize])] = &[
+    ("createwallet", &[3]),
+    ("encryptwallet", &[0]),
+    ("importmulti", &[0]),
+    ("importprivkey", &[0]),
+    ("sethdseed", &[1]),
+    ("signmessagewithprivkey", &[0]),
+    ("signrawtransactionwithkey", &[1]),
+    ("walletpassphrase", &[0]),
+    ("walletpassphrasechange", &[0, 1]),
+];
+
+/// The parameters of a call, with secrets replaced so they can be logged or
+/// kept in errors.
+pub(crate) fn redact_params(cmd: &str, args: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    let secret = SECRET_PARAMS.iter().find(|(m, _)| *m == cmd).map_or(&[][..], |(_, idx)| *idx);
+    args.iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            if secret.contains(&i) && !arg.is_null() {
+                serde_json::Value::from("<redacted>")
+            } else {
+                arg.clone()
+            }
+        })
+        .collect()
+}
+
+/// Extract the result from a response, with the location of the offending
+/// value if it can't be deserialized.
+fn parse_result<T: for<'a> serde::de::Deserialize<'a>>(
+    resp: &jsonrpc_async::Response,
+) -> Result<T> {
+    if let Some(ref e) = resp.error {
+        return Err(Error::JsonRpc(jsonrpc_async::Error::Rpc(e.clone())));
     }
+    parse_raw(resp.result.as_ref().map_or("null", |r| r.get()))
+}
+
+/// Deserialize a raw result, with the location of the offending value if it
+/// can't be deserialized.
+pub(crate) fn parse_raw<T: for<'a> serde::de::Deserialize<'a>>(raw: &str) -> Result<T> {
+    let mut de = serde_json::Deserializer::from_str(raw);
+    serde_path_to_error::deserialize(&mut de).map_err(|e| deserialize_error(raw, e))
+}
+
+/// Deserialize a result that was already parsed as JSON, with the location of
+/// the offending value if it can't be deserialized.
+pub(crate) fn parse_value<T: for<'a> serde::de::Deserialize<'a>>(
+    value: &serde_json::Value,
+) -> Result<T> {
+    parse_raw(&serde_json::to_string(value)?)
+}
+
+/// The error for a raw result that couldn't be deserialized.
+pub(crate) fn deserialize_error(
+    raw: &str,
+    e: serde_path_to_error::Error<serde_json::Error>,
+) -> Error {
+    let path = e.path().to_string();
+    let source = e.into_inner();
+    Error::Deserialize {
+        path,
+        snippet: snippet(raw, source.line(), source.column()),
+        source,
+    }
+}
+
+/// The part of `raw` around the given position, as reported by serde_json.
+fn snippet(raw: &str, line: usize, column: usize) -> String {
+    const CONTEXT: usize = 40;
+    let line = raw.lines().nth(line.saturating_sub(1)).unwrap_or(raw);
+    let mut start = column.saturating_sub(CONTEXT).min(line.len());
+    let mut end = (column + CONTEXT).min(line.len());
+    while !line.is_char_boundary(start) {
+        start -= 1;
+    }
+    while !line.is_char_boundary(end) {
+        end += 1;
+    }
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str("...");
+    }
+    snippet.push_str(&line[start..end]);
+    if end < line.len() {
+        snippet.push_str("...");
+    }
+    snippet
 }
 
 fn log_response(cmd: &str, resp: &Result<jsonrpc_async::Response>) {
@@ -1433,6 +1680,81 @@ mod tests {
             .is_err());
     }
 
+    #[tokio::test]
+    async fn test_call_timeouts() {
+        use std::time::Instant;
+
+        // A node that accepts connections but never answers.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut conns = Vec::new();
+            while let Ok((conn, _)) = listener.accept().await {
+                conns.push(conn);
+            }
+        });
+
+        let mut client = Client::new(&url, Auth::None).await.unwrap();
+        client.set_timeout(Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(client.get_block_count().await.is_err());
+        assert!(start.elapsed() < Duration::from_millis(250));
+
+        // The override also applies to the transport, so it can be longer.
+        let start = Instant::now();
+        let res = client.with_timeout(Duration::from_millis(300)).get_block_count().await;
+        assert!(matches!(res.unwrap_err().root(), Error::Timeout(_) | Error::JsonRpc(_)));
+        assert!(start.elapsed() >= Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_error_context() {
+        use std::error::Error as _;
+
+        let args = [into_json("hunter2").unwrap(), into_json(60).unwrap()];
+        assert_eq!(
+            redact_params("walletpassphrase", &args),
+            vec![into_json("<redacted>").unwrap(), args[1].clone()]
+        );
+        assert_eq!(redact_params("getblock", &args), args);
+
+        let resp = jsonrpc_async::Response {
+            result: Some(
+                serde_json::value::RawValue::from_string(r#"{"a":[{"b":1},{"b":"two"}]}"#.into())
+                    .unwrap(),
+            ),
+            error: None,
+            id: 1.into(),
+            jsonrpc: None,
+        };
+        #[derive(Debug, serde::Deserialize)]
+        struct B {
+            #[allow(dead_code)]
+            b: u64,
+        }
+        #[derive(Debug, serde::Deserialize)]
+        struct A {
+            #[allow(dead_code)]
+            a: Vec<B>,
+        }
+        let err = parse_result::<A>(&resp).unwrap_err();
+        match err {
+            Error::Deserialize {
+                ref path,
+                ref snippet,
+                ..
+            } => {
+                assert_eq!(path, "a[1].b");
+                assert!(snippet.contains(r#""b":"two""#));
+            }
+            ref e => panic!("unexpected error: {}", e),
+        }
+        assert!(err.source().unwrap().is::<serde_json::Error>());
+        let raw = parse_result::<crate::Raw<A>>(&resp).unwrap();
+        assert_eq!(raw.get(), r#"{"a":[{"b":1},{"b":"two"}]}"#);
+        assert_eq!(snippet(&"x".repeat(100), 1, 50), format!("...{}...", "x".repeat(80)));
+    }
+
     async fn test_handle_defaults_inner() -> Result<()> {
         {
             let mut args = [into_json(0)?, null(), null()];
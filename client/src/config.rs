@@ -0,0 +1,387 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Connection settings from `defi.conf`.
+//!
+//! [RpcConfig::discover] finds the settings the same way `defi-cli` does: it
+//! reads `defi.conf` from the data directory, picks the section of the chain,
+//! and falls back to the cookie file of the chain when no password is set.
+//!
+//! ```no_run
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use defichain_rpc::config::Chain;
+//! use defichain_rpc::Client;
+//!
+//! // Uses the default data directory, e.g. ~/.defi on Linux.
+//! let client = Client::from_conf(None, Some(Chain::Regtest)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::client::{Auth, Client, Result};
+use crate::error::Error;
+
+/// The name of the configuration file in the data directory.
+pub const CONF_FILE: &str = "defi.conf";
+
+/// Options that are ignored outside of a section for chains other than main.
+const NETWORK_ONLY: &[&str] = &["rpcport", "rpcbind"];
+
+/// A DeFiChain network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Chain {
+    Main,
+    Test,
+    Changi,
+    Devnet,
+    Regtest,
+}
+
+impl Chain {
+    /// The name of the chain, as used for its section in `defi.conf`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Chain::Main => "main",
+            Chain::Test => "test",
+            Chain::Changi => "changi",
+            Chain::Devnet => "devnet",
+            Chain::Regtest => "regtest",
+        }
+    }
+
+    /// The subdirectory of the data directory the chain stores its data in.
+    pub fn data_subdir(self) -> Option<&'static str> {
+        match self {
+            Chain::Main => None,
+            Chain::Test => Some("testnet3"),
+            Chain::Changi => Some("changi"),
+            Chain::Devnet => Some("devnet"),
+            Chain::Regtest => Some("regtest"),
+        }
+    }
+
+    /// The default RPC port of the chain.
+    pub fn default_rpc_port(self) -> u16 {
+        match self {
+            Chain::Main => 8554,
+            Chain::Test => 18554,
+            Chain::Changi => 20554,
+            Chain::Devnet => 21554,
+            Chain::Regtest => 19554,
+        }
+    }
+
+    const ALL: [Chain; 5] =
+        [Chain::Main, Chain::Test, Chain::Changi, Chain::Devnet, Chain::Regtest];
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Chain {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Chain> {
+        match s {
+            "main" | "mainnet" => Ok(Chain::Main),
+            "test" | "testnet" => Ok(Chain::Test),
+            "changi" => Ok(Chain::Changi),
+            "devnet" => Ok(Chain::Devnet),
+            "regtest" => Ok(Chain::Regtest),
+            _ => Err(Error::InvalidConfigFile(format!("unknown chain: {}", s))),
+        }
+    }
+}
+
+/// The default data directory of the node on this platform, if known.
+pub fn default_data_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        std::env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("DeFi"))
+    } else {
+        let home = PathBuf::from(std::env::var_os("HOME")?);
+        if cfg!(target_os = "macos") {
+            Some(home.join("Library/Application Support/DeFi"))
+        } else {
+            Some(home.join(".defi"))
+        }
+    }
+}
+
+/// The options of a parsed `defi.conf`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfFile {
+    /// The options outside of any section.
+    default: HashMap<String, Vec<String>>,
+    /// The options of every section.
+    sections: HashMap<String, HashMap<String, Vec<String>>>,
+}
+
+impl ConfFile {
+    /// Parse the contents of a configuration file.
+    pub fn parse(s: &str) -> Result<ConfFile> {
+        let mut conf = ConfFile::default();
+        let mut section: Option<String> = None;
+        for (i, line) in s.lines().enumerate() {
+            let line = match line.find('#') {
+                Some(pos) => &line[..pos],
+                None => line,
+            }
+            .trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                section = Some(line[1..line.len() - 1].trim().to_owned());
+                continue;
+            }
+            let eq = line.find('=').ok_or_else(|| {
+                Error::InvalidConfigFile(format!("parse error on line {}: {}", i + 1, line))
+            })?;
+            let (key, value) = (line[..eq].trim(), line[eq + 1..].trim());
+            let key = key.strip_prefix('-').unwrap_or(key);
+            // Options can also be scoped with a prefix, as in `regtest.rpcport=1234`.
+            let (scope, key) = match (section.as_deref(), key.find('.')) {
+                (None, Some(dot)) => (Some(&key[..dot]), &key[dot + 1..]),
+                (scope, _) => (scope, key),
+            };
+            let options = match scope {
+                Some(scope) => conf.sections.entry(scope.to_owned()).or_default(),
+                None => &mut conf.default,
+            };
+            options.entry(key.to_owned()).or_default().push(value.to_owned());
+        }
+        Ok(conf)
+    }
+
+    /// Read and parse the configuration file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<ConfFile> {
+        ConfFile::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// The value of `key` for `chain`.
+    ///
+    /// The section of the chain takes precedence over the options outside of a
+    /// section, and the first value of an option that is set more than once
+    /// wins, like the node does. Network-only options such as `rpcport` outside
+    /// of a section only apply to main.
+    pub fn get(&self, chain: Chain, key: &str) -> Option<&str> {
+        let section = self.sections.get(chain.name()).and_then(|s| s.get(key));
+        let default = if chain != Chain::Main && NETWORK_ONLY.contains(&key) {
+            None
+        } else {
+            self.default.get(key)
+        };
+        section.or(default).and_then(|v| v.first()).map(|v| v.as_str())
+    }
+
+    /// The chain selected with `chain=` or flags like `regtest=1`, if any.
+    pub fn chain(&self) -> Result<Option<Chain>> {
+        let flag = |key: &str| matches!(self.default.get(key).and_then(|v| v.first()), Some(v) if v != "0");
+        if let Some(chain) = self.default.get("chain").and_then(|v| v.first()) {
+            return Ok(Some(chain.parse()?));
+        }
+        // Test is selected with `testnet=1`, the others with their name.
+        let mut flagged = Chain::ALL[1..].iter().filter(|c| match c {
+            Chain::Test => flag("testnet"),
+            c => flag(c.name()),
+        });
+        match (flagged.next(), flagged.next()) {
+            (Some(_), Some(_)) => {
+                Err(Error::InvalidConfigFile("more than one chain is selected".to_owned()))
+            }
+            (chain, _) => Ok(chain.copied()),
+        }
+    }
+}
+
+/// The URL and credentials to connect to a node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RpcConfig {
+    pub chain: Chain,
+    pub url: String,
+    pub auth: Auth,
+}
+
+impl RpcConfig {
+    /// Resolve the settings for `chain` from a parsed configuration file.
+    ///
+    /// The host is taken from `rpcconnect`, or from `rpcbind` unless it binds
+    /// all interfaces, and defaults to localhost. `rpcport` overrides the port
+    /// of the host. Without `rpcpassword`, the cookie file of the chain in
+    /// `data_dir` is used, or the one set with `rpccookiefile`.
+    pub fn from_conf(conf: &ConfFile, chain: Chain, data_dir: &Path) -> RpcConfig {
+        let host = conf
+            .get(chain, "rpcconnect")
+            .or_else(|| conf.get(chain, "rpcbind").filter(|b| !is_wildcard(b)))
+            .unwrap_or("127.0.0.1");
+        let (host, port) = split_host_port(host);
+        let port = conf
+            .get(chain, "rpcport")
+            .and_then(|p| p.parse().ok())
+            .or(port)
+            .unwrap_or_else(|| chain.default_rpc_port());
+
+        let auth = match conf.get(chain, "rpcpassword") {
+            Some(pass) if !pass.is_empty() => {
+                Auth::UserPass(conf.get(chain, "rpcuser").unwrap_or("").to_owned(), pass.to_owned())
+            }
+            _ => {
+                let net_dir = match chain.data_subdir() {
+                    Some(subdir) => data_dir.join(subdir),
+                    None => data_dir.to_owned(),
+                };
+                // A relative cookie file is relative to the data directory of the chain.
+                Auth::CookieFile(
+                    net_dir.join(conf.get(chain, "rpccookiefile").unwrap_or(".cookie")),
+                )
+            }
+        };
+
+        RpcConfig {
+            chain,
+            url: format!("http://{}:{}", host, port),
+            auth,
+        }
+    }
+
+    /// Find the settings of a local node.
+    ///
+    /// Reads `defi.conf` from `data_dir`, or from the [default_data_dir] if
+    /// none is given. A missing configuration file is treated as empty. The
+    /// chain defaults to the one selected in the configuration file, and to
+    /// main if none is.
+    pub fn discover(data_dir: Option<&Path>, chain: Option<Chain>) -> Result<RpcConfig> {
+        let base = match data_dir {
+            Some(dir) => dir.to_owned(),
+            None => default_data_dir()
+                .ok_or_else(|| Error::InvalidConfigFile("no default data directory".to_owned()))?,
+        };
+        let path = base.join(CONF_FILE);
+        let conf = if path.exists() {
+            ConfFile::load(&path)?
+        } else {
+            ConfFile::default()
+        };
+        let chain = match chain {
+            Some(chain) => chain,
+            None => conf.chain()?.unwrap_or(Chain::Main),
+        };
+        // A data directory that was given explicitly wins over the one in the file.
+        let data_dir = match (data_dir, conf.default.get("datadir").and_then(|v| v.first())) {
+            (None, Some(dir)) => PathBuf::from(dir),
+            _ => base,
+        };
+        Ok(RpcConfig::from_conf(&conf, chain, &data_dir))
+    }
+}
+
+fn is_wildcard(host: &str) -> bool {
+    let (host, _) = split_host_port(host);
+    host == "0.0.0.0" || host == "::" || host == "[::]"
+}
+
+/// Split an optional port off a host, keeping IPv6 addresses in brackets.
+fn split_host_port(s: &str) -> (&str, Option<u16>) {
+    match s.rfind(':') {
+        // A bare IPv6 address without brackets has no port.
+        Some(colon) if s[..colon].find(':').is_none() || s[..colon].ends_with(']') => {
+            match s[colon + 1..].parse() {
+                Ok(port) => (&s[..colon], Some(port)),
+                Err(_) => (s, None),
+            }
+        }
+        _ => (s, None),
+    }
+}
+
+impl Client {
+    /// Create a client to a local node, using the settings from `defi.conf`.
+    ///
+    /// See [RpcConfig::discover].
+    pub async fn from_conf(data_dir: Option<&Path>, chain: Option<Chain>) -> Result<Client> {
+        let config = RpcConfig::discover(data_dir, chain)?;
+        debug!(target: "defichain_rpc", "connecting to {} node at {}", config.chain, config.url);
+        Client::new(&config.url, config.auth).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONF: &str = "
+# global options
+rpcuser=alice
+rpcpassword=secret # trailing comment
+rpcport=1000
+rpcport=1001
+regtest=1
+changi.rpcconnect=node.local
+
+[regtest]
+rpcbind=0.0.0.0
+rpcport=2000
+
+[test]
+rpcpassword=
+rpcbind=[::1]:3000
+";
+
+    #[test]
+    fn resolves_sections() {
+        let conf = ConfFile::parse(CONF).unwrap();
+        assert_eq!(conf.chain().unwrap(), Some(Chain::Regtest));
+        let dir = Path::new("/data");
+
+        let main = RpcConfig::from_conf(&conf, Chain::Main, dir);
+        assert_eq!(main.url, "http://127.0.0.1:1000");
+        assert_eq!(main.auth, Auth::UserPass("alice".into(), "secret".into()));
+
+        let regtest = RpcConfig::from_conf(&conf, Chain::Regtest, dir);
+        assert_eq!(regtest.url, "http://127.0.0.1:2000");
+
+        let test = RpcConfig::from_conf(&conf, Chain::Test, dir);
+        assert_eq!(test.url, "http://[::1]:3000");
+        assert_eq!(test.auth, Auth::CookieFile("/data/testnet3/.cookie".into()));
+
+        // Network-only options outside of a section only apply to main.
+        let changi = RpcConfig::from_conf(&conf, Chain::Changi, dir);
+        assert_eq!(changi.url, "http://node.local:20554");
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert!(matches!(ConfFile::parse("rpcuser"), Err(Error::InvalidConfigFile(_))));
+        assert!(ConfFile::parse("regtest=1\ntestnet=1").unwrap().chain().is_err());
+    }
+
+    #[test]
+    fn discovers_data_dir() {
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(tempdir.path().join(CONF_FILE), "datadir=/elsewhere\nchain=devnet\n")
+            .unwrap();
+        let config = RpcConfig::discover(Some(tempdir.path()), None).unwrap();
+        assert_eq!(config.chain, Chain::Devnet);
+        assert_eq!(config.url, "http://127.0.0.1:21554");
+        assert_eq!(config.auth, Auth::CookieFile(tempdir.path().join("devnet/.cookie")));
+
+        let empty = tempfile::tempdir().unwrap();
+        let config = RpcConfig::discover(Some(empty.path()), Some(Chain::Regtest)).unwrap();
+        assert_eq!(config.auth, Auth::CookieFile(empty.path().join("regtest/.cookie")));
+    }
+}
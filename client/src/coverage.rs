@@ -0,0 +1,420 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Coverage of the node's RPCs.
+//!
+//! [check_coverage] calls `help` on a node and compares the RPCs it lists with
+//! the ones this crate has typed wrappers for, so coverage can be tracked
+//! release by release:
+//!
+//! ```no_run
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use defichain_rpc::coverage::check_coverage;
+//! use defichain_rpc::{Auth, Client};
+//!
+//! let client = Client::new("http://localhost:19554", Auth::None).await?;
+//! let report = check_coverage(&client).await?;
+//! println!("{}", report);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `coverage` example prints the report for a node.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::client::{Result, RpcApi};
+use crate::requests;
+
+/// The RPCs of the [RpcApi] wrappers that don't send a [request](crate::requests),
+/// mostly those of Bitcoin Core, with the most parameters the wrapper sends.
+const CLIENT_RPCS: &[(&str, usize)] = &[
+    ("addmultisigaddress", 4),
+    ("addnode", 2),
+    ("backupwallet", 1),
+    ("clearbanned", 0),
+    ("combinepsbt", 1),
+    ("combinerawtransaction", 1),
+    ("createpsbt", 4),
+    ("createrawtransaction", 4),
+    ("createwallet", 5),
+    ("decoderawtransaction", 2),
+    ("deriveaddresses", 2),
+    ("disconnectnode", 2),
+    ("dumpprivkey", 1),
+    ("encryptwallet", 1),
+    ("estimatesmartfee", 2),
+    ("finalizepsbt", 2),
+    ("fundrawtransaction", 3),
+    ("generate", 2),
+    ("generatetoaddress", 2),
+    ("getaddednodeinfo", 1),
+    ("getaddressinfo", 1),
+    ("getbalance", 3),
+    ("getbalances", 0),
+    ("getblockfilter", 1),
+    ("getblockheader", 2),
+    ("getblocktemplate", 1),
+    ("getconnectioncount", 0),
+    ("getdescriptorinfo", 1),
+    ("getindexinfo", 0),
+    ("getmininginfo", 0),
+    ("getnettotals", 0),
+    ("getnetworkhashps", 2),
+    ("getnetworkinfo", 0),
+    ("getnewaddress", 2),
+    ("getnodeaddresses", 1),
+    ("getpeerinfo", 0),
+    ("getrawchangeaddress", 1),
+    ("getrawmempool", 1),
+    ("getrawtransaction", 3),
+    ("getreceivedbyaddress", 2),
+    ("gettransaction", 2),
+    ("gettxoutproof", 2),
+    ("gettxoutsetinfo", 3),
+    ("getwalletinfo", 0),
+    ("importaddress", 4),
+    ("importdescriptors", 1),
+    ("importmulti", 2),
+    ("importprivkey", 3),
+    ("importpubkey", 3),
+    ("invalidateblock", 1),
+    ("joinpsbts", 1),
+    ("keypoolrefill", 1),
+    ("listbanned", 0),
+    ("listreceivedbyaddress", 4),
+    ("listsinceblock", 4),
+    ("listtransactions", 4),
+    ("listunspent", 5),
+    ("listwalletdir", 0),
+    ("listwallets", 0),
+    ("loadwallet", 1),
+    ("lockunspent", 2),
+    ("ping", 0),
+    ("reconsiderblock", 1),
+    ("rescanblockchain", 2),
+    ("scantxoutset", 2),
+    ("sendrawtransaction", 2),
+    ("sendtoaddress", 8),
+    ("setban", 4),
+    ("setlabel", 2),
+    ("setnetworkactive", 1),
+    ("signrawtransaction", 4),
+    ("signrawtransactionwithkey", 4),
+    ("signrawtransactionwithwallet", 3),
+    ("stop", 0),
+    ("submitblock", 1),
+    ("testmempoolaccept", 2),
+    ("unloadwallet", 1),
+    ("uptime", 0),
+    ("verifymessage", 3),
+    ("waitforblock", 2),
+    ("waitfornewblock", 1),
+    ("walletcreatefundedpsbt", 5),
+    ("walletprocesspsbt", 4),
+];
+
+/// The RPCs with a typed wrapper in this crate, with the most parameters the
+/// wrapper sends.
+pub fn wrapped_rpcs() -> BTreeMap<&'static str, usize> {
+    let tables = [
+        CLIENT_RPCS,
+        requests::account::RPCS,
+        requests::blockchain::RPCS,
+        requests::governance::RPCS,
+        requests::icxorderbook::RPCS,
+        requests::loan::RPCS,
+        requests::masternode::RPCS,
+        requests::oracle::RPCS,
+        requests::poolpair::RPCS,
+        requests::spv::RPCS,
+        requests::token::RPCS,
+        requests::vault::RPCS,
+    ];
+    let mut wrapped = BTreeMap::new();
+    for &(method, params) in tables.iter().flat_map(|t| t.iter()) {
+        let most = wrapped.entry(method).or_insert(0);
+        *most = params.max(*most);
+    }
+    wrapped
+}
+
+/// An RPC as listed by the `help` RPC of the node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HelpMethod {
+    /// The category of the RPC, e.g. `Blockchain`.
+    pub category: String,
+    /// The name of the RPC.
+    pub name: String,
+    /// The number of required parameters.
+    pub required: usize,
+    /// The number of optional parameters.
+    pub optional: usize,
+    /// The signature as listed, e.g. `getblock "blockhash" ( verbosity )`.
+    pub signature: String,
+}
+
+impl HelpMethod {
+    /// Whether the RPC accepts `params` parameters.
+    pub fn accepts(&self, params: usize) -> bool {
+        params >= self.required && params <= self.required + self.optional
+    }
+}
+
+/// Parse the output of the `help` RPC without arguments.
+///
+/// The output lists the RPCs by category, one signature per line, with
+/// optional parameters in parentheses:
+///
+/// ```text
+/// == Blockchain ==
+/// getblock "blockhash" ( verbosity )
+/// ```
+pub fn parse_help(help: &str) -> Vec<HelpMethod> {
+    let mut category = String::new();
+    let mut methods = Vec::new();
+    for line in help.lines().map(str::trim) {
+        if line.starts_with("==") && line.ends_with("==") {
+            category = line.trim_matches('=').trim().to_owned();
+            continue;
+        }
+        let mut words = line.splitn(2, ' ');
+        let name = match words.next() {
+            Some(name) if !name.is_empty() => name,
+            _ => continue,
+        };
+        let (required, optional) = count_params(words.next().unwrap_or(""));
+        methods.push(HelpMethod {
+            category: category.clone(),
+            name: name.to_owned(),
+            required,
+            optional,
+            signature: line.to_owned(),
+        });
+    }
+    methods
+}
+
+/// Count the required and optional parameters of a signature.
+///
+/// Parameters are separated by spaces outside of strings, arrays and objects,
+/// and parameters in parentheses are optional.
+fn count_params(params: &str) -> (usize, usize) {
+    let (mut required, mut optional) = (0, 0);
+    let (mut nesting, mut optional_nesting) = (0usize, 0usize);
+    let (mut in_string, mut in_param) = (false, false);
+    for c in params.chars() {
+        if in_string {
+            in_string = c != '"';
+            continue;
+        }
+        match c {
+            '(' if nesting == 0 => {
+                optional_nesting += 1;
+                in_param = false;
+                continue;
+            }
+            ')' if nesting == 0 => {
+                optional_nesting = optional_nesting.saturating_sub(1);
+                in_param = false;
+                continue;
+            }
+            ' ' | '\t' if nesting == 0 => {
+                in_param = false;
+                continue;
+            }
+            '[' | '{' => nesting += 1,
+            ']' | '}' => nesting = nesting.saturating_sub(1),
+            '"' => in_string = true,
+            _ => {}
+        }
+        if !in_param {
+            in_param = true;
+            // `...` repeats the previous parameter, it is not one itself.
+            if c == '.' {
+                continue;
+            }
+            if optional_nesting > 0 {
+                optional += 1;
+            } else {
+                required += 1;
+            }
+        }
+    }
+    (required, optional)
+}
+
+/// An RPC whose wrapper sends a number of parameters the node doesn't accept.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamMismatch {
+    /// The RPC as listed by the node.
+    pub method: HelpMethod,
+    /// The most parameters the wrapper sends.
+    pub params: usize,
+}
+
+/// How the wrappers of this crate cover the RPCs of a node.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// The RPCs of the node with a wrapper.
+    pub covered: Vec<HelpMethod>,
+    /// The RPCs of the node without a wrapper.
+    pub missing: Vec<HelpMethod>,
+    /// The covered RPCs whose wrapper sends more parameters than the node
+    /// accepts, or fewer than it requires.
+    pub mismatched: Vec<ParamMismatch>,
+    /// The wrapped RPCs the node doesn't list, such as RPCs of Bitcoin Core
+    /// that the node doesn't have, or that were removed.
+    pub unknown: Vec<&'static str>,
+}
+
+impl CoverageReport {
+    /// Compare the RPCs listed by `help` with the wrapped ones.
+    pub fn new(methods: Vec<HelpMethod>) -> CoverageReport {
+        let mut wrapped = wrapped_rpcs();
+        let mut report = CoverageReport::default();
+        for method in methods {
+            match wrapped.remove(method.name.as_str()) {
+                Some(params) => {
+                    if !method.accepts(params) {
+                        report.mismatched.push(ParamMismatch {
+                            method: method.clone(),
+                            params,
+                        });
+                    }
+                    report.covered.push(method);
+                }
+                None => report.missing.push(method),
+            }
+        }
+        report.unknown = wrapped.keys().cloned().collect();
+        report
+    }
+
+    /// The share of the node's RPCs with a wrapper, from 0 to 1.
+    pub fn ratio(&self) -> f64 {
+        let total = self.covered.len() + self.missing.len();
+        if total == 0 {
+            return 1.0;
+        }
+        self.covered.len() as f64 / total as f64
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} RPCs covered ({:.1}%)",
+            self.covered.len(),
+            self.covered.len() + self.missing.len(),
+            self.ratio() * 100.0
+        )?;
+        let mut category = None;
+        for method in self.missing.iter() {
+            if category != Some(&method.category) {
+                writeln!(f, "\nmissing ({}):", method.category)?;
+                category = Some(&method.category);
+            }
+            writeln!(f, "  {}", method.signature)?;
+        }
+        if !self.mismatched.is_empty() {
+            writeln!(f, "\nparameter counts differ:")?;
+            for m in self.mismatched.iter() {
+                writeln!(
+                    f,
+                    "  {} sends {}, node: {}",
+                    m.method.name, m.params, m.method.signature
+                )?;
+            }
+        }
+        if !self.unknown.is_empty() {
+            writeln!(f, "\nnot listed by the node:")?;
+            for name in self.unknown.iter() {
+                writeln!(f, "  {}", name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Call `help` on the node and compare the RPCs it lists with the wrapped
+/// ones.
+pub async fn check_coverage<R: RpcApi + Sync>(rpc: &R) -> Result<CoverageReport> {
+    let help: String = rpc.call("help", &[]).await?;
+    Ok(CoverageReport::new(parse_help(&help)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELP: &str = r#"== Blockchain ==
+getblock "blockhash" ( verbosity )
+getblockcount
+getblockhash height
+
+== Rawtransactions ==
+createrawtransaction [{"txid":"hex","vout":n},...] [{"address":amount},...] ( locktime replaceable )
+sendrawtransaction "hexstring" ( maxfeerate ) ( extra )
+
+== Evm ==
+vmmap "input" type
+"#;
+
+    #[test]
+    fn parses_help() {
+        let methods = parse_help(HELP);
+        let counts: Vec<_> = methods
+            .iter()
+            .map(|m| (m.category.as_str(), m.name.as_str(), m.required, m.optional))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("Blockchain", "getblock", 1, 1),
+                ("Blockchain", "getblockcount", 0, 0),
+                ("Blockchain", "getblockhash", 1, 0),
+                ("Rawtransactions", "createrawtransaction", 2, 2),
+                ("Rawtransactions", "sendrawtransaction", 1, 2),
+                ("Evm", "vmmap", 2, 0),
+            ]
+        );
+
+        let report = CoverageReport::new(methods);
+        assert_eq!(report.covered.len(), 5);
+        assert_eq!(report.missing[0].name, "vmmap");
+        assert!(report.mismatched.is_empty());
+        assert!(report.unknown.contains(&"getvault"));
+        assert!(report.to_string().starts_with("5 of 6 RPCs covered (83.3%)"));
+
+        let report = CoverageReport::new(parse_help("getblockhash\n"));
+        assert_eq!(report.mismatched[0].params, 1);
+    }
+
+    #[test]
+    fn counts_params_of_requests() {
+        use crate::requests::{oracle, vault, RpcRequest};
+
+        let wrapped = wrapped_rpcs();
+        let request = vault::ListAuctionHistory {
+            owner: None,
+            pagination: None,
+        };
+        assert_eq!(wrapped["listauctionhistory"], request.params().unwrap().len());
+        // The checks before the parameters are not counted.
+        assert_eq!(wrapped["appointoracle"], 4);
+        assert_eq!(oracle::RPCS[0], ("appointoracle", 4));
+        // The longest call of the wrappers of `RpcApi` and the requests.
+        assert_eq!(wrapped["getblockheader"], 2);
+        assert_eq!(wrapped.len(), 230);
+    }
+}
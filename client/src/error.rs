@@ -8,12 +8,14 @@
 // If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
 //
 
+use std::time::Duration;
 use std::{error, fmt, io};
 
 use crate::bitcoin;
 use crate::bitcoin::hashes::hex;
 use crate::bitcoin::secp256k1;
 use jsonrpc_async;
+use jsonrpc_async::simple_http;
 use serde_json;
 
 /// The error type for errors produced in this library.
@@ -27,11 +29,42 @@ pub enum Error {
     Io(io::Error),
     InvalidAmount(bitcoin::amount::ParseAmountError),
     InvalidCookieFile,
+    /// The configuration file of the node could not be used.
+    InvalidConfigFile(String),
     /// The JSON result had an unexpected structure.
     UnexpectedStructure,
     /// The daemon returned an error string.
     ReturnedError(String),
+    /// The call did not complete within the timeout.
+    Timeout(Duration),
     Custom(String),
+    /// A call to the node failed.
+    Call {
+        /// The name of the RPC method.
+        method: String,
+        /// The parameters of the call, with secrets such as passphrases and
+        /// private keys redacted.
+        params: Vec<serde_json::Value>,
+        /// Why the call failed.
+        source: Box<Error>,
+    },
+    /// The result of a call did not have the expected structure.
+    Deserialize {
+        /// The path of the offending value in the result, e.g. `vaults[0].state`.
+        path: String,
+        /// The part of the raw result around the offending value.
+        snippet: String,
+        source: serde_json::error::Error,
+    },
+    /// The result of a call had fields its type doesn't know, see the
+    /// [schema](crate::schema) module.
+    UnknownFields {
+        /// The name of the type the result was deserialized into.
+        type_name: &'static str,
+        /// The paths of the unknown fields in the result, e.g.
+        /// `vaults[0].newField`.
+        paths: Vec<String>,
+    },
 }
 
 impl From<jsonrpc_async::error::Error> for Error {
@@ -87,19 +120,34 @@ impl fmt::Display for Error {
             Error::Io(ref e) => write!(f, "I/O error: {}", e),
             Error::InvalidAmount(ref e) => write!(f, "invalid amount: {}", e),
             Error::InvalidCookieFile => write!(f, "invalid cookie file"),
+            Error::InvalidConfigFile(ref s) => write!(f, "invalid config file: {}", s),
             Error::UnexpectedStructure => write!(f, "the JSON result had an unexpected structure"),
             Error::ReturnedError(ref s) => write!(f, "the daemon returned an error string: {}", s),
+            Error::Timeout(ref t) => write!(f, "the call timed out after {:?}", t),
             Error::Custom(ref s) => write!(f, "Custom error: {}", s),
+            Error::Call {
+                ref method,
+                ref params,
+                ref source,
+            } => {
+                let params = serde_json::to_string(params).unwrap_or_default();
+                write!(f, "{} {} failed: {}", method, params, source)
+            }
+            Error::Deserialize {
+                ref path,
+                ref snippet,
+                ref source,
+            } => write!(f, "invalid result at {}: {} in `{}`", path, source, snippet),
+            Error::UnknownFields {
+                type_name,
+                ref paths,
+            } => write!(f, "unknown fields in {}: {}", type_name, paths.join(", ")),
         }
     }
 }
 
 impl error::Error for Error {
-    fn description(&self) -> &str {
-        "defichain-rpc error"
-    }
-
-    fn cause(&self) -> Option<&dyn error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Error::JsonRpc(ref e) => Some(e),
             Error::Hex(ref e) => Some(e),
@@ -107,7 +155,510 @@ impl error::Error for Error {
             Error::BitcoinSerialization(ref e) => Some(e),
             Error::Secp256k1(ref e) => Some(e),
             Error::Io(ref e) => Some(e),
+            Error::InvalidAmount(ref e) => Some(e),
+            Error::Call {
+                ref source,
+                ..
+            } => Some(&**source),
+            Error::Deserialize {
+                ref source,
+                ..
+            } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The standard error codes of the node.
+///
+/// Codes that are not known are kept as [RpcErrorCode::Other].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RpcErrorCode {
+    // Standard JSON-RPC 2.0 errors.
+    InvalidRequest,
+    MethodNotFound,
+    InvalidParams,
+    InternalError,
+    ParseError,
+
+    // General application defined errors.
+    /// `std::exception` thrown in command handling, also used for custom
+    /// transactions the node rejected.
+    MiscError,
+    /// Unexpected type was passed as parameter.
+    TypeError,
+    /// Invalid address or key.
+    InvalidAddressOrKey,
+    /// Ran out of memory during operation.
+    OutOfMemory,
+    /// Invalid, missing or duplicate parameter.
+    InvalidParameter,
+    /// Database error.
+    DatabaseError,
+    /// Error parsing or validating structure in raw format.
+    DeserializationError,
+    /// General error during transaction or block submission.
+    VerifyError,
+    /// Transaction or block was rejected by network rules.
+    VerifyRejected,
+    /// Transaction already in chain.
+    VerifyAlreadyInChain,
+    /// Client still warming up.
+    InWarmup,
+    /// RPC method is deprecated.
+    MethodDeprecated,
+
+    // P2P client errors.
+    /// The node is not connected to any peers.
+    ClientNotConnected,
+    /// Still downloading initial blocks.
+    ClientInInitialDownload,
+    /// Node is already added.
+    ClientNodeAlreadyAdded,
+    /// Node has not been added before.
+    ClientNodeNotAdded,
+    /// Node to disconnect not found in connected nodes.
+    ClientNodeNotConnected,
+    /// Invalid IP/Subnet.
+    ClientInvalidIpOrSubnet,
+    /// No valid connection manager instance found.
+    ClientP2pDisabled,
+
+    // Wallet errors.
+    /// Unspecified problem with wallet (key not found etc.).
+    WalletError,
+    /// Not enough funds in wallet or account.
+    WalletInsufficientFunds,
+    /// Invalid label name.
+    WalletInvalidLabelName,
+    /// Keypool ran out, call keypoolrefill first.
+    WalletKeypoolRanOut,
+    /// Enter the wallet passphrase with walletpassphrase first.
+    WalletUnlockNeeded,
+    /// The wallet passphrase entered was incorrect.
+    WalletPassphraseIncorrect,
+    /// Command given in wrong wallet encryption state.
+    WalletWrongEncState,
+    /// Failed to encrypt the wallet.
+    WalletEncryptionFailed,
+    /// Wallet is already unlocked.
+    WalletAlreadyUnlocked,
+    /// Invalid wallet specified.
+    WalletNotFound,
+    /// No wallet specified, because more than one wallet is loaded.
+    WalletNotSpecified,
+    /// This same wallet is already loaded.
+    WalletAlreadyLoaded,
+
+    /// Any other code.
+    Other(i32),
+}
+
+impl RpcErrorCode {
+    /// The typed error code for `code`.
+    pub fn from_code(code: i32) -> RpcErrorCode {
+        use self::RpcErrorCode::*;
+        match code {
+            -32600 => InvalidRequest,
+            -32601 => MethodNotFound,
+            -32602 => InvalidParams,
+            -32603 => InternalError,
+            -32700 => ParseError,
+            -1 => MiscError,
+            -3 => TypeError,
+            -5 => InvalidAddressOrKey,
+            -7 => OutOfMemory,
+            -8 => InvalidParameter,
+            -20 => DatabaseError,
+            -22 => DeserializationError,
+            -25 => VerifyError,
+            -26 => VerifyRejected,
+            -27 => VerifyAlreadyInChain,
+            -28 => InWarmup,
+            -32 => MethodDeprecated,
+            -9 => ClientNotConnected,
+            -10 => ClientInInitialDownload,
+            -23 => ClientNodeAlreadyAdded,
+            -24 => ClientNodeNotAdded,
+            -29 => ClientNodeNotConnected,
+            -30 => ClientInvalidIpOrSubnet,
+            -31 => ClientP2pDisabled,
+            -4 => WalletError,
+            -6 => WalletInsufficientFunds,
+            -11 => WalletInvalidLabelName,
+            -12 => WalletKeypoolRanOut,
+            -13 => WalletUnlockNeeded,
+            -14 => WalletPassphraseIncorrect,
+            -15 => WalletWrongEncState,
+            -16 => WalletEncryptionFailed,
+            -17 => WalletAlreadyUnlocked,
+            -18 => WalletNotFound,
+            -19 => WalletNotSpecified,
+            -35 => WalletAlreadyLoaded,
+            code => Other(code),
+        }
+    }
+
+    /// The numeric error code.
+    pub fn code(self) -> i32 {
+        use self::RpcErrorCode::*;
+        match self {
+            InvalidRequest => -32600,
+            MethodNotFound => -32601,
+            InvalidParams => -32602,
+            InternalError => -32603,
+            ParseError => -32700,
+            MiscError => -1,
+            TypeError => -3,
+            InvalidAddressOrKey => -5,
+            OutOfMemory => -7,
+            InvalidParameter => -8,
+            DatabaseError => -20,
+            DeserializationError => -22,
+            VerifyError => -25,
+            VerifyRejected => -26,
+            VerifyAlreadyInChain => -27,
+            InWarmup => -28,
+            MethodDeprecated => -32,
+            ClientNotConnected => -9,
+            ClientInInitialDownload => -10,
+            ClientNodeAlreadyAdded => -23,
+            ClientNodeNotAdded => -24,
+            ClientNodeNotConnected => -29,
+            ClientInvalidIpOrSubnet => -30,
+            ClientP2pDisabled => -31,
+            WalletError => -4,
+            WalletInsufficientFunds => -6,
+            WalletInvalidLabelName => -11,
+            WalletKeypoolRanOut => -12,
+            WalletUnlockNeeded => -13,
+            WalletPassphraseIncorrect => -14,
+            WalletWrongEncState => -15,
+            WalletEncryptionFailed => -16,
+            WalletAlreadyUnlocked => -17,
+            WalletNotFound => -18,
+            WalletNotSpecified => -19,
+            WalletAlreadyLoaded => -35,
+            Other(code) => code,
+        }
+    }
+
+    /// Whether the error is expected to go away by itself, so that the call
+    /// can succeed when it is made again later.
+    pub fn is_transient(self) -> bool {
+        matches!(
+            self,
+            RpcErrorCode::InWarmup
+                | RpcErrorCode::ClientNotConnected
+                | RpcErrorCode::ClientInInitialDownload
+        )
+    }
+}
+
+impl fmt::Display for RpcErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RpcErrorCode::Other(code) => write!(f, "{}", code),
+            code => write!(f, "{:?} ({})", code, code.code()),
+        }
+    }
+}
+
+/// Why the node rejected a custom transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RejectReason {
+    /// The balance was too low, as in `amount 1.0 is less than 2.0`.
+    InsufficientFunds {
+        /// The amount that was available, possibly with a token suffix.
+        available: String,
+        /// The amount that was needed, possibly with a token suffix.
+        required: String,
+    },
+    /// The transaction has no input from the owner of what it changes, as in
+    /// `tx must have at least one input from token owner`.
+    MissingAuth,
+    /// Something the transaction refers to doesn't exist, as in
+    /// `token 5 does not exist` or `vault <id> not found`.
+    NotFound(String),
+    /// Any other reason.
+    Other(String),
+}
+
+impl RejectReason {
+    fn parse(reason: &str) -> RejectReason {
+        if let Some(amounts) = reason.strip_prefix("amount ") {
+            if let Some(pos) = amounts.find(" is less than ") {
+                return RejectReason::InsufficientFunds {
+                    available: amounts[..pos].to_owned(),
+                    required: amounts[pos + " is less than ".len()..].to_owned(),
+                };
+            }
+        }
+        if reason.contains("must have at least one input from") {
+            return RejectReason::MissingAuth;
+        }
+        for suffix in [" does not exist", " not found"].iter() {
+            if let Some(subject) = reason.strip_suffix(suffix) {
+                return RejectReason::NotFound(subject.to_owned());
+            }
+        }
+        RejectReason::Other(reason.to_owned())
+    }
+}
+
+/// A custom transaction, such as an `AccountToAccount`, rejected by the node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomTxReject {
+    /// The type of the transaction, e.g. `AccountToAccount`.
+    pub tx_type: String,
+    /// Why the transaction was rejected.
+    pub reason: RejectReason,
+    /// The reason as returned by the node.
+    pub message: String,
+}
+
+impl CustomTxReject {
+    /// Parse the message of an error returned for a custom transaction.
+    ///
+    /// Understands the messages of the RPCs that create the transactions, as in
+    /// `Test AccountToAccountTx execution failed:\namount 1.0 is less than 2.0`,
+    /// the mempool rejections of `sendrawtransaction`, as in
+    /// `bad-txns-customtx, AccountToAccount: ... (code 16)`, and the bare
+    /// `AccountToAccountMessage: ...` form.
+    pub fn parse(message: &str) -> Option<CustomTxReject> {
+        let message = message.trim();
+        let (tx_type, reason) = if let Some(rest) = message.strip_prefix("Test ") {
+            let pos = rest.find(" execution failed:")?;
+            (&rest[..pos], rest[pos + " execution failed:".len()..].trim())
+        } else {
+            let rest = message.strip_prefix("bad-txns-customtx, ").unwrap_or(message);
+            let rest = match rest.rfind(" (code ") {
+                Some(pos) if rest.ends_with(')') => &rest[..pos],
+                _ => rest,
+            };
+            let pos = rest.find(": ")?;
+            (&rest[..pos], rest[pos + 2..].trim())
+        };
+        // Only CamelCase type names, so ordinary messages with a colon don't match.
+        if !tx_type.starts_with(|c: char| c.is_ascii_uppercase())
+            || !tx_type.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return None;
+        }
+        let tx_type = tx_type
+            .strip_suffix("Message")
+            .or_else(|| tx_type.strip_suffix("Tx"))
+            .unwrap_or(tx_type);
+        Some(CustomTxReject {
+            tx_type: tx_type.to_owned(),
+            reason: RejectReason::parse(reason),
+            message: reason.to_owned(),
+        })
+    }
+}
+
+impl Error {
+    /// The error without the context of the call it occurred in.
+    pub fn root(&self) -> &Error {
+        match *self {
+            Error::Call {
+                ref source,
+                ..
+            } => source.root(),
+            ref e => e,
+        }
+    }
+
+    /// The name of the RPC method of the call that failed, if known.
+    pub fn method(&self) -> Option<&str> {
+        match *self {
+            Error::Call {
+                ref method,
+                ..
+            } => Some(method),
             _ => None,
         }
     }
+
+    /// The error returned by the node, if the call failed with one.
+    pub fn rpc_error(&self) -> Option<&jsonrpc_async::error::RpcError> {
+        match *self.root() {
+            Error::JsonRpc(jsonrpc_async::Error::Rpc(ref e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The code of the error returned by the node, if the call failed with one.
+    pub fn rpc_code(&self) -> Option<RpcErrorCode> {
+        self.rpc_error().map(|e| RpcErrorCode::from_code(e.code))
+    }
+
+    /// The rejected custom transaction, if the node returned an error for one.
+    pub fn custom_tx_reject(&self) -> Option<CustomTxReject> {
+        self.rpc_error().and_then(|e| CustomTxReject::parse(&e.message))
+    }
+
+    /// Whether the error is expected to go away by itself, so that the call
+    /// can succeed when it is made again later. All other errors are
+    /// permanent.
+    ///
+    /// These are timeouts, failures to connect to the node or of the
+    /// connection, and errors of a node that is warming up, downloading the
+    /// initial blocks or not connected to peers. HTTP errors such as rejected
+    /// credentials, unparsable responses and invalid URLs are permanent.
+    pub fn is_transient(&self) -> bool {
+        match *self.root() {
+            Error::JsonRpc(jsonrpc_async::Error::Rpc(ref e)) => {
+                RpcErrorCode::from_code(e.code).is_transient()
+            }
+            Error::JsonRpc(jsonrpc_async::Error::Transport(ref e)) => is_transient_transport(&**e),
+            Error::Timeout(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Whether the error of a transport is a socket error or a timeout.
+fn is_transient_transport(e: &(dyn error::Error + Send + Sync + 'static)) -> bool {
+    if let Some(e) = e.downcast_ref::<simple_http::Error>() {
+        match *e {
+            simple_http::Error::SocketError(ref e) => is_transient_io(e),
+            simple_http::Error::Timeout => true,
+            _ => false,
+        }
+    } else if let Some(e) = e.downcast_ref::<io::Error>() {
+        is_transient_io(e)
+    } else {
+        is_transient_reqwest(e)
+    }
+}
+
+/// Whether the I/O error is a failure to connect, a lost connection or a
+/// timeout.
+fn is_transient_io(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::TimedOut
+            // Timeouts of socket reads on Unix.
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+#[cfg(feature = "reqwest")]
+fn is_transient_reqwest(e: &(dyn error::Error + Send + Sync + 'static)) -> bool {
+    match e.downcast_ref::<reqwest::Error>() {
+        Some(e) if e.is_timeout() => true,
+        // Failed TLS handshakes, such as of untrusted certificates, are
+        // reported as connection errors too.
+        Some(e) if e.is_connect() => !is_invalid_data(e),
+        _ => false,
+    }
+}
+
+/// Whether an I/O error of invalid data caused `e`.
+#[cfg(feature = "reqwest")]
+fn is_invalid_data(e: &(dyn error::Error + 'static)) -> bool {
+    let mut next = Some(e);
+    while let Some(e) = next {
+        if let Some(e) = e.downcast_ref::<io::Error>() {
+            if e.kind() == io::ErrorKind::InvalidData {
+                return true;
+            }
+            // The source of an I/O error is the source of its inner error.
+            if let Some(inner) = e.get_ref() {
+                next = Some(inner);
+                continue;
+            }
+        }
+        next = e.source();
+    }
+    false
+}
+
+#[cfg(not(feature = "reqwest"))]
+fn is_transient_reqwest(_: &(dyn error::Error + Send + Sync + 'static)) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_rpc_codes() {
+        for code in -32700..0 {
+            assert_eq!(RpcErrorCode::from_code(code).code(), code);
+        }
+        assert_eq!(RpcErrorCode::from_code(-32601), RpcErrorCode::MethodNotFound);
+        assert!(RpcErrorCode::from_code(-28).is_transient());
+        assert!(!RpcErrorCode::from_code(-26).is_transient());
+    }
+
+    #[test]
+    fn only_socket_errors_and_timeouts_are_transient() {
+        fn transport<E: error::Error + Send + Sync + 'static>(e: E) -> Error {
+            Error::JsonRpc(jsonrpc_async::Error::Transport(Box::new(e)))
+        }
+        let refused = || io::Error::new(io::ErrorKind::ConnectionRefused, "refused");
+
+        assert!(transport(simple_http::Error::SocketError(refused())).is_transient());
+        assert!(transport(simple_http::Error::Timeout).is_transient());
+        assert!(transport(refused()).is_transient());
+        assert!(Error::Timeout(Duration::from_secs(1)).is_transient());
+        let err = Error::Call {
+            method: "getblockcount".into(),
+            params: vec![],
+            source: Box::new(transport(refused())),
+        };
+        assert!(err.is_transient());
+
+        assert!(!transport(simple_http::Error::HttpErrorCode(401)).is_transient());
+        assert!(!transport(simple_http::Error::HttpErrorCode(403)).is_transient());
+        assert!(!transport(simple_http::Error::HttpParseError).is_transient());
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid");
+        assert!(!transport(invalid()).is_transient());
+        assert!(!transport(simple_http::Error::SocketError(invalid())).is_transient());
+        let url = simple_http::Error::InvalidUrl {
+            url: "ftp://node".into(),
+            reason: "invalid scheme",
+        };
+        assert!(!transport(url).is_transient());
+    }
+
+    #[test]
+    fn parses_custom_tx_rejects() {
+        let reject =
+            CustomTxReject::parse("AccountToAccountMessage: amount 1.0 is less than 2.0").unwrap();
+        assert_eq!(reject.tx_type, "AccountToAccount");
+        assert_eq!(
+            reject.reason,
+            RejectReason::InsufficientFunds {
+                available: "1.0".into(),
+                required: "2.0".into(),
+            }
+        );
+
+        let reject = CustomTxReject::parse(
+            "Test DepositToVaultTx execution failed:\nvault 0a1b does not exist",
+        )
+        .unwrap();
+        assert_eq!(reject.tx_type, "DepositToVault");
+        assert_eq!(reject.reason, RejectReason::NotFound("vault 0a1b".into()));
+
+        let reject = CustomTxReject::parse(
+            "bad-txns-customtx, UpdateToken: tx must have at least one input from token owner (code 16)",
+        )
+        .unwrap();
+        assert_eq!(reject.tx_type, "UpdateToken");
+        assert_eq!(reject.reason, RejectReason::MissingAuth);
+
+        assert_eq!(CustomTxReject::parse("Invalid parameter: bad address"), None);
+        assert_eq!(CustomTxReject::parse("Method not found"), None);
+    }
 }
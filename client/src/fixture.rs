@@ -0,0 +1,327 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Recording calls to fixture files and replaying them without a node.
+//!
+//! [RecordingClient] wraps any [RpcApi] implementation and saves every call
+//! with its response to a fixture file. [ReplayClient] serves the responses
+//! of a fixture file, so code built on the domain traits can be tested
+//! offline:
+//!
+//! ```no_run
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use defichain_rpc::fixture::{RecordingClient, ReplayClient};
+//! use defichain_rpc::{Auth, Client, PoolPairRPC};
+//!
+//! // Against a live node, once.
+//! let client = Client::new("http://localhost:19554", Auth::None).await?;
+//! let rpc = RecordingClient::new(client, "tests/fixtures/poolpairs.json");
+//! rpc.list_pool_pairs(None, None).await?;
+//!
+//! // In tests.
+//! let rpc = ReplayClient::load("tests/fixtures/poolpairs.json")?;
+//! let pairs = rpc.list_pool_pairs(None, None).await?;
+//! rpc.assert_exhausted();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Secret parameters, such as wallet passphrases, are redacted in fixtures.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::client::{parse_value, redact_params, Result, RpcApi};
+use crate::error::Error;
+
+/// A recorded call and its response.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Exchange {
+    /// The name of the RPC method.
+    pub method: String,
+    /// The parameters of the call, with secrets redacted.
+    pub params: Vec<serde_json::Value>,
+    /// The result, if the call succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    /// The error returned by the node, if the call failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<jsonrpc_async::error::RpcError>,
+}
+
+/// Read the exchanges of a fixture file.
+pub fn load_fixture<P: AsRef<Path>>(path: P) -> Result<Vec<Exchange>> {
+    Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+}
+
+/// An [RpcApi] implementation that records the calls of the wrapped one to a
+/// fixture file.
+///
+/// The file is rewritten after every call, so it is complete even if the
+/// program doesn't exit cleanly. Calls that fail without a response from the
+/// node, for instance because of a transport error, are not recorded.
+///
+/// Failing to write the file doesn't fail the call, which the node already
+/// executed. The failure is logged and kept for [RecordingClient::take_write_error].
+pub struct RecordingClient<C> {
+    inner: C,
+    path: PathBuf,
+    exchanges: Mutex<Vec<Exchange>>,
+    write_error: Mutex<Option<Error>>,
+}
+
+impl<C: fmt::Debug> fmt::Debug for RecordingClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::RecordingClient({:?}, {:?})", self.inner, self.path)
+    }
+}
+
+impl<C: RpcApi + Sync + Send> RecordingClient<C> {
+    /// Wrap `inner`, recording to the fixture file at `path`.
+    pub fn new<P: Into<PathBuf>>(inner: C, path: P) -> Self {
+        RecordingClient {
+            inner,
+            path: path.into(),
+            exchanges: Mutex::new(Vec::new()),
+            write_error: Mutex::new(None),
+        }
+    }
+
+    /// The calls recorded so far.
+    pub fn exchanges(&self) -> Vec<Exchange> {
+        self.exchanges.lock().unwrap().clone()
+    }
+
+    /// The last failure to write the fixture file, if any since the last call
+    /// of this method.
+    pub fn take_write_error(&self) -> Option<Error> {
+        self.write_error.lock().unwrap().take()
+    }
+
+    /// Get the wrapped client.
+    pub fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    fn record(&self, exchange: Exchange) -> Result<()> {
+        let mut exchanges = self.exchanges.lock().unwrap();
+        exchanges.push(exchange);
+        let json = serde_json::to_vec_pretty(&*exchanges)?;
+        std::fs::write(&self.path, json)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<C: RpcApi + Sync + Send> RpcApi for RecordingClient<C> {
+    async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        let res = self.inner.call::<serde_json::Value>(cmd, args).await;
+        let (result, error) = match res {
+            Ok(ref v) => (Some(v.clone()), None),
+            Err(ref e) => match e.rpc_error() {
+                Some(e) => (None, Some(e.clone())),
+                None => return Err(res.unwrap_err()),
+            },
+        };
+        let recorded = self.record(Exchange {
+            method: cmd.to_owned(),
+            params: redact_params(cmd, args),
+            result,
+            error,
+        });
+        if let Err(e) = recorded {
+            warn!(target: "defichain_rpc", "failed to write fixture {}: {}", self.path.display(), e);
+            *self.write_error.lock().unwrap() = Some(e);
+        }
+        // Like direct calls, keep the method and params, and the location of
+        // a result that can't be deserialized.
+        parse_value(&res?).map_err(|e| Error::Call {
+            method: cmd.to_owned(),
+            params: redact_params(cmd, args),
+            source: Box::new(e),
+        })
+    }
+}
+
+/// An [RpcApi] implementation that serves the responses of recorded calls.
+///
+/// A call is answered with the first unused exchange that has the same method
+/// and parameters, so repeated calls get their responses in the recorded
+/// order. Calls without such an exchange fail with [Error::Custom].
+pub struct ReplayClient {
+    exchanges: Mutex<VecDeque<Exchange>>,
+}
+
+impl fmt::Debug for ReplayClient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::ReplayClient({} exchanges left)", self.remaining().len())
+    }
+}
+
+impl ReplayClient {
+    /// Serve the given exchanges.
+    pub fn new(exchanges: Vec<Exchange>) -> Self {
+        ReplayClient {
+            exchanges: Mutex::new(exchanges.into()),
+        }
+    }
+
+    /// Serve the exchanges of the fixture file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(ReplayClient::new(load_fixture(path)?))
+    }
+
+    /// The exchanges that were not used yet.
+    pub fn remaining(&self) -> Vec<Exchange> {
+        self.exchanges.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Panic if some of the exchanges were not used.
+    pub fn assert_exhausted(&self) {
+        let remaining = self.remaining();
+        if !remaining.is_empty() {
+            let calls: Vec<_> = remaining
+                .iter()
+                .map(|e| format!("{} {}", e.method, serde_json::Value::from(e.params.clone())))
+                .collect();
+            panic!("{} recorded calls were not made: {}", calls.len(), calls.join(", "));
+        }
+    }
+}
+
+#[async_trait]
+impl RpcApi for ReplayClient {
+    async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        let params = redact_params(cmd, args);
+        let exchange = {
+            let mut exchanges = self.exchanges.lock().unwrap();
+            let pos = exchanges.iter().position(|e| e.method == cmd && e.params == params);
+            pos.and_then(|pos| exchanges.remove(pos))
+        };
+        let res = match exchange {
+            Some(Exchange {
+                error: Some(e),
+                ..
+            }) => Err(Error::JsonRpc(jsonrpc_async::Error::Rpc(e))),
+            Some(Exchange {
+                result,
+                ..
+            }) => parse_value(&result.unwrap_or(serde_json::Value::Null)),
+            None => Err(Error::Custom(format!(
+                "no recorded call matches {} {}",
+                cmd,
+                serde_json::Value::from(params.clone())
+            ))),
+        };
+        res.map_err(|e| Error::Call {
+            method: cmd.to_owned(),
+            params,
+            source: Box::new(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RpcErrorCode;
+
+    /// Answers `getblockcount` with the number of calls so far, and fails
+    /// every other call.
+    #[derive(Default)]
+    struct Counter(Mutex<u64>);
+
+    #[async_trait]
+    impl RpcApi for Counter {
+        async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+            &self,
+            cmd: &str,
+            _: &[serde_json::Value],
+        ) -> Result<T> {
+            if cmd != "getblockcount" {
+                return Err(Error::JsonRpc(jsonrpc_async::Error::Rpc(
+                    jsonrpc_async::error::RpcError {
+                        code: -32601,
+                        message: "Method not found".into(),
+                        data: None,
+                    },
+                )));
+            }
+            let mut count = self.0.lock().unwrap();
+            *count += 1;
+            Ok(serde_json::from_value((*count).into())?)
+        }
+    }
+
+    #[tokio::test]
+    async fn records_and_replays() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("fixture.json");
+
+        let rpc = RecordingClient::new(Counter::default(), &path);
+        assert_eq!(rpc.get_block_count().await.unwrap(), 1);
+        assert_eq!(rpc.get_block_count().await.unwrap(), 2);
+        assert!(rpc.call::<u64>("getblocks", &[]).await.is_err());
+        let _ = rpc.call::<()>("walletpassphrase", &["secret".into(), 60.into()]).await;
+        assert_eq!(load_fixture(&path).unwrap().len(), 4);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("secret"));
+
+        let rpc = ReplayClient::load(&path).unwrap();
+        assert_eq!(rpc.get_block_count().await.unwrap(), 1);
+        let err = rpc.call::<u64>("getblocks", &[]).await.unwrap_err();
+        assert_eq!(err.rpc_code(), Some(RpcErrorCode::MethodNotFound));
+        assert_eq!(rpc.get_block_count().await.unwrap(), 2);
+        // Only the passphrase is redacted, the other params must match.
+        let err = rpc.call::<()>("walletpassphrase", &["secret".into(), 30.into()]).await;
+        assert!(matches!(err.unwrap_err().root(), Error::Custom(ref m) if m.contains("30")));
+        let err = rpc.call::<()>("walletpassphrase", &["other".into(), 60.into()]).await;
+        assert_eq!(err.unwrap_err().rpc_code(), Some(RpcErrorCode::MethodNotFound));
+
+        let err = rpc.get_block_count().await.unwrap_err();
+        assert!(matches!(err.root(), Error::Custom(ref m) if m.contains("getblockcount")));
+        rpc.assert_exhausted();
+    }
+
+    #[tokio::test]
+    async fn keeps_calls_that_were_not_recorded() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let rpc = RecordingClient::new(Counter::default(), tempdir.path().join("no/such/dir"));
+        assert_eq!(rpc.get_block_count().await.unwrap(), 1);
+        assert!(matches!(rpc.take_write_error(), Some(Error::Io(_))));
+        assert!(rpc.take_write_error().is_none());
+
+        let err = rpc.call::<String>("getblockcount", &[]).await.unwrap_err();
+        assert_eq!(err.method(), Some("getblockcount"));
+        assert!(matches!(err.root(), Error::Deserialize { .. }));
+
+        let rpc = ReplayClient::new(rpc.exchanges());
+        let err = rpc.call::<String>("getblockcount", &[]).await.unwrap_err();
+        assert_eq!(err.method(), Some("getblockcount"));
+        assert!(matches!(err.root(), Error::Deserialize { .. }));
+    }
+}
@@ -0,0 +1,404 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! The HTTP transport used by [Client::with_options], with support for TLS,
+//! extra headers and proxies.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use jsonrpc_async::simple_http;
+use jsonrpc_async::{Request, Response, Transport};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{Certificate, Proxy, StatusCode, Url};
+
+use crate::client::{Auth, Client, Result};
+use crate::error::Error;
+use crate::http::url_encode;
+use crate::transport::Connector;
+
+/// Options for connections that plain HTTP with basic authentication doesn't
+/// cover, see [Client::with_options].
+///
+/// Requires the `reqwest` feature.
+#[derive(Clone, Debug, Default)]
+pub struct HttpOptions {
+    /// Headers to send with every request, for instance the API key of a
+    /// gateway in front of the node.
+    pub headers: Vec<(String, String)>,
+    /// PEM encoded certificates to trust for `https://` URLs, such as the
+    /// self-signed certificate of the node or the certificate of a private CA.
+    ///
+    /// If any are given, they are the only certificates trusted, so the
+    /// connection is pinned to them. Otherwise the usual public certificate
+    /// authorities are trusted.
+    pub root_certificates: Vec<Vec<u8>>,
+    /// The proxy to connect through, for instance `socks5h://127.0.0.1:9050`
+    /// for Tor. With `socks5h://` host names are resolved by the proxy, which
+    /// onion addresses need.
+    pub proxy: Option<String>,
+}
+
+impl Client {
+    /// Creates a client to a node at an `http://` or `https://` URL, with the
+    /// given connection options.
+    ///
+    /// ```no_run
+    /// # fn example() -> defichain_rpc::Result<()> {
+    /// use defichain_rpc::{Auth, Client, HttpOptions};
+    ///
+    /// let options = HttpOptions {
+    ///     headers: vec![("X-Api-Key".into(), "secret".into())],
+    ///     root_certificates: vec![std::fs::read("node.pem")?],
+    ///     proxy: Some("socks5h://127.0.0.1:9050".into()),
+    /// };
+    /// let auth = Auth::UserPass("user".into(), "pass".into());
+    /// let client = Client::with_options("https://node.example.onion", auth, options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Requires the `reqwest` feature.
+    pub fn with_options(url: &str, auth: Auth, options: HttpOptions) -> Result<Client> {
+        Ok(Client::with_connector(HttpsConfig::new(url, auth, options)?))
+    }
+}
+
+/// The connection settings shared by all transports of a client.
+pub(crate) struct HttpsConfig {
+    client: reqwest::Client,
+    url: Url,
+    credentials: RwLock<(Option<String>, Option<String>)>,
+    /// The cookie file to re-read when the node rejects the credentials.
+    cookie_file: Option<PathBuf>,
+}
+
+impl HttpsConfig {
+    pub(crate) fn new(url: &str, auth: Auth, options: HttpOptions) -> Result<Arc<HttpsConfig>> {
+        let url =
+            Url::parse(url).map_err(|e| Error::Custom(format!("invalid URL {}: {}", url, e)))?;
+        let cookie_file = match auth {
+            Auth::CookieFile(ref path) => Some(path.clone()),
+            _ => None,
+        };
+        let credentials = auth.get_user_pass()?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in options.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| Error::Custom(format!("invalid header name {}: {}", name, e)))?;
+            let value = HeaderValue::from_str(&value)
+                .map_err(|e| Error::Custom(format!("invalid value of header {}: {}", name, e)))?;
+            headers.append(name, value);
+        }
+        let mut builder = reqwest::Client::builder().default_headers(headers);
+        if !options.root_certificates.is_empty() {
+            builder = builder.tls_built_in_root_certs(false);
+        }
+        for pem in options.root_certificates {
+            let certs = Certificate::from_pem_bundle(&pem)
+                .map_err(|e| Error::Custom(format!("invalid certificate: {}", e)))?;
+            if certs.is_empty() {
+                return Err(Error::Custom("no certificate in PEM data".into()));
+            }
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        if let Some(proxy) = options.proxy {
+            let proxy = Proxy::all(&proxy)
+                .map_err(|e| Error::Custom(format!("invalid proxy {}: {}", proxy, e)))?;
+            builder = builder.proxy(proxy);
+        }
+        let client = builder.build().map_err(|e| Error::Custom(e.to_string()))?;
+
+        Ok(Arc::new(HttpsConfig {
+            client,
+            url,
+            credentials: RwLock::new(credentials),
+            cookie_file,
+        }))
+    }
+
+    /// Re-read the cookie file, returning whether the credentials were updated.
+    fn refresh_cookie(&self) -> bool {
+        let path = match self.cookie_file {
+            Some(ref path) => path.clone(),
+            None => return false,
+        };
+        match Auth::CookieFile(path).get_user_pass() {
+            Ok(credentials) => {
+                *self.credentials.write().unwrap() = credentials;
+                true
+            }
+            Err(e) => {
+                warn!(target: "defichain_rpc", "failed to re-read the cookie file: {}", e);
+                false
+            }
+        }
+    }
+}
+
+impl Connector for Arc<HttpsConfig> {
+    fn transport(&self, timeout: Duration, wallet: Option<&str>) -> Box<dyn Transport> {
+        let mut url = self.url.clone();
+        if let Some(wallet) = wallet {
+            url.set_path(&format!("/wallet/{}", url_encode(wallet)));
+        }
+        Box::new(HttpsTransport {
+            config: self.clone(),
+            url,
+            timeout,
+        })
+    }
+}
+
+/// A [reqwest] transport that re-reads the cookie file and retries once when
+/// the node rejects the credentials.
+struct HttpsTransport {
+    config: Arc<HttpsConfig>,
+    url: Url,
+    timeout: Duration,
+}
+
+impl HttpsTransport {
+    async fn post<B, R>(&self, body: &B) -> std::result::Result<R, jsonrpc_async::Error>
+    where
+        B: serde::Serialize + ?Sized,
+        R: for<'a> serde::de::Deserialize<'a>,
+    {
+        let mut res = self.send(body).await?;
+        if res.status() == StatusCode::UNAUTHORIZED && self.config.refresh_cookie() {
+            debug!(target: "defichain_rpc", "retrying request to {} with a refreshed cookie", self.url);
+            res = self.send(body).await?;
+        }
+        let status = res.status();
+        let bytes = res.bytes().await.map_err(transport_error)?;
+        // Like the built-in transport, prefer the JSON-RPC error in the body
+        // to the less meaningful HTTP status.
+        serde_json::from_slice(&bytes).map_err(|e| {
+            if status.is_success() {
+                jsonrpc_async::Error::Json(e)
+            } else {
+                transport_error(simple_http::Error::HttpErrorCode(status.as_u16()))
+            }
+        })
+    }
+
+    async fn send<B: serde::Serialize + ?Sized>(
+        &self,
+        body: &B,
+    ) -> std::result::Result<reqwest::Response, jsonrpc_async::Error> {
+        let body = serde_json::to_vec(body).map_err(jsonrpc_async::Error::Json)?;
+        let mut req = self
+            .config
+            .client
+            .post(self.url.clone())
+            .timeout(self.timeout)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body);
+        let (user, pass) = self.config.credentials.read().unwrap().clone();
+        if let Some(user) = user {
+            req = req.basic_auth(user, pass);
+        }
+        req.send().await.map_err(transport_error)
+    }
+}
+
+fn transport_error<E>(e: E) -> jsonrpc_async::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    jsonrpc_async::Error::Transport(Box::new(e))
+}
+
+#[async_trait]
+impl Transport for HttpsTransport {
+    async fn send_request(
+        &self,
+        r: Request<'_>,
+    ) -> std::result::Result<Response, jsonrpc_async::Error> {
+        self.post(&r).await
+    }
+
+    async fn send_batch(
+        &self,
+        rs: &[Request<'_>],
+    ) -> std::result::Result<Vec<Response>, jsonrpc_async::Error> {
+        self.post(rs).await
+    }
+
+    fn fmt_target(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RpcApi;
+    use tokio::io::{
+        AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+    };
+    use tokio::net::TcpListener;
+    use tokio_rustls::rustls::pki_types::PrivateKeyDer;
+    use tokio_rustls::rustls::ServerConfig;
+    use tokio_rustls::TlsAcceptor;
+
+    /// Serves over plain HTTP.
+    async fn serve(listener: TcpListener) {
+        while let Ok((conn, _)) = listener.accept().await {
+            tokio::spawn(answer(conn));
+        }
+    }
+
+    /// Serves over TLS with a self-signed certificate for "localhost", which
+    /// is returned as PEM.
+    fn serve_tls(listener: TcpListener) -> String {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let key = PrivateKeyDer::Pkcs8(cert.key_pair.serialize_der().into());
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert.cert.der().clone()], key)
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        tokio::spawn(async move {
+            while let Ok((conn, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    // Clients that don't trust the certificate abort the handshake.
+                    if let Ok(conn) = acceptor.accept(conn).await {
+                        answer(conn).await;
+                    }
+                });
+            }
+        });
+        cert.cert.pem()
+    }
+
+    /// Answer requests with their path, rejecting those that don't use the
+    /// "__cookie__:new" cookie or lack the "x-api-key" header.
+    async fn answer<S: AsyncRead + AsyncWrite + Unpin>(conn: S) {
+        let mut conn = BufReader::new(conn);
+        loop {
+            let (mut authorized, mut api_key, mut len) = (false, false, 0);
+            let mut request_line = String::new();
+            if !matches!(conn.read_line(&mut request_line).await, Ok(n) if n > 0) {
+                return;
+            }
+            let path = request_line.split(' ').nth(1).unwrap().to_owned();
+            loop {
+                let mut line = String::new();
+                conn.read_line(&mut line).await.unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                // reqwest sends header names in lower case.
+                let mut header = line.trim_end().splitn(2, ": ");
+                let (name, value) = (header.next().unwrap(), header.next().unwrap());
+                match name.to_lowercase().as_str() {
+                    "authorization" => authorized = value == "Basic X19jb29raWVfXzpuZXc=",
+                    "x-api-key" => api_key = value == "secret",
+                    "content-length" => len = value.parse().unwrap(),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; len];
+            conn.read_exact(&mut body).await.unwrap();
+            let req: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let (status, body) = match (authorized, api_key) {
+                (true, true) => (
+                    "200 OK",
+                    serde_json::json!({"result": path, "error": null, "id": req["id"]}).to_string(),
+                ),
+                (false, _) => ("401 Unauthorized", String::new()),
+                (true, false) => ("403 Forbidden", String::new()),
+            };
+            let resp =
+                format!("HTTP/1.1 {}\r\nContent-Length: {}\r\n\r\n{}", status, body.len(), body);
+            conn.get_mut().write_all(resp.as_bytes()).await.unwrap();
+            conn.get_mut().flush().await.unwrap();
+        }
+    }
+
+    fn options() -> HttpOptions {
+        HttpOptions {
+            headers: vec![("X-Api-Key".into(), "secret".into())],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_headers_and_refreshes_cookie() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve(listener));
+
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join(".cookie");
+        std::fs::write(&path, "__cookie__:old").unwrap();
+        let client = Client::with_options(&url, Auth::CookieFile(path.clone()), options()).unwrap();
+
+        // The node restarted and rotated its cookie.
+        std::fs::write(&path, "__cookie__:new").unwrap();
+        assert_eq!(client.call::<String>("getblockcount", &[]).await.unwrap(), "/");
+        let wallet = client.wallet("my wallet/1").unwrap();
+        assert_eq!(
+            wallet.call::<String>("getbalance", &[]).await.unwrap(),
+            "/wallet/my%20wallet%2F1"
+        );
+
+        let auth = Auth::UserPass("__cookie__".into(), "new".into());
+        let client = Client::with_options(&url, auth, HttpOptions::default()).unwrap();
+        let err = client.get_block_count().await.unwrap_err();
+        assert!(matches!(
+            err.root(),
+            Error::JsonRpc(jsonrpc_async::Error::Transport(ref e))
+                if matches!(e.downcast_ref(), Some(simple_http::Error::HttpErrorCode(403)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn verifies_certificates() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("https://localhost:{}", listener.local_addr().unwrap().port());
+        let pem = serve_tls(listener);
+        let auth = || Auth::UserPass("__cookie__".into(), "new".into());
+
+        let trusted = HttpOptions {
+            root_certificates: vec![pem.into_bytes()],
+            ..options()
+        };
+        let client = Client::with_options(&url, auth(), trusted).unwrap();
+        assert_eq!(client.call::<String>("getblockcount", &[]).await.unwrap(), "/");
+
+        // The self-signed certificate is not trusted by default.
+        let client = Client::with_options(&url, auth(), options()).unwrap();
+        let err = client.call::<String>("getblockcount", &[]).await.unwrap_err();
+        assert!(!err.is_transient());
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let options = HttpOptions {
+            root_certificates: vec![b"not a certificate".to_vec()],
+            ..Default::default()
+        };
+        assert!(Client::with_options("https://localhost", Auth::None, options).is_err());
+        let options = HttpOptions {
+            headers: vec![("X Api Key".into(), "secret".into())],
+            ..Default::default()
+        };
+        assert!(Client::with_options("https://localhost", Auth::None, options).is_err());
+    }
+}
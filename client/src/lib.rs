@@ -30,15 +30,44 @@ pub use defichain_rpc_json as json;
 use json::bitcoin::consensus::{Decodable, ReadExt};
 use json::bitcoin::hex::HexToBytesIter;
 
+mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod client;
+pub mod config;
+pub mod coverage;
 pub mod error;
+pub mod fixture;
+mod http;
+#[cfg(feature = "reqwest")]
+mod https;
+pub mod middleware;
+pub mod mock;
+pub mod multinode;
 mod queryable;
+pub mod raw;
+pub mod requests;
+pub mod retry;
+pub mod schema;
+pub mod timeout;
 mod traits;
+pub mod transport;
 
+pub use crate::batch::*;
 pub use crate::client::*;
-pub use crate::error::Error;
+pub use crate::error::{CustomTxReject, Error, RejectReason, RpcErrorCode};
+pub use crate::fixture::{RecordingClient, ReplayClient};
+#[cfg(feature = "reqwest")]
+pub use crate::https::HttpOptions;
+pub use crate::middleware::{Middleware, MiddlewareClient};
+pub use crate::multinode::MultiNodeClient;
 pub use crate::queryable::*;
+pub use crate::raw::Raw;
+pub use crate::requests::RpcRequest;
+pub use crate::retry::{RetryClient, RetryPolicy};
+pub use crate::timeout::WithTimeout;
 pub use crate::traits::*;
+pub use crate::transport::Connector;
 
 fn deserialize_hex<T: Decodable>(hex: &str) -> Result<T> {
     let mut reader = HexToBytesIter::new(&hex)?;
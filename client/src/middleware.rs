@@ -0,0 +1,300 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Hooks around every call.
+//!
+//! [MiddlewareClient] wraps any [RpcApi] implementation and runs a stack of
+//! [Middleware]s around every call, which is the place to plug in tracing,
+//! metrics, auditing or rate limiting. Since all domain traits are built on
+//! [RpcApi::call], they all go through the stack.
+//!
+//! ```no_run
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use async_trait::async_trait;
+//! use defichain_rpc::middleware::{CallInfo, Middleware, MiddlewareClient};
+//! use defichain_rpc::{Auth, Client, Error, PoolPairRPC};
+//!
+//! struct Audit;
+//!
+//! #[async_trait]
+//! impl Middleware for Audit {
+//!     async fn after(
+//!         &self,
+//!         call: &CallInfo<'_>,
+//!         result: Result<&serde_json::Value, &Error>,
+//!     ) {
+//!         println!("{} took {:?}, ok: {}", call.method, call.elapsed(), result.is_ok());
+//!     }
+//! }
+//!
+//! let client = Client::new("http://localhost:8554", Auth::None).await?;
+//! let rpc = MiddlewareClient::new(client).with(Audit);
+//! let pairs = rpc.list_pool_pairs(None, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::client::{parse_value, redact_params, Result, RpcApi};
+use crate::error::Error;
+
+/// A call as seen by a [Middleware].
+#[derive(Debug)]
+pub struct CallInfo<'a> {
+    /// The name of the RPC method.
+    pub method: &'a str,
+    /// The parameters of the call.
+    pub params: &'a [serde_json::Value],
+    /// When the call was started, before the first middleware ran.
+    pub started: Instant,
+}
+
+impl<'a> CallInfo<'a> {
+    /// The time since the call was started.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// Hooks that run before and after every call of a [MiddlewareClient].
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Called before the call is made.
+    ///
+    /// Returning an error aborts the call with that error. The hooks of the
+    /// middlewares that already ran are still called with the error.
+    async fn before(&self, _call: &CallInfo<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called after the call completed, with the raw result or the error.
+    async fn after(
+        &self,
+        _call: &CallInfo<'_>,
+        _result: std::result::Result<&serde_json::Value, &Error>,
+    ) {
+    }
+}
+
+#[async_trait]
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    async fn before(&self, call: &CallInfo<'_>) -> Result<()> {
+        (**self).before(call).await
+    }
+
+    async fn after(
+        &self,
+        call: &CallInfo<'_>,
+        result: std::result::Result<&serde_json::Value, &Error>,
+    ) {
+        (**self).after(call, result).await
+    }
+}
+
+/// An [RpcApi] implementation that runs a stack of [Middleware]s around
+/// every call of the wrapped one.
+///
+/// The `before` hooks run in the order the middlewares were added, the `after`
+/// hooks in reverse order.
+pub struct MiddlewareClient<C> {
+    inner: C,
+    stack: Vec<Arc<dyn Middleware>>,
+}
+
+impl<C: fmt::Debug> fmt::Debug for MiddlewareClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "defichain_rpc::MiddlewareClient({:?}, {} middlewares)",
+            self.inner,
+            self.stack.len()
+        )
+    }
+}
+
+impl<C: RpcApi + Sync + Send> MiddlewareClient<C> {
+    /// Wrap `inner` with an empty stack.
+    pub fn new(inner: C) -> Self {
+        MiddlewareClient {
+            inner,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Add a middleware on top of the stack.
+    pub fn with<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.stack.push(Arc::new(middleware));
+        self
+    }
+
+    /// Get the wrapped client.
+    pub fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+#[async_trait]
+impl<C: RpcApi + Sync + Send> RpcApi for MiddlewareClient<C> {
+    async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        let call = CallInfo {
+            method: cmd,
+            params: args,
+            started: Instant::now(),
+        };
+
+        let mut ran = 0;
+        let mut result = Ok(serde_json::Value::Null);
+        for middleware in self.stack.iter() {
+            ran += 1;
+            if let Err(e) = middleware.before(&call).await {
+                result = Err(e);
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = self.inner.call(cmd, args).await;
+        }
+        for middleware in self.stack[..ran].iter().rev() {
+            middleware.after(&call, result.as_ref()).await;
+        }
+        // Like direct calls, keep the method and params, and the location of
+        // a result that can't be deserialized.
+        parse_value(&result?).map_err(|e| Error::Call {
+            method: cmd.to_owned(),
+            params: redact_params(cmd, args),
+            source: Box::new(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Answers every call with its method name.
+    struct Echo;
+
+    #[async_trait]
+    impl RpcApi for Echo {
+        async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+            &self,
+            cmd: &str,
+            _: &[serde_json::Value],
+        ) -> Result<T> {
+            Ok(serde_json::from_value(cmd.into())?)
+        }
+    }
+
+    /// Records the hooks it sees, and rejects calls of `reject`.
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        reject: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl Middleware for Recorder {
+        async fn before(&self, call: &CallInfo<'_>) -> Result<()> {
+            self.log.lock().unwrap().push(format!("{} before {}", self.name, call.method));
+            if self.reject == Some(call.method) {
+                return Err(Error::Custom("rejected".into()));
+            }
+            Ok(())
+        }
+
+        async fn after(
+            &self,
+            call: &CallInfo<'_>,
+            result: std::result::Result<&serde_json::Value, &Error>,
+        ) {
+            let outcome = match result {
+                Ok(v) => v.to_string(),
+                Err(e) => e.to_string(),
+            };
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} after {} {}", self.name, call.method, outcome));
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_hooks_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let rpc = MiddlewareClient::new(Echo)
+            .with(Recorder {
+                name: "a",
+                log: log.clone(),
+                reject: None,
+            })
+            .with(Recorder {
+                name: "b",
+                log: log.clone(),
+                reject: Some("stop"),
+            })
+            .with(Recorder {
+                name: "c",
+                log: log.clone(),
+                reject: None,
+            });
+
+        let res: String = rpc.call("uptime", &[]).await.unwrap();
+        assert_eq!(res, "uptime");
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "a before uptime",
+                "b before uptime",
+                "c before uptime",
+                "c after uptime \"uptime\"",
+                "b after uptime \"uptime\"",
+                "a after uptime \"uptime\"",
+            ]
+        );
+
+        log.lock().unwrap().clear();
+        assert!(rpc.call::<String>("stop", &[]).await.is_err());
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "a before stop",
+                "b before stop",
+                "b after stop Custom error: rejected",
+                "a after stop Custom error: rejected",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_context_of_deserialize_errors() {
+        let rpc = MiddlewareClient::new(Echo);
+        let err = rpc.call::<u64>("uptime", &[1.into()]).await.unwrap_err();
+        assert_eq!(err.method(), Some("uptime"));
+        assert!(matches!(err, Error::Call { ref params, .. } if params[0] == 1));
+        assert!(
+            matches!(err.root(), Error::Deserialize { ref snippet, .. } if snippet.contains("uptime"))
+        );
+    }
+}
@@ -0,0 +1,443 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! A scriptable in-memory node for unit tests.
+//!
+//! [MockNode] implements [RpcApi], so all domain traits can be used on it. It
+//! keeps a small [MockState] with blocks, tokens, pool pairs, vaults and
+//! account balances, answers a few common calls from it, and can be scripted
+//! with handlers for any other call. Every request is recorded, so tests can
+//! assert on exactly what a method sends:
+//!
+//! ```
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use defichain_rpc::mock::MockNode;
+//! use defichain_rpc::TokenRPC;
+//! use serde_json::json;
+//!
+//! let node = MockNode::new();
+//! node.state().add_token("BTC");
+//!
+//! let tokens = node.list_tokens(None, Some(true)).await?;
+//! assert_eq!(tokens.0["1"].symbol, "BTC");
+//! node.assert_last_request("listtokens", json!([{}, true]));
+//! # Ok(())
+//! # }
+//! # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use jsonrpc_async::error::RpcError;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::bitcoin::hashes::{sha256d, Hash};
+use crate::bitcoin::{BlockHash, Txid};
+use crate::client::{Result, RpcApi};
+use crate::error::{Error, RpcErrorCode};
+use crate::json::poolpair::PoolPairInfo;
+use crate::json::token::TokenInfo;
+
+/// A handler for the calls of a method.
+pub type Handler =
+    Box<dyn Fn(&[Value], &mut MockState) -> std::result::Result<Value, RpcError> + Send + Sync>;
+
+/// An error as the node would return it.
+pub fn rpc_error(code: RpcErrorCode, message: &str) -> RpcError {
+    RpcError {
+        code: code.code(),
+        message: message.to_owned(),
+        data: None,
+    }
+}
+
+/// The parameter at `index` of a call, or an error as the node would return it
+/// if it is missing or has the wrong type.
+pub fn param<T: DeserializeOwned>(
+    params: &[Value],
+    index: usize,
+) -> std::result::Result<T, RpcError> {
+    let value = params.get(index).cloned().unwrap_or(Value::Null);
+    serde_json::from_value(value).map_err(|e| {
+        rpc_error(RpcErrorCode::TypeError, &format!("invalid parameter {}: {}", index, e))
+    })
+}
+
+/// Deserialize a parameter type that has no public constructor.
+#[cfg(test)]
+pub(crate) fn from_json<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).unwrap()
+}
+
+/// A request received by a [MockNode].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockRequest {
+    pub method: String,
+    pub params: Vec<Value>,
+}
+
+/// The state of a [MockNode].
+///
+/// Tokens, pool pairs and vaults are kept by their id, balances by owner and
+/// token id. It starts with the genesis block and the DFI token.
+#[derive(Debug)]
+pub struct MockState {
+    pub blocks: Vec<BlockHash>,
+    pub tokens: BTreeMap<String, TokenInfo>,
+    pub pool_pairs: BTreeMap<String, PoolPairInfo>,
+    pub vaults: BTreeMap<String, Value>,
+    pub balances: BTreeMap<String, BTreeMap<String, f64>>,
+    /// The number of transactions created so far.
+    pub txs: u64,
+}
+
+impl Default for MockState {
+    fn default() -> Self {
+        let mut state = MockState {
+            blocks: Vec::new(),
+            tokens: BTreeMap::new(),
+            pool_pairs: BTreeMap::new(),
+            vaults: BTreeMap::new(),
+            balances: BTreeMap::new(),
+            txs: 0,
+        };
+        state.mine(1);
+        state.add_token("DFI");
+        state
+    }
+}
+
+impl MockState {
+    /// Append `count` blocks, returning their hashes.
+    pub fn mine(&mut self, count: u64) -> Vec<BlockHash> {
+        (0..count)
+            .map(|_| {
+                let height = self.blocks.len() as u64;
+                let hash = BlockHash::from_raw_hash(sha256d::Hash::hash(&height.to_le_bytes()));
+                self.blocks.push(hash);
+                hash
+            })
+            .collect()
+    }
+
+    /// The id of a new transaction.
+    pub fn next_txid(&mut self) -> Txid {
+        self.txs += 1;
+        Txid::from_raw_hash(sha256d::Hash::hash(&self.txs.to_le_bytes()))
+    }
+
+    /// Add a token with the next free id, returning the id.
+    pub fn add_token(&mut self, symbol: &str) -> String {
+        let id = self.tokens.len().to_string();
+        let creation_tx = self.next_txid().to_string();
+        self.tokens.insert(
+            id.clone(),
+            TokenInfo {
+                symbol: symbol.to_owned(),
+                symbol_key: symbol.to_owned(),
+                name: symbol.to_owned(),
+                decimal: 8,
+                limit: 0,
+                mintable: true,
+                tradeable: true,
+                is_dat: true,
+                is_lps: false,
+                is_loan_token: false,
+                finalized: false,
+                minted: 0.0,
+                creation_tx,
+                creation_height: self.blocks.len() as i64 - 1,
+                destruction_tx: "0".repeat(64),
+                destruction_height: -1,
+                collateral_address: None,
+            },
+        );
+        id
+    }
+
+    /// The id of the token with the given id or symbol.
+    pub fn token_id(&self, key: &str) -> Option<String> {
+        if self.tokens.contains_key(key) {
+            return Some(key.to_owned());
+        }
+        self.tokens.iter().find(|(_, t)| t.symbol_key == key).map(|(id, _)| id.clone())
+    }
+
+    /// Set the balance of `owner` in the token with the given id or symbol.
+    ///
+    /// Panics if there is no such token.
+    pub fn set_balance(&mut self, owner: &str, token: &str, amount: f64) {
+        let id = self.token_id(token).expect("unknown token");
+        self.balances.entry(owner.to_owned()).or_default().insert(id, amount);
+    }
+
+    /// The balance of `owner` in the token with the given id or symbol.
+    pub fn balance(&self, owner: &str, token: &str) -> f64 {
+        let id = match self.token_id(token) {
+            Some(id) => id,
+            None => return 0.0,
+        };
+        self.balances.get(owner).and_then(|b| b.get(&id)).copied().unwrap_or(0.0)
+    }
+
+    /// Parse an amount like `1.5@BTC`.
+    fn parse_amount(&self, amount: &str) -> std::result::Result<(String, f64), RpcError> {
+        let invalid = || rpc_error(RpcErrorCode::InvalidParameter, "Invalid amount");
+        let at = amount.find('@').ok_or_else(invalid)?;
+        let value = amount[..at].parse().map_err(|_| invalid())?;
+        let token = self
+            .token_id(&amount[at + 1..])
+            .ok_or_else(|| rpc_error(RpcErrorCode::InvalidParameter, "Invalid Defi token"))?;
+        Ok((token, value))
+    }
+
+    fn account_to_account(&mut self, params: &[Value]) -> std::result::Result<Value, RpcError> {
+        let from: String = param(params, 0)?;
+        let to: BTreeMap<String, String> = param(params, 1)?;
+        for (owner, amount) in to {
+            let (token, amount) = self.parse_amount(&amount)?;
+            let available = self.balance(&from, &token);
+            if available < amount {
+                return Err(rpc_error(
+                    RpcErrorCode::InvalidRequest,
+                    &format!(
+                        "Test AccountToAccountTx execution failed:\namount {:.8} is less than {:.8}",
+                        available, amount
+                    ),
+                ));
+            }
+            self.set_balance(&from, &token, available - amount);
+            let received = self.balance(&owner, &token);
+            self.set_balance(&owner, &token, received + amount);
+        }
+        Ok(self.next_txid().to_string().into())
+    }
+
+    fn get_account(&self, params: &[Value]) -> std::result::Result<Value, RpcError> {
+        let owner: String = param(params, 0)?;
+        let indexed: Option<bool> = param(params, 2)?;
+        let balances = self.balances.get(&owner).cloned().unwrap_or_default();
+        if indexed.unwrap_or(false) {
+            return Ok(serde_json::to_value(balances).unwrap());
+        }
+        let amounts: Vec<String> = balances
+            .iter()
+            .map(|(id, amount)| format!("{:.8}@{}", amount, self.tokens[id].symbol_key))
+            .collect();
+        Ok(amounts.into())
+    }
+}
+
+/// Look up an entry by id, or by the key that `key_of` returns.
+fn lookup<'a, T, F: Fn(&T) -> &str>(
+    map: &'a BTreeMap<String, T>,
+    key: &str,
+    key_of: F,
+) -> Option<(&'a String, &'a T)> {
+    map.get_key_value(key).or_else(|| map.iter().find(|(_, v)| key_of(v) == key))
+}
+
+/// An in-memory node implementing [RpcApi].
+///
+/// Calls are answered by the handler registered for their method with
+/// [MockNode::on], or by the built-in handlers for `getblockcount`,
+/// `getbestblockhash`, `getblockhash`, `generatetoaddress`, `listtokens`,
+/// `gettoken`, `listpoolpairs`, `getpoolpair`, `listvaults`, `getvault`,
+/// `getaccount` and `accounttoaccount`. Other calls fail with
+/// [RpcErrorCode::MethodNotFound].
+pub struct MockNode {
+    handlers: HashMap<String, Handler>,
+    state: Mutex<MockState>,
+    requests: Mutex<Vec<MockRequest>>,
+}
+
+impl fmt::Debug for MockNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::MockNode({} requests)", self.requests.lock().unwrap().len())
+    }
+}
+
+impl Default for MockNode {
+    fn default() -> Self {
+        MockNode::new()
+    }
+}
+
+impl MockNode {
+    /// A node with the default [MockState] and the built-in handlers.
+    pub fn new() -> Self {
+        MockNode {
+            handlers: HashMap::new(),
+            state: Mutex::new(MockState::default()),
+            requests: Mutex::new(Vec::new()),
+        }
+        .on("getblockcount", |_, s| Ok((s.blocks.len() - 1).into()))
+        .on("getbestblockhash", |_, s| Ok(s.blocks.last().unwrap().to_string().into()))
+        .on("getblockhash", |p, s| {
+            let height: usize = param(p, 0)?;
+            s.blocks.get(height).map(|h| h.to_string().into()).ok_or_else(|| {
+                rpc_error(RpcErrorCode::InvalidParameter, "Block height out of range")
+            })
+        })
+        .on("generatetoaddress", |p, s| {
+            let count: u64 = param(p, 0)?;
+            Ok(s.mine(count).iter().map(|h| h.to_string()).collect::<Vec<_>>().into())
+        })
+        .on("listtokens", |_, s| Ok(serde_json::to_value(&s.tokens).unwrap()))
+        .on("gettoken", |p, s| {
+            let key: String = param(p, 0)?;
+            let (id, token) = lookup(&s.tokens, &key, |t| &t.symbol_key)
+                .ok_or_else(|| rpc_error(RpcErrorCode::InvalidAddressOrKey, "Token not found"))?;
+            Ok(serde_json::json!({ id: token }))
+        })
+        .on("listpoolpairs", |_, s| Ok(serde_json::to_value(&s.pool_pairs).unwrap()))
+        .on("getpoolpair", |p, s| {
+            let key: String = param(p, 0)?;
+            let (id, pair) = lookup(&s.pool_pairs, &key, |p| &p.symbol)
+                .ok_or_else(|| rpc_error(RpcErrorCode::InvalidAddressOrKey, "Pool not found"))?;
+            Ok(serde_json::json!({ id: pair }))
+        })
+        .on("listvaults", |_, s| Ok(s.vaults.values().cloned().collect::<Vec<_>>().into()))
+        .on("getvault", |p, s| {
+            let id: String = param(p, 0)?;
+            s.vaults.get(&id).cloned().ok_or_else(|| {
+                rpc_error(RpcErrorCode::InvalidParameter, &format!("Vault <{}> not found", id))
+            })
+        })
+        .on("getaccount", |p, s| s.get_account(p))
+        .on("accounttoaccount", |p, s| s.account_to_account(p))
+    }
+
+    /// Answer the calls of `method` with `handler`, replacing any previous one.
+    pub fn on<F>(mut self, method: &str, handler: F) -> Self
+    where
+        F: Fn(&[Value], &mut MockState) -> std::result::Result<Value, RpcError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.handlers.insert(method.to_owned(), Box::new(handler));
+        self
+    }
+
+    /// Answer the calls of `method` with `result`.
+    pub fn respond(self, method: &str, result: Value) -> Self {
+        self.on(method, move |_, _| Ok(result.clone()))
+    }
+
+    /// The state of the node.
+    pub fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap()
+    }
+
+    /// All requests received so far.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The last request received.
+    pub fn last_request(&self) -> Option<MockRequest> {
+        self.requests.lock().unwrap().last().cloned()
+    }
+
+    /// Forget the requests received so far.
+    pub fn clear_requests(&self) {
+        self.requests.lock().unwrap().clear()
+    }
+
+    /// Panic unless the last request has the given method and parameters.
+    pub fn assert_last_request(&self, method: &str, params: Value) {
+        let expected = MockRequest {
+            method: method.to_owned(),
+            params: match params {
+                Value::Array(params) => params,
+                params => vec![params],
+            },
+        };
+        assert_eq!(self.last_request().as_ref(), Some(&expected));
+    }
+}
+
+#[async_trait]
+impl RpcApi for MockNode {
+    async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        cmd: &str,
+        args: &[Value],
+    ) -> Result<T> {
+        self.requests.lock().unwrap().push(MockRequest {
+            method: cmd.to_owned(),
+            params: args.to_vec(),
+        });
+        let res = match self.handlers.get(cmd) {
+            Some(handler) => handler(args, &mut self.state()),
+            None => Err(rpc_error(RpcErrorCode::MethodNotFound, "Method not found")),
+        };
+        res.map_err(|e| Error::JsonRpc(jsonrpc_async::Error::Rpc(e)))
+            .and_then(|v| Ok(serde_json::from_value(v)?))
+            .map_err(|e| Error::Call {
+                method: cmd.to_owned(),
+                params: args.to_vec(),
+                source: Box::new(e),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountRPC, BlockchainRPC, TokenRPC};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serves_state() {
+        let node = MockNode::new();
+        assert_eq!(BlockchainRPC::get_block_count(&node).await.unwrap(), 0);
+        node.state().mine(2);
+        assert_eq!(BlockchainRPC::get_block_count(&node).await.unwrap(), 2);
+        let hash = BlockchainRPC::get_block_hash(&node, 2).await.unwrap();
+        assert_eq!(BlockchainRPC::get_best_block_hash(&node).await.unwrap(), hash);
+
+        node.state().add_token("BTC");
+        let token = node.get_token("BTC").await.unwrap();
+        assert_eq!(token.0["1"].symbol, "BTC");
+        assert!(node.get_token("ETH").await.is_err());
+
+        node.state().set_balance("alice", "BTC", 1.0);
+        let payload = serde_json::from_value(json!({"bob": "2@BTC"})).unwrap();
+        let err = node
+            .account_to_account("alice".into(), payload, serde_json::from_value(json!({})).unwrap())
+            .await
+            .unwrap_err();
+        let reject = err.custom_tx_reject().unwrap();
+        assert_eq!(reject.tx_type, "AccountToAccount");
+    }
+
+    #[tokio::test]
+    async fn scripts_and_records_calls() {
+        let node = MockNode::new()
+            .respond("getburninfo", json!(null))
+            .on("getblockcount", |_, _| Err(rpc_error(RpcErrorCode::InWarmup, "Loading")));
+        assert!(node.get_burn_info().await.is_err());
+        node.assert_last_request("getburninfo", json!([]));
+
+        let err = BlockchainRPC::get_block_count(&node).await.unwrap_err();
+        assert!(err.is_transient());
+        let err = node.call::<Value>("nosuchmethod", &[1.into()]).await.unwrap_err();
+        assert_eq!(err.rpc_code(), Some(RpcErrorCode::MethodNotFound));
+        assert_eq!(node.requests().len(), 3);
+        node.clear_requests();
+        assert_eq!(node.last_request(), None);
+    }
+}
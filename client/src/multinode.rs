@@ -0,0 +1,682 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! A client that spreads calls over several nodes.
+//!
+//! [MultiNodeClient] balances read calls over all healthy nodes and fails over
+//! to the next node when one cannot be reached. All other calls, such as ones
+//! that create transactions or use the wallet, are always sent to the primary
+//! node, and are never retried on another node, since the node may have
+//! processed the call before the connection failed. See [is_pinned].
+//!
+//! ```no_run
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use defichain_rpc::multinode::MultiNodeClient;
+//! use defichain_rpc::{Auth, Client, PoolPairRPC, RpcApi};
+//!
+//! let rpc = MultiNodeClient::new(vec![
+//!     Client::new("http://node1:8554", Auth::None).await?,
+//!     Client::new("http://node2:8554", Auth::None).await?,
+//!     Client::new("http://node3:8554", Auth::None).await?,
+//! ]);
+//! for status in rpc.check_consistency().await {
+//!     println!("{:?}", status);
+//! }
+//! let count = rpc.get_block_count().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::bitcoin::BlockHash;
+use crate::client::{Result, RpcApi};
+use crate::error::Error;
+use crate::retry::is_retryable;
+
+/// RPC methods that only read chain state, and whose results don't depend on
+/// the wallet or the local state of the node, so any node in sync can answer
+/// them.
+///
+/// Methods that may filter by the wallet, such as `listaccounts` with
+/// `is_mine_only` or `listaccounthistory "mine"`, are not listed.
+const LOAD_BALANCED_METHODS: &[&str] = &[
+    // Blockchain
+    "getbestblockhash",
+    "getblock",
+    "getblockchaininfo",
+    "getblockcount",
+    "getblockfilter",
+    "getblockhash",
+    "getblockheader",
+    "getblockstats",
+    "getchaintips",
+    "getchaintxstats",
+    "getdifficulty",
+    "getindexinfo",
+    "getmempoolancestors",
+    "getmempooldescendants",
+    "getmempoolentry",
+    "getmempoolinfo",
+    "getmininginfo",
+    "getnetworkhashps",
+    "getrawmempool",
+    "gettxout",
+    "gettxoutproof",
+    "gettxoutsetinfo",
+    "scantxoutset",
+    // Raw transactions and utilities, which don't use the wallet
+    "combinepsbt",
+    "combinerawtransaction",
+    "createpsbt",
+    "createrawtransaction",
+    "decoderawtransaction",
+    "deriveaddresses",
+    "estimatesmartfee",
+    "finalizepsbt",
+    "getdescriptorinfo",
+    "getrawtransaction",
+    "joinpsbts",
+    "signrawtransactionwithkey",
+    "testmempoolaccept",
+    "verifymessage",
+    // Accounts and tokens
+    "decodecustomtx",
+    "getaccount",
+    "getburninfo",
+    "getcustomtx",
+    "getpendingdusdswaps",
+    "getpendingfutureswaps",
+    "gettoken",
+    "listburnhistory",
+    "listcommunitybalances",
+    "listpendingdusdswaps",
+    "listpendingfutureswaps",
+    "listtokens",
+    // Pool pairs
+    "getpoolpair",
+    "listpoolpairs",
+    "testpoolswap",
+    // Loans and vaults
+    "estimatecollateral",
+    "estimateloan",
+    "estimatevault",
+    "getcollateraltoken",
+    "getinterest",
+    "getloaninfo",
+    "getloanscheme",
+    "getloantoken",
+    "getvault",
+    "listauctions",
+    "listcollateraltokens",
+    "listloanschemes",
+    "listloantokens",
+    "listvaults",
+    // Governance and masternodes
+    "getactivemasternodecount",
+    "getanchorteams",
+    "getgov",
+    "getgovproposal",
+    "getmasternodeblocks",
+    "isappliedcustomtx",
+    "listgovproposals",
+    "listgovs",
+    // Oracles
+    "getfixedintervalprice",
+    "getfutureswapblock",
+    "getoracledata",
+    "getprice",
+    "listfixedintervalprices",
+    "listlatestrawprices",
+    "listoracles",
+    "listprices",
+    // ICX orders
+    "icx_getorder",
+    "icx_listhtlcs",
+    "icx_listorders",
+    // SPV anchors and HTLC scripts, which don't use the SPV wallet
+    "spv_decodehtlcscript",
+    "spv_listanchorauths",
+    "spv_listanchorrewardconfirms",
+    "spv_listanchorrewards",
+    "spv_listanchors",
+    "spv_listanchorspending",
+    "spv_listanchorsunrewarded",
+];
+
+/// Whether the call must be sent to the primary node.
+///
+/// Only calls known to be read-only and independent of the wallet are
+/// balanced over the nodes. All other calls, including ones this library
+/// doesn't know, create transactions or depend on the state of the node
+/// they are sent to.
+pub fn is_pinned(method: &str) -> bool {
+    !LOAD_BALANCED_METHODS.contains(&method)
+}
+
+/// Configuration of a [MultiNodeClient].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiNodeConfig {
+    /// The number of consecutive failures after which a node is taken out of
+    /// rotation.
+    pub failure_threshold: u32,
+    /// How long a node stays out of rotation before it is tried again.
+    pub open_duration: Duration,
+    /// How many blocks a node may be behind the best known node before it is
+    /// considered lagging by [MultiNodeClient::check_consistency].
+    pub max_lag: u64,
+}
+
+impl Default for MultiNodeConfig {
+    fn default() -> Self {
+        MultiNodeConfig {
+            failure_threshold: 3,
+            open_duration: Duration::from_secs(30),
+            max_lag: 2,
+        }
+    }
+}
+
+/// The state of a node as last determined by [MultiNodeClient::check_consistency].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NodeSync {
+    /// The node was not checked yet.
+    Unknown,
+    /// The node is on the best known tip, or close enough to it.
+    InSync,
+    /// The node is more than [MultiNodeConfig::max_lag] blocks behind.
+    Lagging {
+        /// The number of blocks the node is behind.
+        behind: u64,
+    },
+    /// The tip of the node is not on the chain of the best known tip.
+    Forked,
+    /// The node could not be queried.
+    Unreachable,
+}
+
+/// The status of a single node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeStatus {
+    /// The index of the node, in the order the nodes were given.
+    pub index: usize,
+    /// The block count of the node, if it could be queried.
+    pub block_count: Option<u64>,
+    /// The best block hash of the node, if it could be queried.
+    pub best_block_hash: Option<BlockHash>,
+    /// How the node compares to the other nodes.
+    pub sync: NodeSync,
+    /// Whether the circuit breaker of the node is open.
+    pub circuit_open: bool,
+}
+
+#[derive(Debug)]
+struct Health {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    sync: NodeSync,
+}
+
+struct Node<C> {
+    client: C,
+    health: Mutex<Health>,
+}
+
+/// An [RpcApi] implementation that spreads calls over several nodes.
+pub struct MultiNodeClient<C> {
+    nodes: Vec<Node<C>>,
+    config: MultiNodeConfig,
+    primary: AtomicUsize,
+    next: AtomicUsize,
+}
+
+impl<C: fmt::Debug> fmt::Debug for MultiNodeClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let clients: Vec<&C> = self.nodes.iter().map(|n| &n.client).collect();
+        write!(f, "defichain_rpc::MultiNodeClient({:?})", clients)
+    }
+}
+
+impl<C: RpcApi + Sync + Send> MultiNodeClient<C> {
+    /// Create a client for the given nodes, using the first one as primary.
+    ///
+    /// Panics if `nodes` is empty.
+    pub fn new(nodes: Vec<C>) -> Self {
+        MultiNodeClient::with_config(nodes, MultiNodeConfig::default())
+    }
+
+    /// Create a client for the given nodes with the given configuration.
+    ///
+    /// Panics if `nodes` is empty.
+    pub fn with_config(nodes: Vec<C>, config: MultiNodeConfig) -> Self {
+        assert!(!nodes.is_empty(), "MultiNodeClient needs at least one node");
+        MultiNodeClient {
+            nodes: nodes
+                .into_iter()
+                .map(|client| Node {
+                    client,
+                    health: Mutex::new(Health {
+                        consecutive_failures: 0,
+                        open_until: None,
+                        sync: NodeSync::Unknown,
+                    }),
+                })
+                .collect(),
+            config,
+            primary: AtomicUsize::new(0),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Always false, a [MultiNodeClient] has at least one node.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Get the client of the node at `index`.
+    pub fn node(&self, index: usize) -> &C {
+        &self.nodes[index].client
+    }
+
+    /// The index of the node calls that create transactions are sent to.
+    pub fn primary(&self) -> usize {
+        self.primary.load(Ordering::Relaxed)
+    }
+
+    /// Choose the node calls that create transactions are sent to.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_primary(&self, index: usize) {
+        assert!(index < self.nodes.len(), "no node with index {}", index);
+        self.primary.store(index, Ordering::Relaxed);
+    }
+
+    /// Whether the node at `index` should currently receive read calls.
+    fn is_available(&self, index: usize, now: Instant) -> bool {
+        let health = self.nodes[index].health.lock().unwrap();
+        let closed = !matches!(health.open_until, Some(until) if until > now);
+        let synced = match health.sync {
+            NodeSync::Unknown | NodeSync::InSync => true,
+            NodeSync::Lagging {
+                ..
+            }
+            | NodeSync::Forked
+            | NodeSync::Unreachable => false,
+        };
+        closed && synced
+    }
+
+    fn record_success(&self, index: usize) {
+        let mut health = self.nodes[index].health.lock().unwrap();
+        health.consecutive_failures = 0;
+        health.open_until = None;
+    }
+
+    fn record_failure(&self, index: usize) {
+        let mut health = self.nodes[index].health.lock().unwrap();
+        health.consecutive_failures += 1;
+        if health.consecutive_failures >= self.config.failure_threshold {
+            warn!(target: "defichain_rpc", "taking node {} out of rotation after {} failures", index, health.consecutive_failures);
+            health.open_until = Some(Instant::now() + self.config.open_duration);
+        }
+    }
+
+    /// The order in which the nodes are tried for a read call: the available
+    /// nodes in round-robin order, then the others as a last resort.
+    fn read_order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let (mut order, unavailable): (Vec<usize>, Vec<usize>) =
+            (0..self.nodes.len()).partition(|&i| self.is_available(i, now));
+        if !order.is_empty() {
+            let start = self.next.fetch_add(1, Ordering::Relaxed) % order.len();
+            order.rotate_left(start);
+        }
+        order.extend(unavailable);
+        order
+    }
+
+    async fn call_node<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        index: usize,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        let res = self.nodes[index].client.call(cmd, args).await;
+        match res {
+            Err(ref e) if is_retryable(e) => self.record_failure(index),
+            _ => self.record_success(index),
+        }
+        res
+    }
+
+    /// Query all nodes for their tip and take nodes that are lagging or on a
+    /// different chain out of rotation for read calls.
+    ///
+    /// Nodes are compared to the node with the highest block count. The tip of
+    /// a node that is behind is looked up on that node's chain.
+    pub async fn check_consistency(&self) -> Vec<NodeStatus> {
+        let mut tips = Vec::with_capacity(self.nodes.len());
+        for index in 0..self.nodes.len() {
+            let count = self.call_node::<u64>(index, "getblockcount", &[]).await;
+            let hash = self.call_node::<BlockHash>(index, "getbestblockhash", &[]).await;
+            tips.push(match (count, hash) {
+                (Ok(count), Ok(hash)) => Some((count, hash)),
+                _ => None,
+            });
+        }
+
+        // The best tip is the highest one, preferring the primary on ties.
+        let primary = self.primary();
+        let best = tips
+            .iter()
+            .enumerate()
+            .filter_map(|(i, tip)| tip.map(|t| (i, t)))
+            .max_by_key(|&(i, (count, _))| (count, i == primary));
+
+        let mut statuses = Vec::with_capacity(tips.len());
+        for (index, tip) in tips.into_iter().enumerate() {
+            let sync = match (tip, best) {
+                (None, _) | (_, None) => NodeSync::Unreachable,
+                (Some((count, hash)), Some((best_index, (best_count, best_hash)))) => {
+                    let behind = best_count - count;
+                    if behind > self.config.max_lag {
+                        NodeSync::Lagging {
+                            behind,
+                        }
+                    } else if behind == 0 {
+                        if hash == best_hash {
+                            NodeSync::InSync
+                        } else {
+                            NodeSync::Forked
+                        }
+                    } else {
+                        // A node that is a few blocks behind must have its tip
+                        // on the chain of the best node.
+                        let args = [count.into()];
+                        match self.call_node::<BlockHash>(best_index, "getblockhash", &args).await {
+                            Ok(ancestor) if ancestor != hash => NodeSync::Forked,
+                            _ => NodeSync::InSync,
+                        }
+                    }
+                }
+            };
+            if sync != NodeSync::InSync {
+                warn!(target: "defichain_rpc", "node {} is out of sync: {:?}", index, sync);
+            }
+            let mut health = self.nodes[index].health.lock().unwrap();
+            health.sync = sync.clone();
+            statuses.push(NodeStatus {
+                index,
+                block_count: tip.map(|(count, _)| count),
+                best_block_hash: tip.map(|(_, hash)| hash),
+                sync,
+                circuit_open: matches!(health.open_until, Some(until) if until > Instant::now()),
+            });
+        }
+        statuses
+    }
+}
+
+#[async_trait]
+impl<C: RpcApi + Sync + Send> RpcApi for MultiNodeClient<C> {
+    async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        if is_pinned(cmd) {
+            return self.call_node(self.primary(), cmd, args).await;
+        }
+
+        let mut last_err = None;
+        for index in self.read_order() {
+            match self.call_node(index, cmd, args).await {
+                Err(e) if is_retryable(&e) => {
+                    debug!(target: "defichain_rpc", "node {} failed for {}, failing over: {}", index, cmd, e);
+                    last_err = Some(e);
+                }
+                res => return res,
+            }
+        }
+        Err(last_err.unwrap_or(Error::UnexpectedStructure))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::hashes::Hash;
+    use std::sync::atomic::AtomicU64;
+
+    /// A node at a given height of a given chain that can be switched off.
+    struct FakeNode {
+        height: AtomicU64,
+        chain: u8,
+        down: std::sync::atomic::AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    impl FakeNode {
+        fn new(height: u64, chain: u8) -> FakeNode {
+            FakeNode {
+                height: AtomicU64::new(height),
+                chain,
+                down: Default::default(),
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        fn block_hash(&self, height: u64) -> BlockHash {
+            let mut hash = [self.chain; 32];
+            hash[..8].copy_from_slice(&height.to_le_bytes());
+            BlockHash::from_byte_array(hash)
+        }
+    }
+
+    #[async_trait]
+    impl RpcApi for FakeNode {
+        async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+            &self,
+            cmd: &str,
+            args: &[serde_json::Value],
+        ) -> Result<T> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.down.load(Ordering::SeqCst) {
+                let err = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "down");
+                return Err(Error::JsonRpc(jsonrpc_async::Error::Transport(Box::new(err))));
+            }
+            let value = match cmd {
+                "getbestblockhash" => {
+                    serde_json::to_value(self.block_hash(self.height.load(Ordering::SeqCst)))?
+                }
+                "getblockhash" => serde_json::to_value(self.block_hash(args[0].as_u64().unwrap()))?,
+                _ => self.height.load(Ordering::SeqCst).into(),
+            };
+            Ok(serde_json::from_value(value)?)
+        }
+    }
+
+    fn calls(rpc: &MultiNodeClient<FakeNode>) -> Vec<usize> {
+        (0..rpc.len()).map(|i| rpc.node(i).calls.swap(0, Ordering::SeqCst)).collect()
+    }
+
+    #[tokio::test]
+    async fn balances_reads_and_pins_writes() {
+        let rpc = MultiNodeClient::new(vec![FakeNode::new(10, 1), FakeNode::new(10, 1)]);
+        for _ in 0..4 {
+            rpc.get_block_count().await.unwrap();
+        }
+        assert_eq!(calls(&rpc), vec![2, 2]);
+
+        rpc.set_primary(1);
+        for _ in 0..3 {
+            rpc.call::<u64>("sendtoaddress", &[]).await.unwrap();
+        }
+        assert_eq!(calls(&rpc), vec![0, 3]);
+    }
+
+    #[test]
+    fn pins_writes_and_wallet_calls() {
+        for method in [
+            "sendtoaddress",
+            "walletpassphrase",
+            "walletpassphrasechange",
+            "getaddressinfo",
+            "listsinceblock",
+            "listreceivedbyaddress",
+            "getreceivedbyaddress",
+            "rescanblockchain",
+            "listwallets",
+            "gettokenbalances",
+            "listaccounthistory",
+            "listaccounts",
+            "nosuchmethod",
+        ]
+        .iter()
+        {
+            assert!(is_pinned(method), "{} is not pinned", method);
+        }
+        assert!(!is_pinned("getblockcount"));
+        assert!(!is_pinned("listpoolpairs"));
+    }
+
+    #[test]
+    fn pins_icx_writes() {
+        for method in [
+            "icx_createorder",
+            "icx_makeoffer",
+            "icx_submitdfchtlc",
+            "icx_submitexthtlc",
+            "icx_claimdfchtlc",
+            "icx_closeorder",
+            "icx_closeoffer",
+        ]
+        .iter()
+        {
+            assert!(is_pinned(method), "{} is not pinned", method);
+        }
+        assert!(!is_pinned("icx_listorders"));
+    }
+
+    #[test]
+    fn pins_spv_wallet_calls() {
+        for method in [
+            "spv_createhtlc",
+            "spv_claimhtlc",
+            "spv_refundhtlc",
+            "spv_refundhtlcall",
+            "spv_sendtoaddress",
+            "spv_createanchor",
+            "spv_getnewaddress",
+            "spv_gethtlcseed",
+            "spv_setlastheight",
+            "spv_listhtlcoutputs",
+            "spv_listreceivedbyaddress",
+        ]
+        .iter()
+        {
+            assert!(is_pinned(method), "{} is not pinned", method);
+        }
+        assert!(!is_pinned("spv_listanchors"));
+    }
+
+    #[test]
+    fn pins_masternode_writes() {
+        for method in [
+            "clearmempool",
+            "createmasternode",
+            "resignmasternode",
+            "updatemasternode",
+            "setgov",
+            "setgovheight",
+            "unsetgov",
+            // These report whether the wallet owns the masternodes.
+            "getmasternode",
+            "listmasternodes",
+        ]
+        .iter()
+        {
+            assert!(is_pinned(method), "{} is not pinned", method);
+        }
+        assert!(!is_pinned("getactivemasternodecount"));
+    }
+
+    #[test]
+    fn balances_only_wrapped_rpcs() {
+        for method in LOAD_BALANCED_METHODS {
+            assert!(
+                crate::coverage::wrapped_rpcs().contains_key(method),
+                "{} is not a wrapped RPC",
+                method
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_over_and_opens_circuit() {
+        let config = MultiNodeConfig {
+            failure_threshold: 2,
+            ..Default::default()
+        };
+        let rpc =
+            MultiNodeClient::with_config(vec![FakeNode::new(10, 1), FakeNode::new(10, 1)], config);
+        rpc.node(0).down.store(true, Ordering::SeqCst);
+        for _ in 0..6 {
+            assert_eq!(rpc.get_block_count().await.unwrap(), 10);
+        }
+        // Node 0 is skipped once it failed twice.
+        assert_eq!(calls(&rpc), vec![2, 6]);
+
+        // Writes are not failed over.
+        assert!(rpc.call::<u64>("sendtoaddress", &[]).await.is_err());
+        assert_eq!(calls(&rpc), vec![1, 0]);
+    }
+
+    #[tokio::test]
+    async fn detects_lagging_and_forked_nodes() {
+        let rpc = MultiNodeClient::new(vec![
+            FakeNode::new(10, 1),
+            FakeNode::new(10, 2),
+            FakeNode::new(5, 1),
+            FakeNode::new(9, 3),
+            FakeNode::new(9, 1),
+        ]);
+        let status = rpc.check_consistency().await;
+        let sync: Vec<NodeSync> = status.into_iter().map(|s| s.sync).collect();
+        assert_eq!(
+            sync,
+            vec![
+                NodeSync::InSync,
+                NodeSync::Forked,
+                NodeSync::Lagging {
+                    behind: 5
+                },
+                NodeSync::Forked,
+                NodeSync::InSync,
+            ]
+        );
+        calls(&rpc);
+
+        for _ in 0..4 {
+            rpc.get_block_count().await.unwrap();
+        }
+        assert_eq!(calls(&rpc), vec![2, 0, 0, 0, 2]);
+    }
+}
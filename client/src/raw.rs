@@ -0,0 +1,159 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Raw JSON results.
+//!
+//! The types of `defichain-rpc-json` can lag behind the node, so a result the
+//! node returns may not deserialize into them, or may have fields they drop.
+//! [RpcApi::call_raw](crate::RpcApi::call_raw) returns the result as raw
+//! JSON, and [Raw] keeps the raw JSON of a result and only deserializes it on
+//! demand, so callers can fall back to the JSON:
+//!
+//! ```no_run
+//! # async fn example(client: &defichain_rpc::Client) -> defichain_rpc::Result<()> {
+//! use defichain_rpc::json::poolpair::PoolPairsResult;
+//! use defichain_rpc::json::vault::VaultResult;
+//! use defichain_rpc::{Raw, RpcApi};
+//!
+//! let vault: Raw<VaultResult> = client.call("getvault", &["0123...".into()]).await?;
+//! match vault.parse() {
+//!     Ok(vault) => println!("{:?}", vault),
+//!     Err(e) => println!("{}, the node returned {}", e, vault.get()),
+//! }
+//!
+//! // Archive the pool pairs exactly as the node returned them.
+//! let pairs: Raw<PoolPairsResult> = client.call("listpoolpairs", &[]).await?;
+//! std::fs::write("poolpairs.json", pairs.get())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_json::value::RawValue;
+
+use crate::client::{parse_raw, Result};
+
+/// A result that keeps its raw JSON, and is deserialized into `T` on demand.
+///
+/// It deserializes from any JSON value, so it can be used as the result type
+/// of [RpcApi::call](crate::RpcApi::call) and in [Batch](crate::Batch)
+/// queues, and serializes to the raw JSON unchanged.
+pub struct Raw<T> {
+    raw: Box<RawValue>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Raw<T> {
+    /// Wrap the raw JSON of a result.
+    pub fn new(raw: Box<RawValue>) -> Self {
+        Raw {
+            raw,
+            marker: PhantomData,
+        }
+    }
+
+    /// The raw JSON.
+    pub fn get(&self) -> &str {
+        self.raw.get()
+    }
+
+    /// The raw JSON as a [RawValue].
+    pub fn raw(&self) -> &RawValue {
+        &self.raw
+    }
+
+    /// Unwrap the raw JSON.
+    pub fn into_raw(self) -> Box<RawValue> {
+        self.raw
+    }
+
+    /// The result as an untyped JSON value, which is always available.
+    pub fn to_value(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::from_str(self.raw.get())?)
+    }
+}
+
+impl<T: for<'a> Deserialize<'a>> Raw<T> {
+    /// Deserialize the result into `T`.
+    ///
+    /// Fails with [Error::Deserialize](crate::Error::Deserialize), giving the
+    /// location of the offending value, if the JSON doesn't match `T`.
+    pub fn parse(&self) -> Result<T> {
+        parse_raw(self.raw.get())
+    }
+}
+
+impl<T> Clone for Raw<T> {
+    fn clone(&self) -> Self {
+        Raw::new(self.raw.clone())
+    }
+}
+
+impl<T> fmt::Debug for Raw<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::Raw({})", self.raw.get())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Raw<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(Raw::new(Box::<RawValue>::deserialize(deserializer)?))
+    }
+}
+
+impl<T> Serialize for Raw<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.raw.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockNode;
+    use crate::{Error, RpcApi};
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Pair {
+        symbol: String,
+        reserve: f64,
+    }
+
+    #[tokio::test]
+    async fn keeps_raw_json_of_results() {
+        let node = MockNode::new()
+            .respond("getpair", json!({"symbol": "BTC-DFI", "reserve": 1.5}))
+            .respond("getnewpair", json!({"symbol": "ETH-DFI", "reserve": "1.5"}));
+
+        let raw = node.call_raw("getpair", &[]).await.unwrap();
+        assert_eq!(raw.get(), r#"{"reserve":1.5,"symbol":"BTC-DFI"}"#);
+
+        let pair: Raw<Pair> = node.call("getpair", &[]).await.unwrap();
+        assert_eq!(
+            pair.parse().unwrap(),
+            Pair {
+                symbol: "BTC-DFI".into(),
+                reserve: 1.5,
+            }
+        );
+
+        // A newer node changed the type of a field.
+        let pair: Raw<Pair> = node.call("getnewpair", &[]).await.unwrap();
+        let err = pair.parse().unwrap_err();
+        assert!(matches!(err, Error::Deserialize { ref path, .. } if path == "reserve"));
+        assert_eq!(pair.to_value().unwrap()["reserve"], "1.5");
+        assert_eq!(serde_json::to_string(&pair).unwrap(), pair.get());
+    }
+}
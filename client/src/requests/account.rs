@@ -0,0 +1,143 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [AccountRPC](crate::AccountRPC).
+
+use defichain_rpc_json::account as json;
+use defichain_rpc_json::common::UTXO;
+
+use crate::{into_json, obj_into_json};
+
+requests! {
+    AccountToAccount("accounttoaccount") -> String {
+        from: String,
+        payload: json::BalanceTransferPayload,
+        options: json::BalanceTransferAccountOptions,
+    }
+    |self| [into_json(&self.from)?, into_json(&self.payload)?, into_json(&self.options)?]
+
+    AccountToUtxos("accounttoutxos") -> String {
+        from: String,
+        payload: json::BalanceTransferPayload,
+        options: json::BalanceTransferAccountOptions,
+    }
+    |self| [into_json(&self.from)?, into_json(&self.payload)?, into_json(&self.options)?]
+
+    FutureSwap("futureswap") -> String {
+        future: json::FutureSwap,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.future)?, into_json(&self.utxos)?]
+
+    GetAccount("getaccount") -> json::AccountAmount {
+        owner: String,
+        pagination: Option<json::GetAccountPagination>,
+        indexed_amounts: Option<bool>,
+    }
+    |self| [
+        into_json(&self.owner)?,
+        obj_into_json(self.pagination.as_ref())?,
+        into_json(self.indexed_amounts)?,
+    ]
+
+    GetAccountHistory("getaccounthistory") -> json::AccountHistory {
+        owner: String,
+        block_height: u32,
+        txn: u32,
+    }
+    |self| [into_json(&self.owner)?, into_json(self.block_height)?, into_json(self.txn)?]
+
+    GetBurnInfo("getburninfo") -> json::BurnInfo {}
+    |self| []
+
+    GetPendingDusdSwaps("getpendingdusdswaps") -> json::DusdSwapsInfo {
+        address: String,
+    }
+    |self| [into_json(&self.address)?]
+
+    GetPendingFutureSwaps("getpendingfutureswaps") -> json::GetFutureInfo {
+        address: String,
+    }
+    |self| [into_json(&self.address)?]
+
+    GetTokenBalances("gettokenbalances") -> () {
+        pagination: Option<json::GetAccountPagination>,
+        indexed_amounts: Option<bool>,
+        options: Option<json::GetTokenBalancesOptions>,
+    }
+    |self| [
+        into_json(&self.pagination)?,
+        into_json(self.indexed_amounts)?,
+        into_json(&self.options)?,
+    ]
+
+    HistoryCount("historycount") -> u64 {
+        owner: Option<String>,
+        options: json::AccountHistoryCountOptions,
+    }
+    |self| [into_json(&self.owner)?, into_json(&self.options)?]
+
+    ListAccountHistory("listaccounthistory") -> Vec<json::AccountHistory> {
+        owner: Option<String>,
+        options: json::AccountHistoryOptions,
+    }
+    |self| [into_json(&self.owner)?, into_json(&self.options)?]
+
+    ListAccounts("listaccounts") -> Vec<json::AccountsResult> {
+        pagination: Option<json::ListAccountsPagination>,
+        verbose: Option<bool>,
+        indexed_amounts: Option<bool>,
+        is_mine_only: Option<bool>,
+    }
+    |self| [
+        into_json(&self.pagination)?,
+        into_json(self.verbose)?,
+        into_json(self.indexed_amounts)?,
+        into_json(self.is_mine_only)?,
+    ]
+
+    ListBurnHistory("listburnhistory") -> Vec<json::BurnHistory> {
+        options: json::BurnHistoryOptions,
+    }
+    |self| [into_json(&self.options)?]
+
+    ListCommunityBalances("listcommunitybalances") -> json::CommunityBalanceData {}
+    |self| []
+
+    ListPendingDusdSwaps("listpendingdusdswaps") -> Vec<json::DusdSwapsInfo> {}
+    |self| []
+
+    ListPendingFutureSwaps("listpendingfutureswaps") -> Vec<json::ListFutureInfo> {}
+    |self| []
+
+    SendTokensToAddress("sendtokenstoaddress") -> String {
+        from: json::AddressBalances,
+        to: json::AddressBalances,
+        options: json::SendTokensOptions,
+    }
+    |self| [into_json(&self.from)?, into_json(&self.to)?, into_json(&self.options)?]
+
+    TransferDomain("transferdomain") -> String {
+        payload: Vec<json::TransferDomain>,
+    }
+    |self| [into_json(&self.payload)?]
+
+    UtxosToAccount("utxostoaccount") -> String {
+        payload: json::BalanceTransferPayload,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.payload)?, into_json(&self.utxos)?]
+
+    WithdrawFutureSwap("withdrawfutureswap") -> String {
+        future: json::FutureSwap,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.future)?, into_json(&self.utxos)?]
+}
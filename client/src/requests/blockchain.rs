@@ -0,0 +1,114 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [BlockchainRPC](crate::BlockchainRPC).
+
+use defichain_rpc_json::blockchain as json;
+
+use crate::bitcoin::{BlockHash, Txid};
+use crate::{into_json, Error};
+
+requests! {
+    GetBestBlockHash("getbestblockhash") -> BlockHash {}
+    |self| []
+
+    GetBlock("getblock") -> json::GetBlockResult {
+        hash: BlockHash,
+        verbosity: u8,
+    }
+    |self| {
+        if self.verbosity > 2 {
+            return Err(Error::Custom("Only verbosity up to 2 is supported".into()));
+        }
+    }
+    [into_json(self.hash)?, into_json(self.verbosity)?]
+
+    GetBlockCount("getblockcount") -> u64 {}
+    |self| []
+
+    GetBlockHash("getblockhash") -> BlockHash {
+        height: u32,
+    }
+    |self| [into_json(self.height)?]
+
+    GetBlockHeader("getblockheader") -> json::BlockHeader {
+        hash: BlockHash,
+    }
+    |self| [into_json(self.hash)?]
+
+    GetBlockStats("getblockstats") -> json::BlockStats {
+        height: u32,
+        stats: Option<Vec<String>>,
+    }
+    |self| [into_json(self.height)?, into_json(&self.stats)?]
+
+    GetBlockchainInfo("getblockchaininfo") -> json::BlockchainInfo {}
+    |self| []
+
+    GetChainTips("getchaintips") -> Vec<json::ChainTip> {}
+    |self| []
+
+    GetChainTxStats("getchaintxstats") -> json::ChainTxStats {
+        n_blocks: Option<u64>,
+        block_hash: Option<BlockHash>,
+    }
+    |self| [into_json(self.n_blocks)?, into_json(self.block_hash)?]
+
+    GetDifficulty("getdifficulty") -> u64 {}
+    |self| []
+
+    GetMempoolAncestors("getmempoolancestors") -> () {
+        tx_id: Txid,
+    }
+    |self| [into_json(self.tx_id)?]
+
+    GetMempoolDescendants("getmempooldescendants") -> () {
+        tx_id: Txid,
+    }
+    |self| [into_json(self.tx_id)?]
+
+    GetMempoolEntry("getmempoolentry") -> json::MempoolTx {
+        tx_id: Txid,
+    }
+    |self| [into_json(self.tx_id)?]
+
+    GetMempoolInfo("getmempoolinfo") -> json::MempoolInfo {}
+    |self| []
+
+    GetRawMempool("getrawmempool") -> () {}
+    |self| []
+
+    GetTxOut("gettxout") -> json::UTXODetails {
+        tx_id: Txid,
+        index: u64,
+        include_mempool: Option<bool>,
+    }
+    |self| [
+        into_json(self.tx_id)?,
+        into_json(self.index)?,
+        into_json(self.include_mempool.unwrap_or(true))?,
+    ]
+
+    GetTxOutSetInfo("gettxoutsetinfo") -> json::TxOutSetInfo {}
+    |self| []
+
+    WaitForBlock("waitforblock") -> json::WaitBlockResult {
+        blockhash: BlockHash,
+    }
+    |self| [into_json(self.blockhash)?]
+
+    WaitForBlockHeight("waitforblockheight") -> json::WaitBlockResult {
+        height: u64,
+    }
+    |self| [into_json(self.height)?]
+
+    WaitForNewBlock("waitfornewblock") -> json::WaitBlockResult {}
+    |self| []
+}
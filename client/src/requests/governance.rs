@@ -0,0 +1,124 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [GovernanceRPC](crate::GovernanceRPC).
+
+use crate::bitcoin::{base58, bech32, Txid};
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::governance as json;
+
+use crate::{into_json, Error, Result};
+
+/// The maximum length of a proposal's title in bytes.
+const MAX_TITLE_LENGTH: usize = 128;
+/// The maximum length of a proposal's context and context hash in bytes.
+const MAX_CONTEXT_LENGTH: usize = 512;
+/// The node only accepts community fund proposals of 1 to 100 cycles.
+const MAX_CYCLES: u64 = 100;
+
+/// Check the lengths of a proposal's title, context and context hash.
+fn check_proposal(title: &str, context: &str, context_hash: Option<&str>) -> Result<()> {
+    let check_length = |name: &str, value: &str, max: usize| match value.len() {
+        0 => Err(Error::Custom(format!("proposal {} cannot be empty", name))),
+        len if len > max => Err(Error::Custom(format!(
+            "proposal {} cannot be longer than {} bytes, got {}",
+            name, max, len
+        ))),
+        _ => Ok(()),
+    };
+    check_length("title", title, MAX_TITLE_LENGTH)?;
+    check_length("context", context, MAX_CONTEXT_LENGTH)?;
+    if let Some(hash) = context_hash {
+        check_length("context hash", hash, MAX_CONTEXT_LENGTH)?;
+    }
+    Ok(())
+}
+
+/// Check that `address` looks like a DeFiChain address, a base58 one with a
+/// version byte or a bech32 one. The node checks the network.
+fn check_address(address: &str) -> Result<()> {
+    let base58 = matches!(base58::decode_check(address), Ok(data) if data.len() == 21);
+    if base58 || bech32::decode(address).is_ok() {
+        Ok(())
+    } else {
+        Err(Error::Custom(format!("invalid payout address {}", address)))
+    }
+}
+
+requests! {
+    CreateGovCfp("creategovcfp") -> String {
+        data: json::CFPData,
+        utxos: Option<UTXO>,
+    }
+    |self| {
+        let data = &self.data;
+        check_proposal(&data.title, &data.context, data.context_hash.as_deref())?;
+        if let Some(cycles) = data.cycles {
+            if !(1..=MAX_CYCLES).contains(&cycles) {
+                return Err(Error::Custom(format!(
+                    "cycles must be between 1 and {}, got {}",
+                    MAX_CYCLES, cycles
+                )));
+            }
+        }
+        if !data.amount.is_finite() || data.amount <= 0.0 {
+            return Err(Error::Custom(format!("amount must be positive, got {}", data.amount)));
+        }
+        check_address(&data.payout_address)?;
+    }
+    [into_json(&self.data)?, into_json(&self.utxos)?]
+
+    CreateGovVoc("creategovvoc") -> String {
+        data: json::VOCData,
+        utxos: Option<UTXO>,
+    }
+    |self| {
+        let data = &self.data;
+        check_proposal(&data.title, &data.context, data.context_hash.as_deref())?;
+    }
+    [into_json(&self.data)?, into_json(&self.utxos)?]
+
+    GetGovProposal("getgovproposal") -> json::ProposalInfo {
+        proposal_id: Txid,
+    }
+    |self| [into_json(self.proposal_id)?]
+
+    ListGovProposals("listgovproposals") -> Vec<json::ProposalInfo> {
+        opts: Option<json::ListProposalsOptions>,
+    }
+    |self| [into_json(&self.opts)?]
+
+    ListGovProposalVotes("listgovproposalvotes") -> Vec<json::ListVotesResult> {
+        opts: Option<json::ListGovProposalVotesOptions>,
+    }
+    |self| [into_json(&self.opts)?]
+
+    VoteGov("votegov") -> String {
+        data: json::VoteData,
+        utxos: Option<UTXO>,
+    }
+    |self| [
+        into_json(self.data.proposal_id)?,
+        into_json(&self.data.masternode_id)?,
+        into_json(self.data.decision)?,
+        into_json(&self.utxos)?,
+    ]
+
+    VoteGovBatch("votegovbatch") -> Vec<String> {
+        votes: Vec<json::VoteData>,
+        sleep_time: Option<u64>,
+    }
+    |self| {
+        // The node takes each vote as [proposalId, masternodeId, decision].
+        let votes: Vec<_> =
+            self.votes.iter().map(|v| (v.proposal_id, &v.masternode_id, v.decision)).collect();
+    }
+    [into_json(votes)?, into_json(self.sleep_time)?]
+}
@@ -0,0 +1,79 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [ICXOrderBookRPC](crate::ICXOrderBookRPC).
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::icxorderbook as json;
+
+use crate::{into_json, obj_into_json};
+
+requests! {
+    ClaimDfcHtlc("icx_claimdfchtlc") -> json::ICXGenericResult {
+        dfchtlc_tx: String,
+        seed: String,
+        utxos: Option<UTXO>,
+    }
+    |self| {
+        let claim = serde_json::json!({"dfchtlcTx": self.dfchtlc_tx, "seed": self.seed});
+    }
+    [claim, into_json(&self.utxos)?]
+
+    CloseOffer("icx_closeoffer") -> json::ICXGenericResult {
+        offer_tx: String,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.offer_tx)?, into_json(&self.utxos)?]
+
+    CloseOrder("icx_closeorder") -> json::ICXGenericResult {
+        order_tx: String,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.order_tx)?, into_json(&self.utxos)?]
+
+    CreateOrder("icx_createorder") -> json::ICXGenericResult {
+        order: json::ICXOrder,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.order)?, into_json(&self.utxos)?]
+
+    GetOrder("icx_getorder") -> json::ICXOrders {
+        order_tx: String,
+    }
+    |self| [into_json(&self.order_tx)?]
+
+    ListHtlcs("icx_listhtlcs") -> json::ICXHTLCs {
+        options: json::ICXListHTLCOptions,
+    }
+    |self| [into_json(&self.options)?]
+
+    ListOrders("icx_listorders") -> json::ICXOrders {
+        options: Option<json::ICXListOrderOptions>,
+    }
+    |self| [obj_into_json(self.options.as_ref())?]
+
+    MakeOffer("icx_makeoffer") -> json::ICXGenericResult {
+        offer: json::ICXOffer,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.offer)?, into_json(&self.utxos)?]
+
+    SubmitDfcHtlc("icx_submitdfchtlc") -> json::ICXGenericResult {
+        htlc: json::HTLC,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.htlc)?, into_json(&self.utxos)?]
+
+    SubmitExtHtlc("icx_submitexthtlc") -> json::ICXGenericResult {
+        htlc: json::ExtHTLC,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.htlc)?, into_json(&self.utxos)?]
+}
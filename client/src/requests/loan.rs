@@ -0,0 +1,114 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [LoanRPC](crate::LoanRPC).
+
+use defichain_rpc_json::common::{Interest, UTXO};
+use defichain_rpc_json::loan as json;
+
+use crate::into_json;
+
+requests! {
+    CreateLoanScheme("createloanscheme") -> String {
+        scheme: json::CreateLoanScheme,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.scheme)?, into_json(&self.utxos)?]
+
+    DestroyLoanScheme("destroyloanscheme") -> String {
+        scheme: json::DestroyLoanScheme,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.scheme)?, into_json(&self.utxos)?]
+
+    GetCollateralToken("getcollateraltoken") -> json::CollateralTokenDetail {
+        token: String,
+    }
+    |self| [into_json(&self.token)?]
+
+    GetInterest("getinterest") -> Vec<Interest> {
+        id: String,
+        token: Option<String>,
+    }
+    |self| [into_json(&self.id)?, into_json(&self.token)?]
+
+    GetLoanInfo("getloaninfo") -> json::GetLoanInfoResult {}
+    |self| []
+
+    GetLoanScheme("getloanscheme") -> json::LoanSchemeResult {
+        id: String,
+    }
+    |self| [into_json(&self.id)?]
+
+    GetLoanToken("getloantoken") -> json::LoanTokenResult {
+        token: String,
+    }
+    |self| [into_json(&self.token)?]
+
+    ListCollateralTokens("listcollateraltokens") -> Vec<json::CollateralTokenDetail> {}
+    |self| []
+
+    ListLoanSchemes("listloanschemes") -> Vec<json::LoanSchemeResult> {}
+    |self| []
+
+    ListLoanTokens("listloantokens") -> Vec<json::LoanTokenResult> {}
+    |self| []
+
+    PaybackLoan("paybackloan") -> String {
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.utxos)?]
+
+    PaybackWithCollateral("paybackwithcollateral") -> String {
+        vault_id: String,
+    }
+    |self| [into_json(&self.vault_id)?]
+
+    SetCollateralToken("setcollateraltoken") -> String {
+        collateral_token: json::SetCollateralToken,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.collateral_token)?, into_json(&self.utxos)?]
+
+    SetDefaultLoanScheme("setdefaultloanscheme") -> String {
+        id: String,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.id)?, into_json(&self.utxos)?]
+
+    SetLoanToken("setloantoken") -> String {
+        loan_token: json::SetLoanToken,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.loan_token)?, into_json(&self.utxos)?]
+
+    TakeLoan("takeloan") -> String {
+        metadata: json::TakeLoanMetadata,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.metadata)?, into_json(&self.utxos)?]
+
+    UpdateLoanScheme("updateloanscheme") -> String {
+        scheme: json::UpdateLoanScheme,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.scheme)?, into_json(&self.utxos)?]
+
+    UpdateLoanToken("updateloantoken") -> String {
+        old_token: String,
+        new_token_details: json::UpdateLoanToken,
+        utxos: Option<UTXO>,
+    }
+    |self| [
+        into_json(&self.old_token)?,
+        into_json(&self.new_token_details)?,
+        into_json(&self.utxos)?,
+    ]
+}
@@ -0,0 +1,136 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [MasternodeRPC](crate::MasternodeRPC).
+
+use std::collections::HashMap;
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::masternode as json;
+
+use crate::{into_json, obj_into_json, Error};
+
+requests! {
+    ClearMempool("clearmempool") -> Vec<String> {}
+    |self| []
+
+    CreateMasternode("createmasternode") -> String {
+        owner_address: String,
+        operator_address: Option<String>,
+        utxos: Option<UTXO>,
+        timelock: Option<json::MasternodeTimeLock>,
+    }
+    |self| [
+        into_json(&self.owner_address)?,
+        into_json(&self.operator_address)?,
+        into_json(&self.utxos)?,
+        into_json(self.timelock)?,
+    ]
+
+    GetActiveMasternodeCount("getactivemasternodecount") -> u64 {
+        block_count: Option<u64>,
+    }
+    |self| [into_json(self.block_count)?]
+
+    GetAnchorTeams("getanchorteams") -> json::AnchorTeamResult {
+        block_height: Option<u64>,
+    }
+    |self| [into_json(self.block_height)?]
+
+    GetGov("getgov") -> HashMap<String, serde_json::Value> {
+        id: String,
+    }
+    |self| [into_json(&self.id)?]
+
+    GetMasternode("getmasternode") -> json::MasternodeResult {
+        masternode_id: String,
+    }
+    |self| [into_json(&self.masternode_id)?]
+
+    GetMasternodeBlocks("getmasternodeblocks") -> HashMap<String, String> {
+        identifier: json::MasternodeBlock,
+        depth: Option<u64>,
+    }
+    |self| {
+        let id = &self.identifier;
+        if id.id.is_none() && id.owner_address.is_none() && id.operator_address.is_none() {
+            return Err(Error::Custom(
+                "a masternode id, owner address or operator address is required".into(),
+            ));
+        }
+    }
+    [into_json(&self.identifier)?, into_json(self.depth)?]
+
+    IsAppliedCustomTx("isappliedcustomtx") -> bool {
+        txid: String,
+        block_height: u64,
+    }
+    |self| [into_json(&self.txid)?, into_json(self.block_height)?]
+
+    ListGovs("listgovs") -> Vec<json::GovVariable> {
+        prefix: Option<String>,
+    }
+    |self| [into_json(&self.prefix)?]
+
+    ListMasternodes("listmasternodes") -> json::ListMasternodesResult {
+        pagination: Option<json::MasternodePagination>,
+        verbose: Option<bool>,
+    }
+    |self| [obj_into_json(self.pagination.as_ref())?, into_json(self.verbose)?]
+
+    ResignMasternode("resignmasternode") -> String {
+        masternode_id: String,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.masternode_id)?, into_json(&self.utxos)?]
+
+    SetGov("setgov") -> String {
+        variables: HashMap<String, serde_json::Value>,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.variables)?, into_json(&self.utxos)?]
+
+    SetGovHeight("setgovheight") -> String {
+        variables: HashMap<String, serde_json::Value>,
+        activation_height: u64,
+        utxos: Option<UTXO>,
+    }
+    |self| [
+        into_json(&self.variables)?,
+        into_json(self.activation_height)?,
+        into_json(&self.utxos)?,
+    ]
+
+    UnsetGov("unsetgov") -> String {
+        variables: HashMap<String, Vec<String>>,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.variables)?, into_json(&self.utxos)?]
+
+    UpdateMasternode("updatemasternode") -> String {
+        masternode_id: String,
+        values: json::UpdateMasternodeValues,
+        utxos: Option<UTXO>,
+    }
+    |self| {
+        let values = &self.values;
+        if values.owner_address.is_none()
+            && values.operator_address.is_none()
+            && values.reward_address.is_none()
+        {
+            return Err(Error::Custom("no masternode values to update".into()));
+        }
+    }
+    [
+        into_json(&self.masternode_id)?,
+        into_json(&self.values)?,
+        into_json(&self.utxos)?,
+    ]
+}
@@ -0,0 +1,143 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests as values.
+//!
+//! Every RPC of the DeFi traits is a type implementing [RpcRequest], with the
+//! arguments of the trait method as public fields. The trait methods are thin
+//! wrappers that send these requests, so a request can be built once and then
+//! sent, batched, logged or retried generically:
+//!
+//! ```no_run
+//! # async fn example(client: &defichain_rpc::Client) -> defichain_rpc::Result<()> {
+//! use defichain_rpc::requests::{vault, RpcRequest};
+//! use defichain_rpc::RpcApi;
+//!
+//! let request = vault::GetVault {
+//!     vault_id: "0123...".into(),
+//!     verbose: Some(true),
+//! };
+//! println!("{} {:?}", vault::GetVault::METHOD, request.params()?);
+//! let vault = client.request(&request).await?;
+//!
+//! let mut batch = client.batch();
+//! let vault = batch.request(request);
+//! let count = batch.request(defichain_rpc::requests::blockchain::GetBlockCount {});
+//! let mut results = batch.send().await?;
+//! let count = results.take(count)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The types are named after the RPCs, so types with the same name as their
+//! argument in `defichain-rpc-json` are best used through their module.
+
+use crate::client::Result;
+
+/// An RPC with its arguments.
+pub trait RpcRequest: Send + Sync {
+    /// The type of the result.
+    type Response: for<'a> serde::de::Deserialize<'a> + Send;
+
+    /// The name of the RPC method.
+    const METHOD: &'static str;
+
+    /// The parameters of the call, in order.
+    ///
+    /// Fails if an argument can't be serialized or is invalid.
+    fn params(&self) -> Result<Vec<serde_json::Value>>;
+}
+
+/// Define request types with the given fields, and implement [RpcRequest] for
+/// them with the parameters listed after the closure-like head. Statements in
+/// an optional block before the list, such as checks of the arguments, run
+/// first.
+///
+/// Also defines `RPCS`, the methods of the requests with their number of
+/// parameters, which the [coverage](crate::coverage) module reports.
+macro_rules! requests {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident($method:literal) -> $response:ty {
+            $($(#[$field_attr:meta])* $field:ident: $ty:ty),* $(,)?
+        }
+        |$this:ident| $({ $($prelude:tt)* })? [$($param:expr),* $(,)?]
+    )*) => {
+        $(
+            #[doc = concat!("The `", $method, "` RPC.")]
+            $(#[$attr])*
+            #[derive(Debug)]
+            pub struct $name {
+                $($(#[$field_attr])* pub $field: $ty,)*
+            }
+
+            impl $crate::requests::RpcRequest for $name {
+                type Response = $response;
+
+                const METHOD: &'static str = $method;
+
+                fn params(&$this) -> $crate::Result<Vec<serde_json::Value>> {
+                    $($($prelude)*)?
+                    Ok(vec![$($param),*])
+                }
+            }
+        )*
+
+        /// The methods of the requests of this module, with their number of
+        /// parameters.
+        pub(crate) const RPCS: &[(&str, usize)] =
+            &[$(($method, <[&str]>::len(&[$(stringify!($param)),*]))),*];
+    };
+}
+
+pub mod account;
+pub mod blockchain;
+pub mod governance;
+pub mod icxorderbook;
+pub mod loan;
+pub mod masternode;
+pub mod oracle;
+pub mod poolpair;
+pub mod spv;
+pub mod token;
+pub mod vault;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockNode;
+    use crate::{Error, RpcApi};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn sends_requests() {
+        let request = vault::ListAuctionHistory {
+            owner: Some("owner".into()),
+            pagination: None,
+        };
+        assert_eq!(vault::ListAuctionHistory::METHOD, "listauctionhistory");
+        assert_eq!(request.params().unwrap(), vec![json!("owner"), json!(null)]);
+
+        let node = MockNode::new().respond("getblockcount", json!(42));
+        assert_eq!(node.request(&blockchain::GetBlockCount {}).await.unwrap(), 42);
+        node.assert_last_request("getblockcount", json!([]));
+        let raw = node.request_raw(&blockchain::GetBlockCount {}).await.unwrap();
+        assert_eq!(raw.get(), "42");
+
+        // Invalid arguments fail before anything is sent.
+        let request = blockchain::GetBlock {
+            hash: node.state().blocks[0],
+            verbosity: 3,
+        };
+        assert!(matches!(request.params(), Err(Error::Custom(_))));
+        assert!(node.request(&request).await.is_err());
+        node.assert_last_request("getblockcount", json!([]));
+    }
+}
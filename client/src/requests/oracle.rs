@@ -0,0 +1,118 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [OracleRPC](crate::OracleRPC).
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::oracle as json;
+
+use crate::{into_json, obj_into_json, Error, Result};
+
+/// The node only accepts weightages from 1 to 100.
+fn check_weightage(weightage: u8) -> Result<()> {
+    match weightage {
+        1..=100 => Ok(()),
+        _ => Err(Error::Custom(format!("weightage must be between 1 and 100, got {}", weightage))),
+    }
+}
+
+requests! {
+    AppointOracle("appointoracle") -> String {
+        address: String,
+        price_feeds: Vec<json::OraclePriceFeed>,
+        weightage: u8,
+        utxos: Option<UTXO>,
+    }
+    |self| {
+        check_weightage(self.weightage)?;
+    }
+    [
+        into_json(&self.address)?,
+        into_json(&self.price_feeds)?,
+        into_json(self.weightage)?,
+        into_json(&self.utxos)?,
+    ]
+
+    GetFixedIntervalPrice("getfixedintervalprice") -> json::FixedIntervalPrice {
+        id: String,
+    }
+    |self| [into_json(&self.id)?]
+
+    GetFutureSwapBlock("getfutureswapblock") -> u64 {}
+    |self| []
+
+    GetOracleData("getoracledata") -> json::OracleData {
+        oracle_id: String,
+    }
+    |self| [into_json(&self.oracle_id)?]
+
+    GetPrice("getprice") -> f64 {
+        price_feed: json::OraclePriceFeed,
+    }
+    |self| [into_json(&self.price_feed)?]
+
+    ListFixedIntervalPrices("listfixedintervalprices") -> Vec<json::ListFixedIntervalPrice> {
+        pagination: Option<json::FixedIntervalPricePagination>,
+    }
+    |self| [obj_into_json(self.pagination.as_ref())?]
+
+    ListLatestRawPrices("listlatestrawprices") -> Vec<json::OracleRawPrice> {
+        price_feed: Option<json::OraclePriceFeed>,
+        pagination: Option<json::OraclePagination>,
+    }
+    |self| [into_json(&self.price_feed)?, obj_into_json(self.pagination.as_ref())?]
+
+    ListOracles("listoracles") -> Vec<String> {
+        pagination: Option<json::OraclePagination>,
+    }
+    |self| [obj_into_json(self.pagination.as_ref())?]
+
+    ListPrices("listprices") -> Vec<json::ListPricesData> {
+        pagination: Option<json::ListPricesPagination>,
+    }
+    |self| [obj_into_json(self.pagination.as_ref())?]
+
+    RemoveOracle("removeoracle") -> String {
+        oracle_id: String,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.oracle_id)?, into_json(&self.utxos)?]
+
+    SetOracleData("setoracledata") -> String {
+        oracle_id: String,
+        timestamp: u64,
+        prices: Vec<json::OraclePrice>,
+        utxos: Option<UTXO>,
+    }
+    |self| [
+        into_json(&self.oracle_id)?,
+        into_json(self.timestamp)?,
+        into_json(&self.prices)?,
+        into_json(&self.utxos)?,
+    ]
+
+    UpdateOracle("updateoracle") -> String {
+        oracle_id: String,
+        address: String,
+        price_feeds: Vec<json::OraclePriceFeed>,
+        weightage: u8,
+        utxos: Option<UTXO>,
+    }
+    |self| {
+        check_weightage(self.weightage)?;
+    }
+    [
+        into_json(&self.oracle_id)?,
+        into_json(&self.address)?,
+        into_json(&self.price_feeds)?,
+        into_json(self.weightage)?,
+        into_json(&self.utxos)?,
+    ]
+}
@@ -0,0 +1,92 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [PoolPairRPC](crate::PoolPairRPC).
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::poolpair as json;
+
+use crate::{into_json, obj_into_json};
+
+requests! {
+    AddPoolLiquidity("addpoolliquidity") -> String {
+        from: json::AddPoolLiquiditySource,
+        share_address: String,
+        options: json::PoolLiquidityOptions,
+    }
+    |self| [
+        into_json(&self.from)?,
+        into_json(&self.share_address)?,
+        into_json(&self.options)?,
+    ]
+
+    CompositeSwap("compositeswap") -> String {
+        metadata: json::PoolSwapMetadata,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.metadata)?, into_json(&self.utxos)?]
+
+    CreatePoolPair("createpoolpair") -> String {
+        metadata: json::CreatePoolPairMetadata,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.metadata)?, into_json(&self.utxos)?]
+
+    GetPoolPair("getpoolpair") -> json::PoolPairsResult {
+        symbol: String,
+        verbose: Option<bool>,
+    }
+    |self| [into_json(&self.symbol)?, into_json(self.verbose)?]
+
+    ListPoolPairs("listpoolpairs") -> json::PoolPairsResult {
+        pagination: Option<json::PoolPairPagination>,
+        verbose: Option<bool>,
+    }
+    |self| [obj_into_json(self.pagination.as_ref())?, into_json(self.verbose)?]
+
+    ListPoolShares("listpoolshares") -> json::PoolSharesResult {
+        pagination: json::PoolPairPagination,
+        verbose: Option<bool>,
+        options: json::PoolShareOptions,
+    }
+    |self| [
+        into_json(&self.pagination)?,
+        into_json(self.verbose)?,
+        into_json(&self.options)?,
+    ]
+
+    PoolSwap("poolswap") -> String {
+        metadata: json::PoolSwapMetadata,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.metadata)?, into_json(&self.utxos)?]
+
+    RemovePoolLiquidity("removepoolliquidity") -> String {
+        address: String,
+        pool_account: String,
+        options: json::PoolLiquidityOptions,
+    }
+    |self| [
+        into_json(&self.address)?,
+        into_json(&self.pool_account)?,
+        into_json(&self.options)?,
+    ]
+
+    TestPoolSwap("testpoolswap") -> String {
+        metadata: json::PoolSwapMetadata,
+    }
+    |self| [into_json(&self.metadata)?]
+
+    UpdatePoolPair("updatepoolpair") -> String {
+        metadata: json::UpdatePoolPairMetadata,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.metadata)?, into_json(&self.utxos)?]
+}
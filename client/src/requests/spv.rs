@@ -0,0 +1,160 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [SpvRPC](crate::SpvRPC).
+
+use defichain_rpc_json::spv as json;
+
+use crate::{into_json, Error};
+
+/// The node only accepts HTLC timeouts from 9 to 1000 Bitcoin blocks.
+const HTLC_TIMEOUT: std::ops::RangeInclusive<u32> = 9..=1000;
+
+requests! {
+    ClaimHtlc("spv_claimhtlc") -> json::SendMessageResult {
+        script_address: String,
+        destination_address: String,
+        options: json::ClaimHtlcOptions,
+    }
+    |self| [
+        into_json(&self.script_address)?,
+        into_json(&self.destination_address)?,
+        into_json(&self.options.seed)?,
+        into_json(self.options.fee_rate)?,
+    ]
+
+    CreateAnchor("spv_createanchor") -> json::CreateAnchorResult {
+        inputs: Vec<json::CreateAnchorInput>,
+        reward_address: String,
+        options: Option<json::CreateAnchorOptions>,
+    }
+    |self| {
+        let options = self.options.as_ref();
+    }
+    [
+        into_json(&self.inputs)?,
+        into_json(&self.reward_address)?,
+        into_json(options.and_then(|o| o.send))?,
+        into_json(options.and_then(|o| o.fee_rate))?,
+    ]
+
+    CreateHtlc("spv_createhtlc") -> json::CreateHtlcResult {
+        receiver_pub_key: String,
+        owner_pub_key: String,
+        options: json::CreateHtlcOptions,
+    }
+    |self| {
+        if !HTLC_TIMEOUT.contains(&self.options.timeout) {
+            return Err(Error::Custom(format!(
+                "timeout must be between {} and {} blocks, got {}",
+                HTLC_TIMEOUT.start(),
+                HTLC_TIMEOUT.end(),
+                self.options.timeout
+            )));
+        }
+    }
+    [
+        into_json(&self.receiver_pub_key)?,
+        into_json(&self.owner_pub_key)?,
+        // The node takes the timeout as a string.
+        into_json(self.options.timeout.to_string())?,
+        into_json(&self.options.seed)?,
+    ]
+
+    DecodeHtlcScript("spv_decodehtlcscript") -> json::DecodeHtlcResult {
+        redeem_script: String,
+    }
+    |self| [into_json(&self.redeem_script)?]
+
+    GetAddressPubKey("spv_getaddresspubkey") -> String {
+        address: String,
+    }
+    |self| [into_json(&self.address)?]
+
+    GetHtlcSeed("spv_gethtlcseed") -> String {
+        address: String,
+    }
+    |self| [into_json(&self.address)?]
+
+    GetNewAddress("spv_getnewaddress") -> String {}
+    |self| []
+
+    ListAnchorAuths("spv_listanchorauths") -> Vec<json::ListAnchorAuthsResult> {}
+    |self| []
+
+    ListAnchorRewardConfirms("spv_listanchorrewardconfirms") -> Vec<json::ListAnchorRewardConfirmsResult> {}
+    |self| []
+
+    ListAnchorRewards("spv_listanchorrewards") -> Vec<json::ListAnchorRewardsResult> {}
+    |self| []
+
+    ListAnchors("spv_listanchors") -> Vec<json::ListAnchorsResult> {
+        options: Option<json::ListAnchorsOptions>,
+    }
+    |self| {
+        // The node takes the filters in order, with -1 for no bound.
+        let options = self.options.as_ref();
+        let bound = |f: fn(&json::ListAnchorsOptions) -> Option<i64>| {
+            into_json(options.and_then(f).unwrap_or(-1))
+        };
+    }
+    [
+        bound(|o| o.min_btc_height)?,
+        bound(|o| o.max_btc_height)?,
+        bound(|o| o.min_confs)?,
+        bound(|o| o.max_confs)?,
+    ]
+
+    ListAnchorsPending("spv_listanchorspending") -> Vec<json::ListAnchorsResult> {}
+    |self| []
+
+    ListAnchorsUnrewarded("spv_listanchorsunrewarded") -> Vec<json::ListAnchorsResult> {}
+    |self| []
+
+    ListHtlcOutputs("spv_listhtlcoutputs") -> Vec<json::ListHtlcsOutputsResult> {
+        script_address: Option<String>,
+    }
+    |self| [into_json(&self.script_address)?]
+
+    ListReceivedByAddress("spv_listreceivedbyaddress") -> Vec<json::ReceivedByAddressInfo> {
+        min_confirmations: Option<u32>,
+        address: Option<String>,
+    }
+    |self| [into_json(self.min_confirmations.unwrap_or(1))?, into_json(&self.address)?]
+
+    RefundHtlc("spv_refundhtlc") -> json::SendMessageResult {
+        script_address: String,
+        destination_address: String,
+        fee_rate: Option<u64>,
+    }
+    |self| [
+        into_json(&self.script_address)?,
+        into_json(&self.destination_address)?,
+        into_json(self.fee_rate)?,
+    ]
+
+    RefundHtlcAll("spv_refundhtlcall") -> Vec<String> {
+        destination_address: String,
+        fee_rate: Option<u64>,
+    }
+    |self| [into_json(&self.destination_address)?, into_json(self.fee_rate)?]
+
+    SendToAddress("spv_sendtoaddress") -> json::SendMessageResult {
+        address: String,
+        amount: f64,
+        fee_rate: Option<u64>,
+    }
+    |self| [into_json(&self.address)?, into_json(self.amount)?, into_json(self.fee_rate)?]
+
+    SetLastHeight("spv_setlastheight") -> () {
+        height: u32,
+    }
+    |self| [into_json(self.height)?]
+}
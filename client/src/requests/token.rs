@@ -0,0 +1,71 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [TokenRPC](crate::TokenRPC).
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::token as json;
+
+use crate::{into_json, obj_into_json};
+
+requests! {
+    BurnTokens("burntokens") -> String {
+        amounts: String,
+        from: Option<String>,
+        context: Option<String>,
+        utxos: Option<UTXO>,
+    }
+    |self| [
+        into_json(&self.amounts)?,
+        into_json(&self.from)?,
+        into_json(&self.context)?,
+        into_json(&self.utxos)?,
+    ]
+
+    CreateToken("createtoken") -> String {
+        metadata: json::CreateTokenMetadata,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.metadata)?, into_json(&self.utxos)?]
+
+    DecodeCustomTx("decodecustomtx") -> () {
+        hexstring: String,
+        iswitness: Option<bool>,
+    }
+    |self| [into_json(&self.hexstring)?, into_json(self.iswitness)?]
+
+    GetCustomTx("getcustomtx") -> () {
+        txid: String,
+        blockhash: Option<String>,
+    }
+    |self| [into_json(&self.txid)?, into_json(&self.blockhash)?]
+
+    GetToken("gettoken") -> json::TokenResult {
+        symbol_key: String,
+    }
+    |self| [into_json(&self.symbol_key)?]
+
+    ListTokens("listtokens") -> json::TokenResult {
+        pagination: Option<json::TokenPagination>,
+        verbose: Option<bool>,
+    }
+    |self| [obj_into_json(self.pagination.as_ref())?, into_json(self.verbose)?]
+
+    MintTokens("minttokens") -> String {
+        options: json::MintTokensOptions,
+    }
+    |self| [into_json(&self.options)?]
+
+    UpdateToken("updatetoken") -> String {
+        token: String,
+        metadata: Option<json::UpdateTokenMetadata>,
+    }
+    |self| [into_json(&self.token)?, into_json(&self.metadata)?]
+}
@@ -0,0 +1,98 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Requests of [VaultRPC](crate::VaultRPC).
+
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::vault as json;
+
+use crate::{into_json, obj_into_json};
+
+requests! {
+    CloseVault("closevault") -> String {
+        close_vault: json::CloseVault,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.close_vault)?, into_json(&self.utxos)?]
+
+    CreateVault("createvault") -> String {
+        vault: json::CreateVault,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.vault)?, into_json(&self.utxos)?]
+
+    DepositToVault("deposittovault") -> String {
+        deposit_vault: json::DepositVault,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.deposit_vault)?, into_json(&self.utxos)?]
+
+    EstimateCollateral("estimatecollateral") -> () {
+        target_ratio: u64,
+        token_split: json::TokenPercentageSplit,
+    }
+    |self| [into_json(self.target_ratio)?, into_json(&self.token_split)?]
+
+    EstimateLoan("estimateloan") -> () {
+        vault_id: String,
+        token_split: json::TokenPercentageSplit,
+        target_ratio: Option<u64>,
+    }
+    |self| [
+        into_json(&self.vault_id)?,
+        into_json(&self.token_split)?,
+        into_json(self.target_ratio)?,
+    ]
+
+    EstimateVault("estimatevault") -> json::VaultEstimation {}
+    |self| []
+
+    GetVault("getvault") -> json::VaultResult {
+        vault_id: String,
+        verbose: Option<bool>,
+    }
+    |self| [into_json(&self.vault_id)?, into_json(self.verbose.unwrap_or_default())?]
+
+    ListAuctionHistory("listauctionhistory") -> Vec<json::ListAuctionHistoryDetail> {
+        owner: Option<String>,
+        pagination: Option<json::ListAuctionHistoryPagination>,
+    }
+    |self| [into_json(&self.owner)?, into_json(&self.pagination)?]
+
+    ListAuctions("listauctions") -> Vec<json::VaultLiquidation> {
+        pagination: Option<json::AuctionPagination>,
+    }
+    |self| [obj_into_json(self.pagination.as_ref())?]
+
+    ListVaults("listvaults") -> Vec<json::VaultResult> {
+        options: json::ListVaultOptions,
+        pagination: json::VaultPagination,
+    }
+    |self| [into_json(&self.options)?, into_json(&self.pagination)?]
+
+    PlaceAuctionBid("placeauctionbid") -> String {
+        place_auction_bid: json::PlaceAuctionBid,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.place_auction_bid)?, into_json(&self.utxos)?]
+
+    UpdateVault("updatevault") -> String {
+        vault_id: String,
+        vault: json::UpdateVault,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.vault_id)?, into_json(&self.vault)?, into_json(&self.utxos)?]
+
+    WithdrawFromVault("withdrawfromvault") -> String {
+        withdraw_vault: json::WithdrawVault,
+        utxos: Option<UTXO>,
+    }
+    |self| [into_json(&self.withdraw_vault)?, into_json(&self.utxos)?]
+}
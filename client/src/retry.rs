@@ -0,0 +1,407 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Retrying of failed calls.
+//!
+//! [RetryClient] wraps any [RpcApi] implementation and retries calls that
+//! failed with a retryable error, sleeping with exponential backoff between
+//! attempts. All domain traits are available on it.
+//!
+//! ```no_run
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use std::time::Duration;
+//! use defichain_rpc::{Auth, Client, RetryClient, RpcApi};
+//!
+//! let client = Client::new("http://localhost:8554", Auth::None).await?;
+//! let rpc = RetryClient::new(client);
+//! rpc.wait_until_ready(Duration::from_secs(60)).await?;
+//! let count = rpc.get_block_count().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::client::{Result, RpcApi};
+use crate::error::Error;
+use crate::multinode::is_pinned;
+
+/// Whether the error is worth retrying by default.
+///
+/// These are the [transient](Error::is_transient) errors, such as errors
+/// returned while the node is warming up, transport errors like a refused
+/// connection while the node is restarting, and timeouts.
+///
+/// Note that a transport error or a timeout may also occur after the node has already
+/// processed the request, so retrying calls that create transactions on
+/// transport errors can submit them twice. [is_retryable_call] takes care of
+/// that.
+pub fn is_retryable(error: &Error) -> bool {
+    error.is_transient()
+}
+
+/// Whether the failed call of `method` is worth retrying by default.
+///
+/// Calls that may create transactions or change the state of the node, the
+/// ones [pinned](crate::multinode::is_pinned) to a single node, are only
+/// retried when the node answered with a transient error such as
+/// [InWarmup](crate::RpcErrorCode::InWarmup), since it didn't process them
+/// then. Other calls are retried on all [retryable](is_retryable) errors.
+pub fn is_retryable_call(method: &str, error: &Error) -> bool {
+    if is_pinned(method) {
+        matches!(error.rpc_code(), Some(code) if code.is_transient())
+    } else {
+        is_retryable(error)
+    }
+}
+
+/// How often and how long to wait between retries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The time to wait before the first retry.
+    pub initial_backoff: Duration,
+    /// The upper bound for the time to wait between attempts.
+    pub max_backoff: Duration,
+    /// The factor by which the backoff grows after every retry. Factors below
+    /// 1 are treated as 1.
+    pub multiplier: f64,
+    /// The fraction of the backoff that is randomized, between 0 and 1.
+    /// Values out of range are clamped, and a jitter that is not finite is
+    /// ignored.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The backoff before the given retry, without jitter.
+    ///
+    /// `retry` starts at 0 for the wait after the first failed attempt.
+    pub fn base_backoff(&self, retry: u32) -> Duration {
+        let multiplier = self.multiplier.max(1.0);
+        let backoff = self.initial_backoff.as_secs_f64() * multiplier.powi(retry.min(1024) as i32);
+        at_most(backoff, self.max_backoff)
+    }
+
+    /// The backoff before the given retry, with jitter applied. It is never
+    /// longer than the maximum backoff.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let base = self.base_backoff(retry).as_secs_f64();
+        let jitter = if self.jitter.is_finite() && self.jitter > 0.0 {
+            self.jitter.min(1.0)
+        } else {
+            0.0
+        };
+        // A factor in [1 - jitter, 1 + jitter].
+        let factor = 1.0 + jitter * (2.0 * random_fraction() - 1.0);
+        at_most(base * factor, self.max_backoff)
+    }
+}
+
+/// `secs` as a duration, but no longer than `max`.
+fn at_most(secs: f64, max: Duration) -> Duration {
+    // Also catches durations that don't fit in a Duration.
+    if secs.is_nan() || secs >= max.as_secs_f64() {
+        return max;
+    }
+    Duration::from_secs_f64(secs.max(0.0)).min(max)
+}
+
+/// A random number in [0, 1], good enough to spread out retries.
+fn random_fraction() -> f64 {
+    RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64
+}
+
+/// An [RpcApi] implementation that retries failed calls of the wrapped one.
+pub struct RetryClient<C> {
+    inner: C,
+    policy: RetryPolicy,
+    retryable: Option<fn(&Error) -> bool>,
+}
+
+impl<C: fmt::Debug> fmt::Debug for RetryClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::RetryClient({:?}, {:?})", self.inner, self.policy)
+    }
+}
+
+impl<C: RpcApi + Sync + Send> RetryClient<C> {
+    /// Wrap `inner` using the default [RetryPolicy].
+    pub fn new(inner: C) -> Self {
+        RetryClient::with_policy(inner, RetryPolicy::default())
+    }
+
+    /// Wrap `inner` using the given [RetryPolicy].
+    pub fn with_policy(inner: C, policy: RetryPolicy) -> Self {
+        RetryClient {
+            inner,
+            policy,
+            retryable: None,
+        }
+    }
+
+    /// Replace the classification of retryable errors, which defaults to
+    /// [is_retryable_call].
+    ///
+    /// The given classification applies to all methods, including ones that
+    /// create transactions.
+    pub fn retry_if(mut self, retryable: fn(&Error) -> bool) -> Self {
+        self.retryable = Some(retryable);
+        self
+    }
+
+    fn is_retryable(&self, cmd: &str, error: &Error) -> bool {
+        match self.retryable {
+            Some(retryable) => retryable(error),
+            None => is_retryable_call(cmd, error),
+        }
+    }
+
+    /// The policy used to retry calls.
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Get the wrapped client.
+    pub fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Wait until the node finished warming up and accepts calls.
+    ///
+    /// See [wait_until_ready].
+    pub async fn wait_until_ready(&self, timeout: Duration) -> Result<()> {
+        wait_until_ready(&self.inner, timeout, self.policy.initial_backoff).await
+    }
+}
+
+#[async_trait]
+impl<C: RpcApi + Sync + Send> RpcApi for RetryClient<C> {
+    async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        let mut retry = 0;
+        loop {
+            let err = match self.inner.call(cmd, args).await {
+                Err(e) if retry + 1 < self.policy.max_attempts && self.is_retryable(cmd, &e) => e,
+                res => return res,
+            };
+            let backoff = self.policy.backoff(retry);
+            debug!(target: "defichain_rpc", "retrying {} in {:?} after error: {}", cmd, backoff, err);
+            tokio::time::sleep(backoff).await;
+            retry += 1;
+        }
+    }
+}
+
+/// Wait until the node finished warming up and accepts calls.
+///
+/// Polls the node every `interval` until it answers a call without a
+/// retryable error, for instance while it is still loading the block index
+/// or not yet listening. Returns the last error if the node is not ready
+/// after `timeout`.
+pub async fn wait_until_ready<C: RpcApi + Sync>(
+    rpc: &C,
+    timeout: Duration,
+    interval: Duration,
+) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match rpc.call::<serde_json::Value>("getblockcount", &[]).await {
+            Ok(_) => return Ok(()),
+            Err(e) if is_retryable(&e) && Instant::now() + interval < deadline => {
+                debug!(target: "defichain_rpc", "waiting for node to be ready: {}", e);
+                tokio::time::sleep(interval).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RpcErrorCode;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails with the given RPC error code, or a transport error without one,
+    /// until it was called `failures` times.
+    struct Flaky {
+        failures: u32,
+        code: Option<i32>,
+        calls: AtomicU32,
+    }
+
+    impl Flaky {
+        fn new(failures: u32, code: i32) -> Flaky {
+            Flaky {
+                failures,
+                code: Some(code),
+                calls: AtomicU32::new(0),
+            }
+        }
+
+        fn unreachable(failures: u32) -> Flaky {
+            Flaky {
+                code: None,
+                ..Flaky::new(failures, 0)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RpcApi for Flaky {
+        async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+            &self,
+            _: &str,
+            _: &[serde_json::Value],
+        ) -> Result<T> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                Err(Error::JsonRpc(match self.code {
+                    Some(code) => jsonrpc_async::Error::Rpc(jsonrpc_async::error::RpcError {
+                        code,
+                        message: "failure".into(),
+                        data: None,
+                    }),
+                    None => {
+                        let err = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+                        jsonrpc_async::Error::Transport(Box::new(err))
+                    }
+                }))
+            } else {
+                Ok(serde_json::from_value(serde_json::json!(7))?)
+            }
+        }
+    }
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            multiplier: 2.0,
+            jitter: 0.5,
+        }
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let policy = policy(10);
+        assert_eq!(policy.base_backoff(0), Duration::from_millis(1));
+        assert_eq!(policy.base_backoff(1), Duration::from_millis(2));
+        assert_eq!(policy.base_backoff(5), Duration::from_millis(4));
+        assert_eq!(policy.base_backoff(u32::MAX), Duration::from_millis(4));
+        for retry in 0..5 {
+            let backoff = policy.backoff(retry).as_secs_f64();
+            let base = policy.base_backoff(retry).as_secs_f64();
+            assert!(backoff >= base * 0.5 && backoff <= base * 1.5);
+        }
+
+        for &jitter in [f64::NAN, f64::INFINITY, -1.0].iter() {
+            let policy = RetryPolicy {
+                jitter,
+                ..policy.clone()
+            };
+            assert_eq!(policy.backoff(1), policy.base_backoff(1));
+        }
+
+        for &multiplier in [-2.0, 0.5, f64::NAN].iter() {
+            let policy = RetryPolicy {
+                multiplier,
+                ..policy.clone()
+            };
+            assert_eq!(policy.base_backoff(1), Duration::from_millis(1));
+            assert_eq!(policy.base_backoff(3), Duration::from_millis(1));
+            assert!(policy.backoff(3) <= Duration::from_millis(4));
+        }
+        let max = Duration::new(u64::MAX, 999_999_999);
+        let policy = RetryPolicy {
+            max_backoff: max,
+            jitter: 1.0,
+            ..policy
+        };
+        for retry in 0..2000 {
+            policy.backoff(retry);
+        }
+        assert_eq!(policy.base_backoff(2000), max);
+    }
+
+    #[tokio::test]
+    async fn retries_warmup_errors() {
+        let rpc = RetryClient::with_policy(Flaky::new(3, RpcErrorCode::InWarmup.code()), policy(5));
+        assert_eq!(rpc.get_block_count().await.unwrap(), 7);
+        assert_eq!(rpc.get_ref().calls.load(Ordering::SeqCst), 4);
+
+        let rpc = RetryClient::with_policy(Flaky::new(3, RpcErrorCode::InWarmup.code()), policy(3));
+        assert!(rpc.get_block_count().await.is_err());
+        assert_eq!(rpc.get_ref().calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_writes_only_when_not_processed() {
+        let rpc = RetryClient::with_policy(Flaky::unreachable(1), policy(5));
+        assert_eq!(rpc.get_block_count().await.unwrap(), 7);
+        assert_eq!(rpc.get_ref().calls.load(Ordering::SeqCst), 2);
+
+        // The node may have sent the transaction before the connection was reset.
+        let rpc = RetryClient::with_policy(Flaky::unreachable(1), policy(5));
+        assert!(rpc.call::<u64>("sendtoaddress", &[]).await.is_err());
+        assert_eq!(rpc.get_ref().calls.load(Ordering::SeqCst), 1);
+
+        let rpc = RetryClient::with_policy(Flaky::new(1, RpcErrorCode::InWarmup.code()), policy(5));
+        assert_eq!(rpc.call::<u64>("sendtoaddress", &[]).await.unwrap(), 7);
+        assert_eq!(rpc.get_ref().calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_other_errors() {
+        let rpc = RetryClient::with_policy(Flaky::new(1, -8), policy(5));
+        assert!(rpc.get_block_count().await.is_err());
+        assert_eq!(rpc.get_ref().calls.load(Ordering::SeqCst), 1);
+
+        let rpc = rpc.retry_if(|_| true);
+        assert_eq!(rpc.get_block_count().await.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn waits_until_ready() {
+        let rpc = Flaky::new(2, RpcErrorCode::InWarmup.code());
+        wait_until_ready(&rpc, Duration::from_secs(1), Duration::from_millis(1)).await.unwrap();
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), 3);
+
+        let rpc = Flaky::new(1000, RpcErrorCode::InWarmup.code());
+        let res = wait_until_ready(&rpc, Duration::from_millis(20), Duration::from_millis(5)).await;
+        assert!(res.is_err());
+    }
+}
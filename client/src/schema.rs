@@ -0,0 +1,309 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Detection of schema drift.
+//!
+//! The node adds fields to its results with every release, which the types of
+//! `defichain-rpc-json` silently drop until they catch up. [SchemaClient]
+//! wraps any [RpcApi] implementation and finds the fields of every result that
+//! its type doesn't know. In [SchemaMode::Lenient] mode the fields are counted,
+//! logged and passed to callbacks, in [SchemaMode::Strict] mode the call fails
+//! with [Error::UnknownFields], which is the mode for checking the types
+//! against a regtest node in CI:
+//!
+//! ```no_run
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use defichain_rpc::schema::{SchemaClient, SchemaMode};
+//! use defichain_rpc::{Auth, Client, PoolPairRPC};
+//!
+//! let client = Client::new("http://localhost:19554", Auth::None).await?;
+//! let rpc = SchemaClient::with_mode(client, SchemaMode::Strict);
+//! rpc.list_pool_pairs(None, None).await?;
+//!
+//! // Or keep going and report at the end.
+//! let rpc = SchemaClient::new(rpc.into_inner())
+//!     .on_unknown_field(|field| println!("{} doesn't know {}", field.type_name, field.path));
+//! rpc.list_pool_pairs(None, None).await?;
+//! for (field, count) in rpc.unknown_fields() {
+//!     println!("{} {}: {} times", field.method, field.path, count);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Fields are only found where the result is deserialized field by field.
+//! Unknown fields inside `#[serde(untagged)]` enums and `#[serde(flatten)]`
+//! structs are dropped by serde before they can be seen.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_ignored::Path;
+
+use crate::client::{deserialize_error, redact_params, Result, RpcApi};
+use crate::error::Error;
+
+/// What to do with the unknown fields of a result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemaMode {
+    /// Return the result, reporting the unknown fields.
+    Lenient,
+    /// Fail the call with [Error::UnknownFields].
+    Strict,
+}
+
+// Deriving it with `#[default]` needs Rust 1.62.
+#[allow(clippy::derivable_impls)]
+impl Default for SchemaMode {
+    fn default() -> Self {
+        SchemaMode::Lenient
+    }
+}
+
+/// A field of a result that its type doesn't know.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnknownField {
+    /// The name of the RPC method.
+    pub method: String,
+    /// The name of the type the result was deserialized into.
+    pub type_name: &'static str,
+    /// The path of the field in the result, e.g. `vaults[0].newField`.
+    pub path: String,
+}
+
+type Callback = Arc<dyn Fn(&UnknownField) + Send + Sync>;
+
+/// An [RpcApi] implementation that finds the fields of results that their
+/// types don't know.
+///
+/// Calls that fail are passed through unchanged.
+pub struct SchemaClient<C> {
+    inner: C,
+    mode: SchemaMode,
+    callbacks: Vec<Callback>,
+    seen: Mutex<BTreeMap<UnknownField, u64>>,
+}
+
+impl<C: fmt::Debug> fmt::Debug for SchemaClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::SchemaClient({:?}, {:?})", self.inner, self.mode)
+    }
+}
+
+impl<C: RpcApi + Sync + Send> SchemaClient<C> {
+    /// Wrap `inner` in [SchemaMode::Lenient] mode.
+    pub fn new(inner: C) -> Self {
+        SchemaClient::with_mode(inner, SchemaMode::default())
+    }
+
+    /// Wrap `inner` in the given mode.
+    pub fn with_mode(inner: C, mode: SchemaMode) -> Self {
+        SchemaClient {
+            inner,
+            mode,
+            callbacks: Vec::new(),
+            seen: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Call `callback` with every unknown field found, in either mode.
+    pub fn on_unknown_field<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&UnknownField) + Send + Sync + 'static,
+    {
+        self.callbacks.push(Arc::new(callback));
+        self
+    }
+
+    /// The mode of the client.
+    pub fn mode(&self) -> SchemaMode {
+        self.mode
+    }
+
+    /// The unknown fields found so far, with how often each was found.
+    pub fn unknown_fields(&self) -> BTreeMap<UnknownField, u64> {
+        self.seen.lock().unwrap().clone()
+    }
+
+    /// Get the wrapped client.
+    pub fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    fn report(&self, field: UnknownField) {
+        for callback in self.callbacks.iter() {
+            callback(&field);
+        }
+        let mut seen = self.seen.lock().unwrap();
+        if !seen.contains_key(&field) {
+            // Only the first time, the counts have the rest.
+            warn!(
+                target: "defichain_rpc",
+                "the result of {} has the field {} unknown to {}",
+                field.method, field.path, field.type_name
+            );
+        }
+        *seen.entry(field).or_insert(0) += 1;
+    }
+}
+
+/// Format a path like serde_path_to_error does, e.g. `vaults[0].state`.
+fn path_string(path: &Path) -> String {
+    match *path {
+        Path::Root => String::new(),
+        Path::Seq {
+            parent,
+            index,
+        } => format!("{}[{}]", path_string(parent), index),
+        Path::Map {
+            parent,
+            ref key,
+        } => match path_string(parent) {
+            ref p if p.is_empty() => key.clone(),
+            p => format!("{}.{}", p, key),
+        },
+        Path::Some {
+            parent,
+        }
+        | Path::NewtypeStruct {
+            parent,
+        }
+        | Path::NewtypeVariant {
+            parent,
+        } => path_string(parent),
+    }
+}
+
+#[async_trait]
+impl<C: RpcApi + Sync + Send> RpcApi for SchemaClient<C> {
+    async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        let raw = self.inner.call_raw(cmd, args).await?;
+        let type_name = std::any::type_name::<T>();
+        let mut paths = Vec::new();
+        let res = {
+            let mut de = serde_json::Deserializer::from_str(raw.get());
+            let mut track = |path: Path| paths.push(path_string(&path));
+            let de = serde_ignored::Deserializer::new(&mut de, &mut track);
+            serde_path_to_error::deserialize(de).map_err(|e| deserialize_error(raw.get(), e))
+        };
+        let res = res.and_then(|value| {
+            for path in paths.iter() {
+                self.report(UnknownField {
+                    method: cmd.to_owned(),
+                    type_name,
+                    path: path.clone(),
+                });
+            }
+            match self.mode {
+                SchemaMode::Strict if !paths.is_empty() => Err(Error::UnknownFields {
+                    type_name,
+                    paths,
+                }),
+                _ => Ok(value),
+            }
+        });
+        res.map_err(|e| Error::Call {
+            method: cmd.to_owned(),
+            params: redact_params(cmd, args),
+            source: Box::new(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockNode;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize)]
+    struct Pair {
+        #[allow(dead_code)]
+        symbol: String,
+        #[allow(dead_code)]
+        reserves: Vec<Reserve>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Reserve {
+        #[allow(dead_code)]
+        amount: f64,
+        #[allow(dead_code)]
+        owner: Option<Owner>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Owner {
+        #[allow(dead_code)]
+        address: String,
+    }
+
+    fn node() -> MockNode {
+        MockNode::new()
+            .respond("getpair", json!({"symbol": "BTC-DFI", "reserves": [{"amount": 1.0}]}))
+            .respond(
+                "getnewpair",
+                json!({
+                    "symbol": "BTC-DFI",
+                    "status": "open",
+                    "reserves": [{"amount": 1.0}, {"amount": 2.0, "owner": {"address": "a", "kind": 1}}],
+                }),
+            )
+    }
+
+    #[tokio::test]
+    async fn reports_unknown_fields() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let rpc = SchemaClient::new(node())
+            .on_unknown_field(move |field| sink.lock().unwrap().push(field.path.clone()));
+
+        rpc.call::<Pair>("getpair", &[]).await.unwrap();
+        assert!(rpc.unknown_fields().is_empty());
+        rpc.call::<Pair>("getnewpair", &[]).await.unwrap();
+        rpc.call::<Pair>("getnewpair", &[]).await.unwrap();
+        let fields = rpc.unknown_fields();
+        let paths: Vec<_> = fields.iter().map(|(f, n)| (f.path.as_str(), *n)).collect();
+        assert_eq!(paths, vec![("reserves[1].owner.kind", 2), ("status", 2)]);
+        let field = fields.keys().next().unwrap();
+        assert_eq!(field.method, "getnewpair");
+        assert!(field.type_name.ends_with("::Pair"));
+        assert_eq!(reported.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn fails_on_unknown_fields_in_strict_mode() {
+        let rpc = SchemaClient::with_mode(node(), SchemaMode::Strict);
+        rpc.call::<Pair>("getpair", &[]).await.unwrap();
+        let err = rpc.call::<Pair>("getnewpair", &[]).await.unwrap_err();
+        match *err.root() {
+            Error::UnknownFields {
+                ref paths,
+                ..
+            } => assert_eq!(paths, &["reserves[1].owner.kind", "status"]),
+            ref e => panic!("unexpected error: {}", e),
+        }
+        assert_eq!(rpc.unknown_fields().len(), 2);
+
+        let err = rpc.call::<u64>("getpair", &[]).await.unwrap_err();
+        assert!(matches!(err.root(), Error::Deserialize { .. }));
+    }
+}
@@ -0,0 +1,167 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Per-call timeouts.
+//!
+//! Every [Client](crate::Client) has a default timeout for its calls, see
+//! [Client::set_timeout](crate::Client::set_timeout). It can be overridden for
+//! single calls with [RpcApi::with_timeout], for instance for long-polling
+//! calls:
+//!
+//! ```no_run
+//! # async fn example(client: &defichain_rpc::Client) -> defichain_rpc::Result<()> {
+//! use std::time::Duration;
+//! use defichain_rpc::{BlockchainRPC, RpcApi};
+//!
+//! let block = client.with_timeout(Duration::from_secs(600)).wait_for_block_height(1000).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A call that times out is cancelled by dropping its connection, and fails
+//! with [Error::Timeout]. The node may still process a call that timed out.
+
+use std::cell::Cell;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::client::{Result, RpcApi};
+use crate::error::Error;
+
+thread_local! {
+    /// The timeout set by the innermost [WithTimeout] of the call being polled.
+    // A `const` initializer needs Rust 1.59.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static CALL_TIMEOUT: Cell<Option<Duration>> = Cell::new(None);
+}
+
+/// The timeout set with [RpcApi::with_timeout] for the current call, if any.
+pub(crate) fn call_timeout() -> Option<Duration> {
+    CALL_TIMEOUT.with(Cell::get)
+}
+
+/// A future that sets the call timeout while it is polled, so the calls made
+/// by the wrapped future see it like a task-local.
+struct Scoped<F> {
+    timeout: Duration,
+    fut: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        /// Restores the outer timeout, even if polling panics.
+        struct Restore(Option<Duration>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CALL_TIMEOUT.with(|t| t.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CALL_TIMEOUT.with(|t| t.replace(Some(self.timeout))));
+        self.fut.as_mut().poll(cx)
+    }
+}
+
+/// Run `fut`, failing with [Error::Timeout] if it takes longer than `timeout`.
+pub(crate) async fn timeout<F, T>(timeout: Duration, fut: F) -> Result<T>
+where
+    F: std::future::Future<Output = Result<T>>,
+{
+    match tokio::time::timeout(timeout, fut).await {
+        Ok(res) => res,
+        Err(_) => Err(Error::Timeout(timeout)),
+    }
+}
+
+/// An [RpcApi] implementation that makes the calls of the wrapped one with a
+/// different timeout.
+///
+/// Created by [RpcApi::with_timeout].
+pub struct WithTimeout<'a, C> {
+    inner: &'a C,
+    timeout: Duration,
+}
+
+impl<'a, C> WithTimeout<'a, C> {
+    pub(crate) fn new(inner: &'a C, timeout: Duration) -> Self {
+        WithTimeout {
+            inner,
+            timeout,
+        }
+    }
+
+    /// The timeout of the calls.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl<'a, C: fmt::Debug> fmt::Debug for WithTimeout<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "defichain_rpc::WithTimeout({:?}, {:?})", self.inner, self.timeout)
+    }
+}
+
+#[async_trait]
+impl<'a, C: RpcApi + Sync> RpcApi for WithTimeout<'a, C> {
+    async fn call<T: for<'de> serde::de::Deserialize<'de>>(
+        &self,
+        cmd: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        // The scoped timeout lets a wrapped Client use it for its transport,
+        // which may allow a longer timeout than its default.
+        Scoped {
+            timeout: self.timeout,
+            fut: Box::pin(timeout(self.timeout, self.inner.call(cmd, args))),
+        }
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers every call after the given delay.
+    struct Slow(Duration);
+
+    #[async_trait]
+    impl RpcApi for Slow {
+        async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+            &self,
+            _: &str,
+            _: &[serde_json::Value],
+        ) -> Result<T> {
+            assert!(call_timeout().is_some());
+            tokio::time::sleep(self.0).await;
+            Ok(serde_json::from_value(1.into())?)
+        }
+    }
+
+    #[tokio::test]
+    async fn times_out_slow_calls() {
+        assert_eq!(call_timeout(), None);
+        let rpc = Slow(Duration::from_millis(50));
+        let res = rpc.with_timeout(Duration::from_millis(5)).get_block_count().await;
+        assert!(matches!(res, Err(Error::Timeout(t)) if t == Duration::from_millis(5)));
+        let res = rpc.with_timeout(Duration::from_secs(5)).get_block_count().await;
+        assert_eq!(res.unwrap(), 1);
+        assert_eq!(call_timeout(), None);
+    }
+}
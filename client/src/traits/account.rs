@@ -1,4 +1,5 @@
-use crate::{into_json, obj_into_json, Client, Result, RpcApi};
+use crate::requests::account as req;
+use crate::{Result, RpcApi};
 use async_trait::async_trait;
 use defichain_rpc_json::{account::*, common::UTXO};
 
@@ -76,15 +77,19 @@ pub trait AccountRPC: RpcApi {
 }
 
 #[async_trait]
-impl AccountRPC for Client {
+impl<T: RpcApi + Sync> AccountRPC for T {
     async fn account_to_account(
         &self,
         from: String,
         payload: BalanceTransferPayload,
         options: BalanceTransferAccountOptions,
     ) -> Result<String> {
-        self.call("accounttoaccount", &[into_json(from)?, into_json(payload)?, into_json(options)?])
-            .await
+        self.request(&req::AccountToAccount {
+            from,
+            payload,
+            options,
+        })
+        .await
     }
     async fn account_to_utxos(
         &self,
@@ -92,11 +97,19 @@ impl AccountRPC for Client {
         payload: BalanceTransferPayload,
         options: BalanceTransferAccountOptions,
     ) -> Result<String> {
-        self.call("accounttoutxos", &[into_json(from)?, into_json(payload)?, into_json(options)?])
-            .await
+        self.request(&req::AccountToUtxos {
+            from,
+            payload,
+            options,
+        })
+        .await
     }
     async fn future_swap(&self, future: FutureSwap, utxos: Option<UTXO>) -> Result<String> {
-        self.call("futureswap", &[into_json(future)?, into_json(utxos)?]).await
+        self.request(&req::FutureSwap {
+            future,
+            utxos,
+        })
+        .await
     }
     async fn get_account(
         &self,
@@ -104,10 +117,11 @@ impl AccountRPC for Client {
         pagination: Option<GetAccountPagination>,
         indexed_amounts: Option<bool>,
     ) -> Result<AccountAmount> {
-        self.call(
-            "getaccount",
-            &[into_json(owner)?, obj_into_json(pagination)?, into_json(indexed_amounts)?],
-        )
+        self.request(&req::GetAccount {
+            owner: owner.to_owned(),
+            pagination,
+            indexed_amounts,
+        })
         .await
     }
     async fn get_account_history(
@@ -116,20 +130,27 @@ impl AccountRPC for Client {
         block_height: u32,
         txn: u32,
     ) -> Result<AccountHistory> {
-        self.call(
-            "getaccounthistory",
-            &[into_json(owner)?, into_json(block_height)?, into_json(txn)?],
-        )
+        self.request(&req::GetAccountHistory {
+            owner: owner.to_owned(),
+            block_height,
+            txn,
+        })
         .await
     }
     async fn get_burn_info(&self) -> Result<BurnInfo> {
-        self.call("getburninfo", &[]).await
+        self.request(&req::GetBurnInfo {}).await
     }
     async fn get_pending_dusd_swaps(&self, address: String) -> Result<DusdSwapsInfo> {
-        self.call("getpendingdusdswaps", &[into_json(address)?]).await
+        self.request(&req::GetPendingDusdSwaps {
+            address,
+        })
+        .await
     }
     async fn get_pending_future_swaps(&self, address: String) -> Result<GetFutureInfo> {
-        self.call("getpendingfutureswaps", &[into_json(address)?]).await
+        self.request(&req::GetPendingFutureSwaps {
+            address,
+        })
+        .await
     }
     async fn get_token_balances(
         &self,
@@ -137,10 +158,11 @@ impl AccountRPC for Client {
         indexed_amounts: Option<bool>,
         options: Option<GetTokenBalancesOptions>,
     ) -> Result<()> {
-        self.call(
-            "gettokenbalances",
-            &[into_json(pagination)?, into_json(indexed_amounts)?, into_json(options)?],
-        )
+        self.request(&req::GetTokenBalances {
+            pagination,
+            indexed_amounts,
+            options,
+        })
         .await
     }
     async fn history_count(
@@ -148,14 +170,22 @@ impl AccountRPC for Client {
         owner: Option<String>,
         options: AccountHistoryCountOptions,
     ) -> Result<u64> {
-        self.call("historycount", &[into_json(owner)?, into_json(options)?]).await
+        self.request(&req::HistoryCount {
+            owner,
+            options,
+        })
+        .await
     }
     async fn list_account_history(
         &self,
         owner: Option<String>,
         options: AccountHistoryOptions,
     ) -> Result<Vec<AccountHistory>> {
-        self.call("listaccounthistory", &[into_json(owner)?, into_json(options)?]).await
+        self.request(&req::ListAccountHistory {
+            owner,
+            options,
+        })
+        .await
     }
     async fn list_accounts(
         &self,
@@ -164,28 +194,28 @@ impl AccountRPC for Client {
         indexed_amounts: Option<bool>,
         is_mine_only: Option<bool>,
     ) -> Result<Vec<AccountsResult>> {
-        self.call(
-            "listaccounts",
-            &[
-                into_json(pagination)?,
-                into_json(verbose)?,
-                into_json(indexed_amounts)?,
-                into_json(is_mine_only)?,
-            ],
-        )
+        self.request(&req::ListAccounts {
+            pagination,
+            verbose,
+            indexed_amounts,
+            is_mine_only,
+        })
         .await
     }
     async fn list_burn_history(&self, options: BurnHistoryOptions) -> Result<Vec<BurnHistory>> {
-        self.call("listburnhistory", &[into_json(options)?]).await
+        self.request(&req::ListBurnHistory {
+            options,
+        })
+        .await
     }
     async fn list_community_balances(&self) -> Result<CommunityBalanceData> {
-        self.call("listcommunitybalances", &[]).await
+        self.request(&req::ListCommunityBalances {}).await
     }
     async fn list_pending_dusd_swaps(&self) -> Result<Vec<DusdSwapsInfo>> {
-        self.call("listpendingdusdswaps", &[]).await
+        self.request(&req::ListPendingDusdSwaps {}).await
     }
     async fn list_pending_future_swaps(&self) -> Result<Vec<ListFutureInfo>> {
-        self.call("listpendingfutureswaps", &[]).await
+        self.request(&req::ListPendingFutureSwaps {}).await
     }
     async fn send_tokens_to_address(
         &self,
@@ -193,24 +223,172 @@ impl AccountRPC for Client {
         to: AddressBalances,
         options: SendTokensOptions,
     ) -> Result<String> {
-        self.call("sendtokenstoaddress", &[into_json(from)?, into_json(to)?, into_json(options)?])
-            .await
+        self.request(&req::SendTokensToAddress {
+            from,
+            to,
+            options,
+        })
+        .await
     }
     async fn transfer_domain(&self, payload: Vec<TransferDomain>) -> Result<String> {
-        self.call("transferdomain", &[into_json(payload)?]).await
+        self.request(&req::TransferDomain {
+            payload,
+        })
+        .await
     }
     async fn utxos_to_account(
         &self,
         payload: BalanceTransferPayload,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("utxostoaccount", &[into_json(payload)?, into_json(utxos)?]).await
+        self.request(&req::UtxosToAccount {
+            payload,
+            utxos,
+        })
+        .await
     }
     async fn withdraw_future_swap(
         &self,
         future: FutureSwap,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("withdrawfutureswap", &[into_json(future)?, into_json(utxos)?]).await
+        self.request(&req::WithdrawFutureSwap {
+            future,
+            utxos,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{from_json, MockNode};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+        let utxos = json!({"txid": txid, "vout": 1});
+        let payload = json!({"bob": "1@DFI"});
+        let options = json!({"utxos": [utxos]});
+
+        let _ = node
+            .account_to_account(
+                "alice".into(),
+                from_json(payload.clone()),
+                from_json(options.clone()),
+            )
+            .await;
+        node.assert_last_request("accounttoaccount", json!(["alice", payload, options]));
+        let _ = node
+            .account_to_utxos("alice".into(), from_json(payload.clone()), from_json(json!({})))
+            .await;
+        node.assert_last_request("accounttoutxos", json!(["alice", payload, {"utxos": null}]));
+
+        let future = json!({"address": "alice", "amount": "1@DFI", "destination": "DUSD"});
+        let _ = node.future_swap(from_json(future.clone()), None).await;
+        node.assert_last_request("futureswap", json!([future, null]));
+        let _ = node
+            .withdraw_future_swap(from_json(future.clone()), Some(from_json(utxos.clone())))
+            .await;
+        node.assert_last_request("withdrawfutureswap", json!([future, utxos]));
+
+        let _ = node.get_account("alice", None, None).await;
+        node.assert_last_request("getaccount", json!(["alice", {}, null]));
+        let pagination = json!({"start": 1, "including_start": true, "limit": 10});
+        let _ = node.get_account("alice", Some(from_json(pagination.clone())), Some(true)).await;
+        node.assert_last_request("getaccount", json!(["alice", pagination, true]));
+        let _ = node.get_account_history("alice", 100, 2).await;
+        node.assert_last_request("getaccounthistory", json!(["alice", 100, 2]));
+
+        let _ = node.get_burn_info().await;
+        node.assert_last_request("getburninfo", json!([]));
+        let _ = node.get_pending_dusd_swaps("alice".into()).await;
+        node.assert_last_request("getpendingdusdswaps", json!(["alice"]));
+        let _ = node.get_pending_future_swaps("alice".into()).await;
+        node.assert_last_request("getpendingfutureswaps", json!(["alice"]));
+
+        let _ = node.get_token_balances(None, None, None).await;
+        node.assert_last_request("gettokenbalances", json!([null, null, null]));
+        let balance_options = json!({"symbolLookup": true, "includeEth": false});
+        let _ = node
+            .get_token_balances(
+                Some(from_json(pagination.clone())),
+                Some(false),
+                Some(from_json(balance_options.clone())),
+            )
+            .await;
+        node.assert_last_request("gettokenbalances", json!([pagination, false, balance_options]));
+
+        let count_options =
+            json!({"token": "DFI", "txtype": "s", "txtypes": ["s", "r"], "noRewards": true});
+        let _ = node.history_count(Some("mine".into()), from_json(count_options.clone())).await;
+        node.assert_last_request("historycount", json!(["mine", count_options]));
+
+        let history_options = AccountHistoryOptions::new(
+            Some(100),
+            Some(10),
+            Some(true),
+            Some("DFI".into()),
+            None,
+            None,
+            Some(5),
+            None,
+            None,
+            None,
+            None,
+        );
+        let _ = node.list_account_history(None, history_options).await;
+        let request = node.last_request().unwrap();
+        assert_eq!(request.method, "listaccounthistory");
+        assert_eq!(request.params[0], json!(null));
+        assert_eq!(request.params[1]["maxBlockHeight"], json!(100));
+        assert_eq!(request.params[1]["no_rewards"], json!(true));
+        assert_eq!(request.params[1]["limit"], json!(5));
+
+        let pagination = ListAccountsPagination::new(Some("alice".into()), Some(false), Some(5));
+        let _ = node.list_accounts(Some(pagination), Some(true), None, Some(false)).await;
+        node.assert_last_request(
+            "listaccounts",
+            json!([{"start": "alice", "including_start": false, "limit": 5}, true, null, false]),
+        );
+
+        let burn_options =
+            json!({"maxBlockHeight": 100, "depth": 10, "token": "DFI", "txtype": "b", "limit": 5});
+        let _ = node.list_burn_history(from_json(burn_options.clone())).await;
+        node.assert_last_request("listburnhistory", json!([burn_options]));
+        let _ = node.list_community_balances().await;
+        node.assert_last_request("listcommunitybalances", json!([]));
+        let _ = node.list_pending_dusd_swaps().await;
+        node.assert_last_request("listpendingdusdswaps", json!([]));
+        let _ = node.list_pending_future_swaps().await;
+        node.assert_last_request("listpendingfutureswaps", json!([]));
+
+        let from = json!({"alice": ["1@DFI"]});
+        let to = json!({"bob": ["1@DFI", "2@BTC"]});
+        let _ = node
+            .send_tokens_to_address(
+                from_json(from.clone()),
+                from_json(to.clone()),
+                from_json(json!({"selectionMode": "pie"})),
+            )
+            .await;
+        node.assert_last_request(
+            "sendtokenstoaddress",
+            json!([from, to, {"selectionMode": "pie"}]),
+        );
+
+        let transfer = json!({
+            "src": {"address": "alice", "amount": "1@DFI", "domain": "Dvm"},
+            "dst": {"address": "0xbob", "amount": "1@DFI", "domain": "Evm"},
+        });
+        let _ = node.transfer_domain(vec![from_json(transfer.clone())]).await;
+        node.assert_last_request("transferdomain", json!([[transfer]]));
+
+        let _ =
+            node.utxos_to_account(from_json(payload.clone()), Some(from_json(utxos.clone()))).await;
+        node.assert_last_request("utxostoaccount", json!([payload, utxos]));
     }
 }
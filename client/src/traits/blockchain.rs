@@ -1,5 +1,6 @@
 use crate::bitcoin::BlockHash;
-use crate::{into_json, Client, Error, Result, RpcApi};
+use crate::requests::blockchain as req;
+use crate::{Result, RpcApi};
 use async_trait::async_trait;
 use defichain_rpc_json::bitcoin::Txid;
 use defichain_rpc_json::blockchain::*;
@@ -38,40 +39,51 @@ pub trait BlockchainRPC: RpcApi {
 }
 
 #[async_trait]
-impl BlockchainRPC for Client {
+impl<T: RpcApi + Sync> BlockchainRPC for T {
     async fn get_best_block_hash(&self) -> Result<BlockHash> {
-        self.call("getbestblockhash", &[]).await
+        self.request(&req::GetBestBlockHash {}).await
     }
 
     async fn get_block(&self, hash: BlockHash, verbosity: u8) -> Result<GetBlockResult> {
-        if verbosity > 2 {
-            return Err(Error::Custom("Only verbosity up to 2 is supported".into()));
-        }
-        self.call("getblock", &[into_json(hash)?, into_json(verbosity)?]).await
+        self.request(&req::GetBlock {
+            hash,
+            verbosity,
+        })
+        .await
     }
 
     async fn get_block_count(&self) -> Result<u64> {
-        self.call("getblockcount", &[]).await
+        self.request(&req::GetBlockCount {}).await
     }
 
     async fn get_block_hash(&self, height: u32) -> Result<BlockHash> {
-        self.call("getblockhash", &[into_json(height)?]).await
+        self.request(&req::GetBlockHash {
+            height,
+        })
+        .await
     }
 
     async fn get_block_header(&self, hash: BlockHash) -> Result<BlockHeader> {
-        self.call("getblockheader", &[into_json(hash)?]).await
+        self.request(&req::GetBlockHeader {
+            hash,
+        })
+        .await
     }
 
     async fn get_block_stats(&self, height: u32, stats: Option<Vec<String>>) -> Result<BlockStats> {
-        self.call("getblockstats", &[into_json(height)?, into_json(stats)?]).await
+        self.request(&req::GetBlockStats {
+            height,
+            stats,
+        })
+        .await
     }
 
     async fn get_blockchain_info(&self) -> Result<BlockchainInfo> {
-        self.call("getblockchaininfo", &[]).await
+        self.request(&req::GetBlockchainInfo {}).await
     }
 
     async fn get_chain_tips(&self) -> Result<Vec<ChainTip>> {
-        self.call("getchaintips", &[]).await
+        self.request(&req::GetChainTips {}).await
     }
 
     async fn get_chain_tx_stats(
@@ -79,31 +91,44 @@ impl BlockchainRPC for Client {
         n_blocks: Option<u64>,
         block_hash: Option<BlockHash>,
     ) -> Result<ChainTxStats> {
-        self.call("getchaintxstats", &[into_json(n_blocks)?, into_json(block_hash)?]).await
+        self.request(&req::GetChainTxStats {
+            n_blocks,
+            block_hash,
+        })
+        .await
     }
 
     async fn get_difficulty(&self) -> Result<u64> {
-        self.call("getdifficulty", &[]).await
+        self.request(&req::GetDifficulty {}).await
     }
 
     async fn get_mempool_ancestors(&self, tx_id: Txid) -> Result<()> {
-        self.call("getmempoolancestors", &[into_json(tx_id)?]).await
+        self.request(&req::GetMempoolAncestors {
+            tx_id,
+        })
+        .await
     }
 
     async fn get_mempool_descendants(&self, tx_id: Txid) -> Result<()> {
-        self.call("getmempooldescendants", &[into_json(tx_id)?]).await
+        self.request(&req::GetMempoolDescendants {
+            tx_id,
+        })
+        .await
     }
 
     async fn get_mempool_entry(&self, tx_id: Txid) -> Result<MempoolTx> {
-        self.call("getmempoolentry", &[into_json(tx_id)?]).await
+        self.request(&req::GetMempoolEntry {
+            tx_id,
+        })
+        .await
     }
 
     async fn get_mempool_info(&self) -> Result<MempoolInfo> {
-        self.call("getmempoolinfo", &[]).await
+        self.request(&req::GetMempoolInfo {}).await
     }
 
     async fn get_raw_mempool(&self) -> Result<()> {
-        self.call("getrawmempool", &[]).await
+        self.request(&req::GetRawMempool {}).await
     }
 
     async fn get_tx_out(
@@ -112,26 +137,101 @@ impl BlockchainRPC for Client {
         index: u64,
         include_mempool: Option<bool>,
     ) -> Result<UTXODetails> {
-        self.call(
-            "gettxout",
-            &[into_json(tx_id)?, into_json(index)?, into_json(include_mempool.unwrap_or(true))?],
-        )
+        self.request(&req::GetTxOut {
+            tx_id,
+            index,
+            include_mempool,
+        })
         .await
     }
 
     async fn get_tx_out_set_info(&self) -> Result<TxOutSetInfo> {
-        self.call("gettxoutsetinfo", &[]).await
+        self.request(&req::GetTxOutSetInfo {}).await
     }
 
     async fn wait_for_block(&self, blockhash: BlockHash) -> Result<WaitBlockResult> {
-        self.call("waitforblock", &[into_json(blockhash)?]).await
+        self.request(&req::WaitForBlock {
+            blockhash,
+        })
+        .await
     }
 
     async fn wait_for_block_height(&self, height: u64) -> Result<WaitBlockResult> {
-        self.call("waitforblockheight", &[into_json(height)?]).await
+        self.request(&req::WaitForBlockHeight {
+            height,
+        })
+        .await
     }
 
     async fn wait_for_new_block(&self) -> Result<WaitBlockResult> {
-        self.call("waitfornewblock", &[]).await
+        self.request(&req::WaitForNewBlock {}).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Not a glob import, as RpcApi has methods of the same names.
+    use super::BlockchainRPC;
+    use crate::mock::MockNode;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let hash = node.get_best_block_hash().await.unwrap();
+        node.assert_last_request("getbestblockhash", json!([]));
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+
+        let _ = node.get_block(hash, 2).await;
+        node.assert_last_request("getblock", json!([hash, 2]));
+        node.clear_requests();
+        assert!(node.get_block(hash, 3).await.is_err());
+        assert_eq!(node.last_request(), None);
+
+        let _ = node.get_block_count().await;
+        node.assert_last_request("getblockcount", json!([]));
+        let _ = node.get_block_hash(0).await;
+        node.assert_last_request("getblockhash", json!([0]));
+        let _ = node.get_block_header(hash).await;
+        node.assert_last_request("getblockheader", json!([hash]));
+        let _ = node.get_block_stats(10, None).await;
+        node.assert_last_request("getblockstats", json!([10, null]));
+        let _ = node.get_block_stats(10, Some(vec!["txs".into()])).await;
+        node.assert_last_request("getblockstats", json!([10, ["txs"]]));
+        let _ = node.get_blockchain_info().await;
+        node.assert_last_request("getblockchaininfo", json!([]));
+        let _ = node.get_chain_tips().await;
+        node.assert_last_request("getchaintips", json!([]));
+        let _ = node.get_chain_tx_stats(None, None).await;
+        node.assert_last_request("getchaintxstats", json!([null, null]));
+        let _ = node.get_chain_tx_stats(Some(10), Some(hash)).await;
+        node.assert_last_request("getchaintxstats", json!([10, hash]));
+        let _ = node.get_difficulty().await;
+        node.assert_last_request("getdifficulty", json!([]));
+
+        let _ = node.get_mempool_ancestors(txid.parse().unwrap()).await;
+        node.assert_last_request("getmempoolancestors", json!([txid]));
+        let _ = node.get_mempool_descendants(txid.parse().unwrap()).await;
+        node.assert_last_request("getmempooldescendants", json!([txid]));
+        let _ = node.get_mempool_entry(txid.parse().unwrap()).await;
+        node.assert_last_request("getmempoolentry", json!([txid]));
+        let _ = node.get_mempool_info().await;
+        node.assert_last_request("getmempoolinfo", json!([]));
+        let _ = node.get_raw_mempool().await;
+        node.assert_last_request("getrawmempool", json!([]));
+
+        let _ = node.get_tx_out(txid.parse().unwrap(), 1, None).await;
+        node.assert_last_request("gettxout", json!([txid, 1, true]));
+        let _ = node.get_tx_out(txid.parse().unwrap(), 1, Some(false)).await;
+        node.assert_last_request("gettxout", json!([txid, 1, false]));
+        let _ = node.get_tx_out_set_info().await;
+        node.assert_last_request("gettxoutsetinfo", json!([]));
+
+        let _ = node.wait_for_block(hash).await;
+        node.assert_last_request("waitforblock", json!([hash]));
+        let _ = node.wait_for_block_height(10).await;
+        node.assert_last_request("waitforblockheight", json!([10]));
+        let _ = node.wait_for_new_block().await;
+        node.assert_last_request("waitfornewblock", json!([]));
     }
 }
@@ -1,13 +1,19 @@
 use async_trait::async_trait;
+use defichain_rpc_json::common::UTXO;
 use defichain_rpc_json::{bitcoin::Txid, governance::*};
 
-use crate::{into_json, Client, Result, RpcApi};
+use crate::requests::governance as req;
+use crate::requests::masternode::GetGov;
+use crate::{Error, Result, RpcApi};
 
 #[async_trait]
 pub trait GovernanceRPC: RpcApi {
-    // async fn create_gov_cfp(&self, data: CFPData, utxos: Option<UTXO>) -> Result<String>;
-    // async fn create_gov_voc(&self, data: VOCData, utxos: Option<UTXO>) -> Result<String>;
+    async fn create_gov_cfp(&self, data: CFPData, utxos: Option<UTXO>) -> Result<String>;
+    async fn create_gov_voc(&self, data: VOCData, utxos: Option<UTXO>) -> Result<String>;
     async fn get_gov_proposal(&self, proposal_id: Txid) -> Result<ProposalInfo>;
+    /// The current fees of creating proposals, to preview the fee of a
+    /// proposal with [ProposalFees::cfp] or [ProposalFees::voc].
+    async fn get_gov_proposal_fees(&self) -> Result<ProposalFees>;
     async fn list_gov_proposal_votes(
         &self,
         options: Option<ListGovProposalVotesOptions>,
@@ -16,24 +22,280 @@ pub trait GovernanceRPC: RpcApi {
         &self,
         opts: Option<ListProposalsOptions>,
     ) -> Result<Vec<ProposalInfo>>;
-    // async fn vote_gov(&self, data: VoteData, utxos: Option<UTXO>) -> Result<String>;
+    async fn vote_gov(&self, data: VoteData, utxos: Option<UTXO>) -> Result<String>;
+    /// Vote on several proposals, waiting `sleep_time` seconds between the
+    /// votes.
+    async fn vote_gov_batch(
+        &self,
+        votes: Vec<VoteData>,
+        sleep_time: Option<u64>,
+    ) -> Result<Vec<String>>;
 }
 
 #[async_trait]
-impl GovernanceRPC for Client {
+impl<T: RpcApi + Sync> GovernanceRPC for T {
+    async fn create_gov_cfp(&self, data: CFPData, utxos: Option<UTXO>) -> Result<String> {
+        self.request(&req::CreateGovCfp {
+            data,
+            utxos,
+        })
+        .await
+    }
+    async fn create_gov_voc(&self, data: VOCData, utxos: Option<UTXO>) -> Result<String> {
+        self.request(&req::CreateGovVoc {
+            data,
+            utxos,
+        })
+        .await
+    }
     async fn get_gov_proposal(&self, proposal_id: Txid) -> Result<ProposalInfo> {
-        self.call("getgovproposal", &[into_json(proposal_id)?]).await
+        self.request(&req::GetGovProposal {
+            proposal_id,
+        })
+        .await
+    }
+    async fn get_gov_proposal_fees(&self) -> Result<ProposalFees> {
+        let mut gov = self
+            .request(&GetGov {
+                id: "ATTRIBUTES".into(),
+            })
+            .await?;
+        let attributes = match gov.remove("ATTRIBUTES") {
+            Some(attributes) => serde_json::from_value(attributes)?,
+            None => return Err(Error::Custom("getgov returned no ATTRIBUTES".into())),
+        };
+        Ok(ProposalFees::from_attributes(&attributes)?)
     }
     async fn list_gov_proposals(
         &self,
         opts: Option<ListProposalsOptions>,
     ) -> Result<Vec<ProposalInfo>> {
-        self.call("listgovproposals", &[into_json(opts)?]).await
+        self.request(&req::ListGovProposals {
+            opts,
+        })
+        .await
     }
     async fn list_gov_proposal_votes(
         &self,
         opts: Option<ListGovProposalVotesOptions>,
     ) -> Result<Vec<ListVotesResult>> {
-        self.call("listgovproposalvotes", &[into_json(opts)?]).await
+        self.request(&req::ListGovProposalVotes {
+            opts,
+        })
+        .await
+    }
+    async fn vote_gov(&self, data: VoteData, utxos: Option<UTXO>) -> Result<String> {
+        self.request(&req::VoteGov {
+            data,
+            utxos,
+        })
+        .await
+    }
+    async fn vote_gov_batch(
+        &self,
+        votes: Vec<VoteData>,
+        sleep_time: Option<u64>,
+    ) -> Result<Vec<String>> {
+        self.request(&req::VoteGovBatch {
+            votes,
+            sleep_time,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockNode;
+    use crate::Error;
+    use serde_json::json;
+
+    const PAYOUT_ADDRESS: &str = "bcrt1qqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5phstwt";
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+
+        let _ = node.get_gov_proposal(txid.parse().unwrap()).await;
+        node.assert_last_request("getgovproposal", json!([txid]));
+
+        let _ = node.list_gov_proposals(None).await;
+        node.assert_last_request("listgovproposals", json!([null]));
+        let opts = ListProposalsOptions {
+            r#type: Some(ListProposalsType::Cfp),
+            status: Some(ListProposalsStatus::Voting),
+            cycle: None,
+            pagination: Some(ListProposalsPagination {
+                limit: Some(10),
+                ..Default::default()
+            }),
+        };
+        let _ = node.list_gov_proposals(Some(opts)).await;
+        node.assert_last_request(
+            "listgovproposals",
+            json!([{"type": "cfp", "status": "voting", "pagination": {"limit": 10}}]),
+        );
+
+        let _ = node.list_gov_proposal_votes(Some(Default::default())).await;
+        node.assert_last_request("listgovproposalvotes", json!([{}]));
+        let opts = ListGovProposalVotesOptions {
+            proposal_id: Some(txid.parse().unwrap()),
+            masternode: Some("all".into()),
+            cycle: Some(-1),
+            ..Default::default()
+        };
+        let _ = node.list_gov_proposal_votes(Some(opts)).await;
+        node.assert_last_request(
+            "listgovproposalvotes",
+            json!([{"proposalId": txid, "masternode": "all", "cycle": -1}]),
+        );
+
+        let cfp = CFPData::new(
+            "Grant".into(),
+            "https://github.com/DeFiCh/dfips".into(),
+            100.0,
+            PAYOUT_ADDRESS.into(),
+        );
+        assert_eq!(cfp.context_hash, None);
+        let _ = node.create_gov_cfp(cfp, None).await;
+        node.assert_last_request(
+            "creategovcfp",
+            json!([{
+                "title": "Grant",
+                "context": "https://github.com/DeFiCh/dfips",
+                "amount": 100.0,
+                "payoutAddress": PAYOUT_ADDRESS,
+            }, null]),
+        );
+        let hash = context_hash(b"# Vote\n");
+        assert_eq!(hash.len(), 64);
+        let voc = VOCData {
+            context_hash: Some(hash.clone()),
+            emergency: Some(true),
+            ..VOCData::new("Vote".into(), "context".into())
+        };
+        let _ = node.create_gov_voc(voc, None).await;
+        node.assert_last_request(
+            "creategovvoc",
+            json!([{
+                "title": "Vote",
+                "context": "context",
+                "contextHash": hash,
+                "emergency": true,
+            }, null]),
+        );
+
+        let vote = VoteData::new(txid.parse().unwrap(), "mn".into(), VoteDecision::Yes);
+        let _ = node.vote_gov(vote, None).await;
+        node.assert_last_request("votegov", json!([txid, "mn", "yes", null]));
+        let votes = vec![
+            VoteData::new(txid.parse().unwrap(), "mn1".into(), VoteDecision::No),
+            VoteData::new(txid.parse().unwrap(), "mn2".into(), VoteDecision::Neutral),
+        ];
+        let _ = node.vote_gov_batch(votes, Some(2)).await;
+        node.assert_last_request(
+            "votegovbatch",
+            json!([[[txid, "mn1", "no"], [txid, "mn2", "neutral"]], 2]),
+        );
+    }
+
+    #[tokio::test]
+    async fn validates_proposals() {
+        let node = MockNode::new();
+        let cfp = || CFPData::new("Grant".into(), "context".into(), 100.0, PAYOUT_ADDRESS.into());
+
+        let invalid = vec![
+            CFPData {
+                title: "".into(),
+                ..cfp()
+            },
+            CFPData {
+                title: "t".repeat(129),
+                ..cfp()
+            },
+            CFPData {
+                context: "c".repeat(513),
+                ..cfp()
+            },
+            CFPData {
+                cycles: Some(0),
+                ..cfp()
+            },
+            CFPData {
+                cycles: Some(101),
+                ..cfp()
+            },
+            CFPData {
+                amount: 0.0,
+                ..cfp()
+            },
+            CFPData {
+                payout_address: "not an address".into(),
+                ..cfp()
+            },
+        ];
+        for data in invalid {
+            let err = node.create_gov_cfp(data, None).await.unwrap_err();
+            assert!(matches!(err, Error::Custom(_)), "{}", err);
+        }
+        let voc = VOCData {
+            context_hash: Some("h".repeat(513)),
+            ..VOCData::new("Vote".into(), "context".into())
+        };
+        assert!(matches!(node.create_gov_voc(voc, None).await, Err(Error::Custom(_))));
+        assert!(node.requests().is_empty());
+
+        let data = CFPData {
+            title: "t".repeat(128),
+            cycles: Some(100),
+            payout_address: "8F5svywp7bPF3ma7WEiAKgYaRmNViRSbFa".into(),
+            ..cfp()
+        };
+        let _ = node.create_gov_cfp(data, None).await;
+        assert_eq!(node.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn previews_proposal_fees() {
+        let node = MockNode::new().respond("getgov", json!({"ATTRIBUTES": {}}));
+        let fees = node.get_gov_proposal_fees().await.unwrap();
+        assert_eq!(fees, ProposalFees::default());
+        let cfp = CFPData::new("Grant".into(), "context".into(), 100.0, PAYOUT_ADDRESS.into());
+        assert_eq!(fees.cfp(&cfp), 10.0);
+
+        let node = MockNode::new().respond(
+            "getgov",
+            json!({"ATTRIBUTES": {
+                "v0/gov/proposals/cfp_fee": "0.25",
+                "v0/gov/proposals/voc_fee": 50,
+                "v0/params/feature/gov": "true",
+            }}),
+        );
+        let fees = node.get_gov_proposal_fees().await.unwrap();
+        assert_eq!(fees.cfp(&cfp), 25.0);
+        let voc = VOCData::new("Vote".into(), "context".into());
+        assert_eq!(fees.voc(&voc), 50.0);
+        let voc = VOCData {
+            emergency: Some(true),
+            ..voc
+        };
+        assert_eq!(fees.voc(&voc), 10000.0);
+
+        for attributes in [
+            json!({"v0/gov/proposals/cfp_fee": "a quarter"}),
+            json!({"v0/gov/proposals/voc_fee": null}),
+            json!({"v0/gov/proposals/voc_emergency_fee": -1}),
+        ]
+        .iter()
+        {
+            let node = MockNode::new().respond("getgov", json!({ "ATTRIBUTES": attributes }));
+            assert!(node.get_gov_proposal_fees().await.is_err(), "{}", attributes);
+        }
+        let node = MockNode::new().respond("getgov", json!({"ATTRIBUTES": []}));
+        assert!(node.get_gov_proposal_fees().await.is_err());
+        let node = MockNode::new().respond("getgov", json!({}));
+        assert!(node.get_gov_proposal_fees().await.is_err());
     }
 }
@@ -1,17 +1,308 @@
+use async_trait::async_trait;
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::icxorderbook::*;
+
+use crate::requests::icxorderbook as req;
+use crate::{Result, RpcApi};
+
+#[async_trait]
 pub trait ICXOrderBookRPC: RpcApi {
-    fn claim_dfchtlc(
+    async fn claim_dfchtlc(
         &self,
-        dfchtlc_tx_id: String,
+        dfchtlc_tx: String,
         seed: String,
         utxos: Option<UTXO>,
     ) -> Result<ICXGenericResult>;
-    fn close_offer(&self, offer_tx: String, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
-    fn close_order(&self, order_tx: String, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
-    fn create_order(&self, order: ICXOrder, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
-    fn get_order(&self, order_tx: String) -> Result<Record>;
-    fn list_htl_cs(&self, options: ICXListHTLCOptions) -> Result<Record>;
-    fn list_orders(&self) -> Result<Record>;
-    fn make_offer(&self, offer: ICXOffer, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
-    fn submit_dfchtlc(&self, htlc: HTLC, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
-    fn submit_ext_htlc(&self, htlc: ExtHTLC, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+    async fn close_offer(&self, offer_tx: String, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+    async fn close_order(&self, order_tx: String, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+    async fn create_order(&self, order: ICXOrder, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+    async fn get_order(&self, order_tx: String) -> Result<ICXOrders>;
+    async fn list_htlcs(&self, options: ICXListHTLCOptions) -> Result<ICXHTLCs>;
+    async fn list_orders(&self, options: Option<ICXListOrderOptions>) -> Result<ICXOrders>;
+    async fn make_offer(&self, offer: ICXOffer, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+    async fn submit_dfchtlc(&self, htlc: HTLC, utxos: Option<UTXO>) -> Result<ICXGenericResult>;
+    async fn submit_ext_htlc(&self, htlc: ExtHTLC, utxos: Option<UTXO>)
+        -> Result<ICXGenericResult>;
+}
+
+#[async_trait]
+impl<T: RpcApi + Sync> ICXOrderBookRPC for T {
+    async fn claim_dfchtlc(
+        &self,
+        dfchtlc_tx: String,
+        seed: String,
+        utxos: Option<UTXO>,
+    ) -> Result<ICXGenericResult> {
+        self.request(&req::ClaimDfcHtlc {
+            dfchtlc_tx,
+            seed,
+            utxos,
+        })
+        .await
+    }
+    async fn close_offer(&self, offer_tx: String, utxos: Option<UTXO>) -> Result<ICXGenericResult> {
+        self.request(&req::CloseOffer {
+            offer_tx,
+            utxos,
+        })
+        .await
+    }
+    async fn close_order(&self, order_tx: String, utxos: Option<UTXO>) -> Result<ICXGenericResult> {
+        self.request(&req::CloseOrder {
+            order_tx,
+            utxos,
+        })
+        .await
+    }
+    async fn create_order(&self, order: ICXOrder, utxos: Option<UTXO>) -> Result<ICXGenericResult> {
+        self.request(&req::CreateOrder {
+            order,
+            utxos,
+        })
+        .await
+    }
+    async fn get_order(&self, order_tx: String) -> Result<ICXOrders> {
+        self.request(&req::GetOrder {
+            order_tx,
+        })
+        .await
+    }
+    async fn list_htlcs(&self, options: ICXListHTLCOptions) -> Result<ICXHTLCs> {
+        self.request(&req::ListHtlcs {
+            options,
+        })
+        .await
+    }
+    async fn list_orders(&self, options: Option<ICXListOrderOptions>) -> Result<ICXOrders> {
+        self.request(&req::ListOrders {
+            options,
+        })
+        .await
+    }
+    async fn make_offer(&self, offer: ICXOffer, utxos: Option<UTXO>) -> Result<ICXGenericResult> {
+        self.request(&req::MakeOffer {
+            offer,
+            utxos,
+        })
+        .await
+    }
+    async fn submit_dfchtlc(&self, htlc: HTLC, utxos: Option<UTXO>) -> Result<ICXGenericResult> {
+        self.request(&req::SubmitDfcHtlc {
+            htlc,
+            utxos,
+        })
+        .await
+    }
+    async fn submit_ext_htlc(
+        &self,
+        htlc: ExtHTLC,
+        utxos: Option<UTXO>,
+    ) -> Result<ICXGenericResult> {
+        self.request(&req::SubmitExtHtlc {
+            htlc,
+            utxos,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{from_json, MockNode};
+    use serde_json::json;
+
+    const WARNING: &str = "ICX and Atomic Swap are experimental features. You might end up \
+                           losing your funds. USE IT AT YOUR OWN RISK.";
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+        let utxos = json!({"txid": txid, "vout": 0});
+
+        let order = ICXOrder {
+            token_from: Some("BTC".into()),
+            chain_to: Some("BTC".into()),
+            owner_address: Some("addr".into()),
+            receive_pubkey: Some("pubkey".into()),
+            amount_from: 1.0,
+            order_price: 10.0,
+            ..Default::default()
+        };
+        let _ = node.create_order(order, None).await;
+        node.assert_last_request(
+            "icx_createorder",
+            json!([{
+                "tokenFrom": "BTC",
+                "chainTo": "BTC",
+                "ownerAddress": "addr",
+                "receivePubkey": "pubkey",
+                "amountFrom": 1.0,
+                "orderPrice": 10.0,
+            }, null]),
+        );
+        let offer = ICXOffer {
+            order_tx: txid.into(),
+            amount: 0.1,
+            owner_address: "addr".into(),
+            ..Default::default()
+        };
+        let _ = node.make_offer(offer, Some(from_json(utxos.clone()))).await;
+        node.assert_last_request(
+            "icx_makeoffer",
+            json!([{"orderTx": txid, "amount": 0.1, "ownerAddress": "addr"}, utxos]),
+        );
+        let htlc = HTLC {
+            offer_tx: txid.into(),
+            amount: 0.5,
+            hash: "hash".into(),
+            timeout: Some(1440),
+        };
+        let _ = node.submit_dfchtlc(htlc, None).await;
+        node.assert_last_request(
+            "icx_submitdfchtlc",
+            json!([{"offerTx": txid, "amount": 0.5, "hash": "hash", "timeout": 1440}, null]),
+        );
+        let htlc = ExtHTLC {
+            offer_tx: txid.into(),
+            amount: 0.05,
+            hash: "hash".into(),
+            htlc_script_address: "script".into(),
+            owner_pubkey: "pubkey".into(),
+            timeout: 24,
+        };
+        let _ = node.submit_ext_htlc(htlc, None).await;
+        node.assert_last_request(
+            "icx_submitexthtlc",
+            json!([{
+                "offerTx": txid,
+                "amount": 0.05,
+                "hash": "hash",
+                "htlcScriptAddress": "script",
+                "ownerPubkey": "pubkey",
+                "timeout": 24,
+            }, null]),
+        );
+        let _ = node.claim_dfchtlc(txid.into(), "seed".into(), None).await;
+        node.assert_last_request(
+            "icx_claimdfchtlc",
+            json!([{"dfchtlcTx": txid, "seed": "seed"}, null]),
+        );
+        let _ = node.close_offer(txid.into(), None).await;
+        node.assert_last_request("icx_closeoffer", json!([txid, null]));
+        let _ = node.close_order(txid.into(), None).await;
+        node.assert_last_request("icx_closeorder", json!([txid, null]));
+
+        let _ = node.get_order(txid.into()).await;
+        node.assert_last_request("icx_getorder", json!([txid]));
+        let _ = node.list_orders(None).await;
+        node.assert_last_request("icx_listorders", json!([{}]));
+        let options = ICXListOrderOptions {
+            order_tx: Some(txid.into()),
+            closed: Some(true),
+            ..Default::default()
+        };
+        let _ = node.list_orders(Some(options)).await;
+        node.assert_last_request("icx_listorders", json!([{"orderTx": txid, "closed": true}]));
+        let options = ICXListHTLCOptions {
+            offer_tx: txid.into(),
+            limit: Some(5),
+            closed: None,
+        };
+        let _ = node.list_htlcs(options).await;
+        node.assert_last_request("icx_listhtlcs", json!([{"offerTx": txid, "limit": 5}]));
+    }
+
+    #[tokio::test]
+    async fn parses_results() {
+        let node = MockNode::new()
+            .respond("icx_closeorder", json!({"WARNING": WARNING, "txid": "close"}))
+            .respond(
+                "icx_listorders",
+                json!({
+                    "WARNING": WARNING,
+                    "order": {
+                        "status": "OPEN",
+                        "type": "INTERNAL",
+                        "tokenFrom": "BTC",
+                        "chainTo": "BTC",
+                        "receivePubkey": "pubkey",
+                        "ownerAddress": "addr",
+                        "amountFrom": 1.0,
+                        "amountToFill": 1.0,
+                        "orderPrice": 10.0,
+                        "amountToFillInToAsset": 10.0,
+                        "height": 120,
+                        "expireHeight": 2999,
+                    },
+                    "offer": {
+                        "orderTx": "order",
+                        "status": "OPEN",
+                        "amount": 0.1,
+                        "amountInFromAsset": 0.01,
+                        "ownerAddress": "addr",
+                        "takerFee": 0.001,
+                        "expireHeight": 140,
+                    },
+                }),
+            )
+            .respond(
+                "icx_listhtlcs",
+                json!({
+                    "WARNING": WARNING,
+                    "dfc": {
+                        "type": "DFC",
+                        "status": "OPEN",
+                        "offerTx": "offer",
+                        "amount": 0.01,
+                        "amountInEXTAsset": 0.1,
+                        "hash": "hash",
+                        "timeout": 1440,
+                        "height": 150,
+                        "refundHeight": 1590,
+                    },
+                    "ext": {
+                        "type": "EXTERNAL",
+                        "status": "OPEN",
+                        "offerTx": "offer",
+                        "amount": 0.1,
+                        "amountInDFCAsset": 0.01,
+                        "hash": "hash",
+                        "htlcScriptAddress": "script",
+                        "ownerPubkey": "pubkey",
+                        "timeout": 24,
+                        "height": 10,
+                    },
+                    "claim": {"type": "CLAIM DFC", "dfchtlcTx": "dfc", "seed": "seed", "height": 160},
+                }),
+            );
+
+        let result = node.close_order("order".into(), None).await.unwrap();
+        assert_eq!(result.txid, "close");
+        assert_eq!(result.warning.as_deref(), Some(WARNING));
+
+        let orders = node.list_orders(None).await.unwrap();
+        assert!(orders.warning.is_some());
+        match orders.entries["order"] {
+            ICXOrderEntry::Order(ref order) => {
+                assert_eq!(order.status, ICXOrderStatus::Open);
+                assert_eq!(order.r#type, ICXOrderType::Internal);
+                assert_eq!(order.expire_height, 2999);
+            }
+            ref e => panic!("unexpected entry: {:?}", e),
+        }
+        assert!(
+            matches!(orders.entries["offer"], ICXOrderEntry::Offer(ref o) if o.order_tx == "order")
+        );
+
+        let htlcs = node.list_htlcs(from_json(json!({"offerTx": "offer"}))).await.unwrap();
+        assert_eq!(htlcs.htlcs.len(), 3);
+        assert!(matches!(htlcs.htlcs["dfc"], ICXHTLCInfo::DFC(ref h) if h.refund_height == 1590));
+        assert!(matches!(
+            htlcs.htlcs["ext"],
+            ICXHTLCInfo::External(ref h) if h.status == ICXHTLCStatus::Open
+        ));
+        assert!(matches!(htlcs.htlcs["claim"], ICXHTLCInfo::ClaimDFC(ref h) if h.seed == "seed"));
+    }
 }
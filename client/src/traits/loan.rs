@@ -1,4 +1,5 @@
-use crate::{into_json, Client, Result, RpcApi};
+use crate::requests::loan as req;
+use crate::{Result, RpcApi};
 use async_trait::async_trait;
 use defichain_rpc_json::common::{Interest, UTXO};
 use defichain_rpc_json::loan::*;
@@ -48,77 +49,124 @@ pub trait LoanRPC: RpcApi {
 }
 
 #[async_trait]
-impl LoanRPC for Client {
+impl<T: RpcApi + Sync> LoanRPC for T {
     async fn create_loan_scheme(
         &self,
         scheme: CreateLoanScheme,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("createloanscheme", &[into_json(scheme)?, into_json(utxos)?]).await
+        self.request(&req::CreateLoanScheme {
+            scheme,
+            utxos,
+        })
+        .await
     }
     async fn destroy_loan_scheme(
         &self,
         scheme: DestroyLoanScheme,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("destroyloanscheme", &[into_json(scheme)?, into_json(utxos)?]).await
+        self.request(&req::DestroyLoanScheme {
+            scheme,
+            utxos,
+        })
+        .await
     }
     async fn get_collateral_token(&self, token: String) -> Result<CollateralTokenDetail> {
-        self.call("getcollateraltoken", &[into_json(token)?]).await
+        self.request(&req::GetCollateralToken {
+            token,
+        })
+        .await
     }
     async fn get_interest(&self, id: String, token: Option<String>) -> Result<Vec<Interest>> {
-        self.call("getinterest", &[into_json(id)?, into_json(token)?]).await
+        self.request(&req::GetInterest {
+            id,
+            token,
+        })
+        .await
     }
     async fn get_loan_info(&self) -> Result<GetLoanInfoResult> {
-        self.call("getloaninfo", &[]).await
+        self.request(&req::GetLoanInfo {}).await
     }
     async fn get_loan_scheme(&self, id: String) -> Result<LoanSchemeResult> {
-        self.call("getloanscheme", &[into_json(id)?]).await
+        self.request(&req::GetLoanScheme {
+            id,
+        })
+        .await
     }
     async fn get_loan_token(&self, token: String) -> Result<LoanTokenResult> {
-        self.call("getloantoken", &[into_json(token)?]).await
+        self.request(&req::GetLoanToken {
+            token,
+        })
+        .await
     }
     async fn list_collateral_tokens(&self) -> Result<Vec<CollateralTokenDetail>> {
-        self.call("listcollateraltokens", &[]).await
+        self.request(&req::ListCollateralTokens {}).await
     }
     async fn list_loan_schemes(&self) -> Result<Vec<LoanSchemeResult>> {
-        self.call("listloanschemes", &[]).await
+        self.request(&req::ListLoanSchemes {}).await
     }
     async fn list_loan_tokens(&self) -> Result<Vec<LoanTokenResult>> {
-        self.call("listloantokens", &[]).await
+        self.request(&req::ListLoanTokens {}).await
     }
     async fn payback_loan(&self, utxos: Option<UTXO>) -> Result<String> {
-        self.call("paybackloan", &[into_json(utxos)?]).await
+        self.request(&req::PaybackLoan {
+            utxos,
+        })
+        .await
     }
     async fn payback_with_collateral(&self, vault_id: String) -> Result<String> {
-        self.call("paybackwithcollateral", &[into_json(vault_id)?]).await
+        self.request(&req::PaybackWithCollateral {
+            vault_id,
+        })
+        .await
     }
     async fn set_collateral_token(
         &self,
         collateral_token: SetCollateralToken,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("setcollateraltoken", &[into_json(collateral_token)?, into_json(utxos)?]).await
+        self.request(&req::SetCollateralToken {
+            collateral_token,
+            utxos,
+        })
+        .await
     }
     async fn set_default_loan_scheme(&self, id: String, utxos: Option<UTXO>) -> Result<String> {
-        self.call("setdefaultloanscheme", &[into_json(id)?, into_json(utxos)?]).await
+        self.request(&req::SetDefaultLoanScheme {
+            id,
+            utxos,
+        })
+        .await
     }
     async fn set_loan_token(
         &self,
         loan_token: SetLoanToken,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("setloantoken", &[into_json(loan_token)?, into_json(utxos)?]).await
+        self.request(&req::SetLoanToken {
+            loan_token,
+            utxos,
+        })
+        .await
     }
     async fn take_loan(&self, metadata: TakeLoanMetadata, utxos: Option<UTXO>) -> Result<String> {
-        self.call("takeloan", &[into_json(metadata)?, into_json(utxos)?]).await
+        self.request(&req::TakeLoan {
+            metadata,
+            utxos,
+        })
+        .await
     }
     async fn update_loan_scheme(
         &self,
         scheme: UpdateLoanScheme,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("updateloanscheme", &[into_json(scheme)?, into_json(utxos)?]).await
+        self.request(&req::UpdateLoanScheme {
+            scheme,
+            utxos,
+        })
+        .await
     }
     async fn update_loan_token(
         &self,
@@ -126,10 +174,104 @@ impl LoanRPC for Client {
         new_token_details: UpdateLoanToken,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call(
-            "updateloantoken",
-            &[into_json(old_token)?, into_json(new_token_details)?, into_json(utxos)?],
-        )
+        self.request(&req::UpdateLoanToken {
+            old_token,
+            new_token_details,
+            utxos,
+        })
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{from_json, MockNode};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+        let utxos = json!({"txid": txid, "vout": 0});
+
+        let scheme = json!({"minColRatio": 150, "interestRate": 5, "id": "C150"});
+        let _ = node.create_loan_scheme(from_json(scheme.clone()), None).await;
+        node.assert_last_request("createloanscheme", json!([scheme, null]));
+        let scheme =
+            json!({"minColRatio": 175, "interestRate": 3, "id": "C150", "activateAfterBlock": 100});
+        let _ = node
+            .update_loan_scheme(from_json(scheme.clone()), Some(from_json(utxos.clone())))
+            .await;
+        node.assert_last_request("updateloanscheme", json!([scheme, utxos]));
+        let _ = node.destroy_loan_scheme(from_json(json!({"id": "C150"})), None).await;
+        node.assert_last_request(
+            "destroyloanscheme",
+            json!([{"id": "C150", "activateAfterBlock": null}, null]),
+        );
+        let _ = node.set_default_loan_scheme("C150".into(), None).await;
+        node.assert_last_request("setdefaultloanscheme", json!(["C150", null]));
+
+        let _ = node.get_collateral_token("DFI".into()).await;
+        node.assert_last_request("getcollateraltoken", json!(["DFI"]));
+        let _ = node.get_interest("C150".into(), None).await;
+        node.assert_last_request("getinterest", json!(["C150", null]));
+        let _ = node.get_interest("C150".into(), Some("DUSD".into())).await;
+        node.assert_last_request("getinterest", json!(["C150", "DUSD"]));
+        let _ = node.get_loan_info().await;
+        node.assert_last_request("getloaninfo", json!([]));
+        let _ = node.get_loan_scheme("C150".into()).await;
+        node.assert_last_request("getloanscheme", json!(["C150"]));
+        let _ = node.get_loan_token("DUSD".into()).await;
+        node.assert_last_request("getloantoken", json!(["DUSD"]));
+        let _ = node.list_collateral_tokens().await;
+        node.assert_last_request("listcollateraltokens", json!([]));
+        let _ = node.list_loan_schemes().await;
+        node.assert_last_request("listloanschemes", json!([]));
+        let _ = node.list_loan_tokens().await;
+        node.assert_last_request("listloantokens", json!([]));
+
+        let _ = node.payback_loan(Some(from_json(utxos.clone()))).await;
+        node.assert_last_request("paybackloan", json!([utxos]));
+        let _ = node.payback_with_collateral("vault".into()).await;
+        node.assert_last_request("paybackwithcollateral", json!(["vault"]));
+
+        let collateral = json!({
+            "token": "DFI",
+            "factor": 1,
+            "fixedIntervalPriceId": "DFI/USD",
+            "activateAfterBlock": null,
+        });
+        let _ = node.set_collateral_token(from_json(collateral.clone()), None).await;
+        node.assert_last_request("setcollateraltoken", json!([collateral, null]));
+        let loan_token = json!({
+            "symbol": "DUSD",
+            "name": "Decentralized USD",
+            "fixedIntervalPriceId": "DUSD/USD",
+            "mintable": true,
+            "interest": 0,
+        });
+        let _ = node.set_loan_token(from_json(loan_token.clone()), None).await;
+        node.assert_last_request("setloantoken", json!([loan_token, null]));
+        let update = json!({
+            "symbol": null,
+            "name": null,
+            "fixedIntervalPriceId": null,
+            "mintable": false,
+            "interest": null,
+        });
+        let _ = node.update_loan_token("DUSD".into(), from_json(update.clone()), None).await;
+        node.assert_last_request("updateloantoken", json!(["DUSD", update, null]));
+
+        // A single amount is sent as a string, like the node accepts it.
+        let metadata = json!({"vaultId": "vault", "amounts": ["1@DUSD"], "to": null});
+        let _ = node.take_loan(from_json(metadata), None).await;
+        node.assert_last_request(
+            "takeloan",
+            json!([{"vaultId": "vault", "amounts": "1@DUSD", "to": null}, null]),
+        );
+        let metadata = json!({"vaultId": "vault", "amounts": ["1@DUSD", "1@TSLA"], "to": "bob"});
+        let _ = node.take_loan(from_json(metadata.clone()), None).await;
+        node.assert_last_request("takeloan", json!([metadata, null]));
+    }
+}
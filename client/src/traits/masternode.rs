@@ -1,59 +1,356 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
-// use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::masternode::*;
 
-use crate::{into_json, Client, Result, RpcApi};
+use crate::requests::masternode as req;
+use crate::{Result, RpcApi};
 
 #[async_trait]
 pub trait MasternodeRPC: RpcApi {
-    // fn clear_mempool(&self) -> Result<()>;
-    // fn create_masternode(
-    //     &self,
-    //     owner_address: String,
-    //     operator_address: Option<String>,
-    // ) -> Result<String>;
-    // fn get_active_masternode_count(&self) -> Result<u64>;
-    // fn get_anchor_teams(&self, block_height: Option<u64>) -> Result<AnchorTeamResult>;
+    async fn clear_mempool(&self) -> Result<Vec<String>>;
+    async fn create_masternode(
+        &self,
+        owner_address: String,
+        operator_address: Option<String>,
+        utxos: Option<UTXO>,
+        timelock: Option<MasternodeTimeLock>,
+    ) -> Result<String>;
+    async fn get_active_masternode_count(&self, block_count: Option<u64>) -> Result<u64>;
+    async fn get_anchor_teams(&self, block_height: Option<u64>) -> Result<AnchorTeamResult>;
     async fn get_gov(&self, name: String) -> Result<HashMap<String, serde_json::Value>>;
-    // fn get_masternode(&self, masternode_id: String) -> Result<MasternodeResult>;
-    // fn get_masternode_blocks(
-    //     &self,
-    //     identifier: MasternodeBlock,
-    //     depth: Option<u64>,
-    // ) -> Result<MasternodeResult>;
-    // fn is_applied_custom_transaction(
-    //     &self,
-    //     transaction_id: String,
-    //     block_height: u64,
-    // ) -> Result<bool>;
-    // fn list_anchors(&self) -> Result<MasternodeResult>;
-    // fn list_govs(&self) -> Result<Array>;
-    // fn list_masternodes(
-    //     &self,
-    //     pagination: Option<MasternodePagination>,
-    //     verbose: Option<bool>,
-    // ) -> Result<MasternodeResult>;
-    // fn resign_masternode(&self, masternode_id: String, utxos: Option<UTXO>) -> Result<String>;
-    // fn set_gov(&self, input: Record, utxos: Option<UTXO>) -> Result<String>;
-    // fn set_gov_height(
-    //     &self,
-    //     input: Record,
-    //     activation_height: u64,
-    //     utxos: Option<UTXO>,
-    // ) -> Result<String>;
-    // fn unset_gov(&self, variables: Record, utxos: Option<UTXO>) -> Result<String>;
-    // fn update_masternode(
-    //     &self,
-    //     masternode_id: String,
-    //     values: UpdateMasternodeValues,
-    //     utxos: Option<UTXO>,
-    // ) -> Result<String>;
+    async fn get_masternode(&self, masternode_id: String) -> Result<MasternodeResult>;
+    async fn get_masternode_blocks(
+        &self,
+        identifier: MasternodeBlock,
+        depth: Option<u64>,
+    ) -> Result<HashMap<String, String>>;
+    async fn is_applied_custom_transaction(
+        &self,
+        transaction_id: String,
+        block_height: u64,
+    ) -> Result<bool>;
+    async fn list_govs(&self, prefix: Option<String>) -> Result<Vec<GovVariable>>;
+    async fn list_masternodes(
+        &self,
+        pagination: Option<MasternodePagination>,
+        verbose: Option<bool>,
+    ) -> Result<ListMasternodesResult>;
+    async fn resign_masternode(&self, masternode_id: String, utxos: Option<UTXO>)
+        -> Result<String>;
+    async fn set_gov(
+        &self,
+        variables: HashMap<String, serde_json::Value>,
+        utxos: Option<UTXO>,
+    ) -> Result<String>;
+    async fn set_gov_height(
+        &self,
+        variables: HashMap<String, serde_json::Value>,
+        activation_height: u64,
+        utxos: Option<UTXO>,
+    ) -> Result<String>;
+    async fn unset_gov(
+        &self,
+        variables: HashMap<String, Vec<String>>,
+        utxos: Option<UTXO>,
+    ) -> Result<String>;
+    async fn update_masternode(
+        &self,
+        masternode_id: String,
+        values: UpdateMasternodeValues,
+        utxos: Option<UTXO>,
+    ) -> Result<String>;
 }
 
 #[async_trait]
-impl MasternodeRPC for Client {
+impl<T: RpcApi + Sync> MasternodeRPC for T {
+    async fn clear_mempool(&self) -> Result<Vec<String>> {
+        self.request(&req::ClearMempool {}).await
+    }
+    async fn create_masternode(
+        &self,
+        owner_address: String,
+        operator_address: Option<String>,
+        utxos: Option<UTXO>,
+        timelock: Option<MasternodeTimeLock>,
+    ) -> Result<String> {
+        self.request(&req::CreateMasternode {
+            owner_address,
+            operator_address,
+            utxos,
+            timelock,
+        })
+        .await
+    }
+    async fn get_active_masternode_count(&self, block_count: Option<u64>) -> Result<u64> {
+        self.request(&req::GetActiveMasternodeCount {
+            block_count,
+        })
+        .await
+    }
+    async fn get_anchor_teams(&self, block_height: Option<u64>) -> Result<AnchorTeamResult> {
+        self.request(&req::GetAnchorTeams {
+            block_height,
+        })
+        .await
+    }
     async fn get_gov(&self, id: String) -> Result<HashMap<String, serde_json::Value>> {
-        self.call("getgov", &[into_json(id)?]).await
+        self.request(&req::GetGov {
+            id,
+        })
+        .await
+    }
+    async fn get_masternode(&self, masternode_id: String) -> Result<MasternodeResult> {
+        self.request(&req::GetMasternode {
+            masternode_id,
+        })
+        .await
+    }
+    async fn get_masternode_blocks(
+        &self,
+        identifier: MasternodeBlock,
+        depth: Option<u64>,
+    ) -> Result<HashMap<String, String>> {
+        self.request(&req::GetMasternodeBlocks {
+            identifier,
+            depth,
+        })
+        .await
+    }
+    async fn is_applied_custom_transaction(
+        &self,
+        transaction_id: String,
+        block_height: u64,
+    ) -> Result<bool> {
+        self.request(&req::IsAppliedCustomTx {
+            txid: transaction_id,
+            block_height,
+        })
+        .await
+    }
+    async fn list_govs(&self, prefix: Option<String>) -> Result<Vec<GovVariable>> {
+        self.request(&req::ListGovs {
+            prefix,
+        })
+        .await
+    }
+    async fn list_masternodes(
+        &self,
+        pagination: Option<MasternodePagination>,
+        verbose: Option<bool>,
+    ) -> Result<ListMasternodesResult> {
+        self.request(&req::ListMasternodes {
+            pagination,
+            verbose,
+        })
+        .await
+    }
+    async fn resign_masternode(
+        &self,
+        masternode_id: String,
+        utxos: Option<UTXO>,
+    ) -> Result<String> {
+        self.request(&req::ResignMasternode {
+            masternode_id,
+            utxos,
+        })
+        .await
+    }
+    async fn set_gov(
+        &self,
+        variables: HashMap<String, serde_json::Value>,
+        utxos: Option<UTXO>,
+    ) -> Result<String> {
+        self.request(&req::SetGov {
+            variables,
+            utxos,
+        })
+        .await
+    }
+    async fn set_gov_height(
+        &self,
+        variables: HashMap<String, serde_json::Value>,
+        activation_height: u64,
+        utxos: Option<UTXO>,
+    ) -> Result<String> {
+        self.request(&req::SetGovHeight {
+            variables,
+            activation_height,
+            utxos,
+        })
+        .await
+    }
+    async fn unset_gov(
+        &self,
+        variables: HashMap<String, Vec<String>>,
+        utxos: Option<UTXO>,
+    ) -> Result<String> {
+        self.request(&req::UnsetGov {
+            variables,
+            utxos,
+        })
+        .await
+    }
+    async fn update_masternode(
+        &self,
+        masternode_id: String,
+        values: UpdateMasternodeValues,
+        utxos: Option<UTXO>,
+    ) -> Result<String> {
+        self.request(&req::UpdateMasternode {
+            masternode_id,
+            values,
+            utxos,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{from_json, MockNode};
+    use crate::Error;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let _ = node.get_gov("ATTRIBUTES".into()).await;
+        node.assert_last_request("getgov", json!(["ATTRIBUTES"]));
+
+        let mn_id = "e86c027861cc0af423313f4152a44a83296a388eb51bf1a6dde9bd75bed55fb4";
+        let utxos = json!({"txid": mn_id, "vout": 1});
+        let _ = node.create_masternode("owner".into(), None, None, None).await;
+        node.assert_last_request("createmasternode", json!(["owner", null, null, null]));
+        let timelock = Some(MasternodeTimeLock::TenYears);
+        let _ =
+            node.create_masternode("owner".into(), Some("operator".into()), None, timelock).await;
+        node.assert_last_request(
+            "createmasternode",
+            json!(["owner", "operator", null, "TENYEARTIMELOCK"]),
+        );
+        let _ = node.resign_masternode(mn_id.into(), Some(from_json(utxos.clone()))).await;
+        node.assert_last_request("resignmasternode", json!([mn_id, utxos]));
+        let values = UpdateMasternodeValues {
+            reward_address: Some("".into()),
+            ..Default::default()
+        };
+        let _ = node.update_masternode(mn_id.into(), values, None).await;
+        node.assert_last_request("updatemasternode", json!([mn_id, {"rewardAddress": ""}, null]));
+        let err = node.update_masternode(mn_id.into(), Default::default(), None).await.unwrap_err();
+        assert!(matches!(err, Error::Custom(_)));
+
+        let _ = node.get_masternode(mn_id.into()).await;
+        node.assert_last_request("getmasternode", json!([mn_id]));
+        let _ = node.list_masternodes(None, None).await;
+        node.assert_last_request("listmasternodes", json!([{}, null]));
+        let pagination = MasternodePagination {
+            start: Some(mn_id.into()),
+            including_start: Some(false),
+            limit: Some(10),
+        };
+        let _ = node.list_masternodes(Some(pagination), Some(false)).await;
+        node.assert_last_request(
+            "listmasternodes",
+            json!([{"start": mn_id, "including_start": false, "limit": 10}, false]),
+        );
+        let identifier = MasternodeBlock {
+            operator_address: Some("operator".into()),
+            ..Default::default()
+        };
+        let _ = node.get_masternode_blocks(identifier, Some(10)).await;
+        node.assert_last_request(
+            "getmasternodeblocks",
+            json!([{"operatorAddress": "operator"}, 10]),
+        );
+        let err = node.get_masternode_blocks(Default::default(), None).await.unwrap_err();
+        assert!(matches!(err, Error::Custom(_)));
+        let _ = node.get_active_masternode_count(None).await;
+        node.assert_last_request("getactivemasternodecount", json!([null]));
+        let _ = node.get_anchor_teams(Some(100)).await;
+        node.assert_last_request("getanchorteams", json!([100]));
+
+        let variables =
+            vec![("LP_DAILY_DFI_REWARD".to_string(), json!(35.5))].into_iter().collect();
+        let _ = node.set_gov(variables, None).await;
+        node.assert_last_request("setgov", json!([{"LP_DAILY_DFI_REWARD": 35.5}, null]));
+        let variables =
+            vec![("ORACLE_BLOCK_INTERVAL".to_string(), json!(30))].into_iter().collect();
+        let _ = node.set_gov_height(variables, 200, None).await;
+        node.assert_last_request("setgovheight", json!([{"ORACLE_BLOCK_INTERVAL": 30}, 200, null]));
+        let keys = vec!["v0/token/1/fixed_interval_price_id".to_string()];
+        let variables = vec![("ATTRIBUTES".to_string(), keys)].into_iter().collect();
+        let _ = node.unset_gov(variables, None).await;
+        node.assert_last_request(
+            "unsetgov",
+            json!([{"ATTRIBUTES": ["v0/token/1/fixed_interval_price_id"]}, null]),
+        );
+        let _ = node.list_govs(Some("gov".into())).await;
+        node.assert_last_request("listgovs", json!(["gov"]));
+        let _ = node.clear_mempool().await;
+        node.assert_last_request("clearmempool", json!([]));
+        let _ = node.is_applied_custom_transaction(mn_id.into(), 100).await;
+        node.assert_last_request("isappliedcustomtx", json!([mn_id, 100]));
+    }
+
+    #[tokio::test]
+    async fn parses_results() {
+        let mn = json!({
+            "ownerAuthAddress": "owner",
+            "operatorAuthAddress": "operator",
+            "rewardAddress": "",
+            "creationHeight": 120,
+            "resignHeight": -1,
+            "resignTx": "0000000000000000000000000000000000000000000000000000000000000000",
+            "collateralTx": "0000000000000000000000000000000000000000000000000000000000000000",
+            "state": "ENABLED",
+            "mintedBlocks": 0,
+            "ownerIsMine": true,
+            "operatorIsMine": true,
+            "localMasternode": false,
+            "targetMultipliers": [1, 1, 1, 1],
+            "timelock": "10 years",
+        });
+        let node = MockNode::new()
+            .respond("getmasternode", json!({"mn": mn.clone()}))
+            .on("listmasternodes", move |params, _| match params[1].as_bool() {
+                Some(false) => Ok(json!({"mn": "PRE_RESIGNED", "other": "BANNED"})),
+                _ => Ok(json!({"mn": mn.clone()})),
+            })
+            .respond("getanchorteams", json!({"auth": ["a"], "confirm": ["b", "c"]}))
+            .respond(
+                "listgovs",
+                json!([
+                    [{"ATTRIBUTES": {}}, {"1200": {"v0/params/dfip2203/active": "true"}}],
+                    [{"ORACLE_BLOCK_INTERVAL": 120}],
+                ]),
+            )
+            .respond("isappliedcustomtx", json!(true));
+
+        let result = node.get_masternode("mn".into()).await.unwrap();
+        assert_eq!(result.0["mn"].state, MasternodeState::Enabled);
+        assert_eq!(result.0["mn"].resign_height, -1);
+        assert_eq!(result.0["mn"].timelock.as_deref(), Some("10 years"));
+        match node.list_masternodes(None, None).await.unwrap() {
+            ListMasternodesResult::Verbose(mns) => assert!(mns.0["mn"].owner_is_mine),
+            r => panic!("unexpected result: {:?}", r),
+        }
+        match node.list_masternodes(None, Some(false)).await.unwrap() {
+            ListMasternodesResult::States(states) => {
+                assert_eq!(states["mn"], MasternodeState::PreResigned);
+                assert_eq!(states["other"], MasternodeState::Unknown);
+            }
+            r => panic!("unexpected result: {:?}", r),
+        }
+        let teams = node.get_anchor_teams(None).await.unwrap();
+        assert_eq!(teams.confirm.len(), 2);
+        let govs = node.list_govs(None).await.unwrap();
+        assert_eq!(govs[0].name, "ATTRIBUTES");
+        assert_eq!(govs[0].pending[&1200]["v0/params/dfip2203/active"], "true");
+        assert_eq!((govs[1].name.as_str(), &govs[1].value), ("ORACLE_BLOCK_INTERVAL", &json!(120)));
+        assert!(govs[1].pending.is_empty());
+        assert!(node.is_applied_custom_transaction("txid".into(), 100).await.unwrap());
     }
 }
@@ -2,17 +2,17 @@ mod account;
 mod blockchain;
 // mod evm;
 mod governance;
-// mod icxorderbook;
+mod icxorderbook;
 mod loan;
 mod masternode;
 // mod mining;
 // mod misc;
 // mod net;
-// mod oracle;
+mod oracle;
 mod poolpair;
 // mod rawtx;
 // mod server;
-// mod spv;
+mod spv;
 mod token;
 mod vault;
 // mod wallet;
@@ -20,8 +20,11 @@ mod vault;
 pub use account::AccountRPC;
 pub use blockchain::BlockchainRPC;
 pub use governance::GovernanceRPC;
+pub use icxorderbook::ICXOrderBookRPC;
 pub use loan::LoanRPC;
 pub use masternode::MasternodeRPC;
+pub use oracle::OracleRPC;
 pub use poolpair::PoolPairRPC;
+pub use spv::SpvRPC;
 pub use token::TokenRPC;
 pub use vault::VaultRPC;
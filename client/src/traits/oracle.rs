@@ -1,22 +1,305 @@
+use async_trait::async_trait;
+use defichain_rpc_json::common::UTXO;
+use defichain_rpc_json::oracle::*;
+
+use crate::requests::oracle as req;
+use crate::{Result, RpcApi};
+
+#[async_trait]
 pub trait OracleRPC: RpcApi {
-    fn appoint_oracle(&self, address: String) -> Result<String>;
-    fn get_fixed_interval_price(&self, id: String) -> Result<FixedIntervalPrice>;
-    fn get_future_swap_block(&self) -> Result<u64>;
-    fn get_oracle_data(&self, oracle_id: String) -> Result<OracleData>;
-    fn get_price(&self, price_feed: OraclePriceFeed) -> Result<BigNumber>;
-    fn list_fixed_interval_prices(&self) -> Result<Vec<ListFixedIntervalPrice>>;
-    fn list_latest_raw_prices(
+    async fn appoint_oracle(
+        &self,
+        address: String,
+        price_feeds: Vec<OraclePriceFeed>,
+        weightage: u8,
+        utxos: Option<UTXO>,
+    ) -> Result<String>;
+    async fn get_fixed_interval_price(&self, id: String) -> Result<FixedIntervalPrice>;
+    async fn get_future_swap_block(&self) -> Result<u64>;
+    async fn get_oracle_data(&self, oracle_id: String) -> Result<OracleData>;
+    async fn get_price(&self, price_feed: OraclePriceFeed) -> Result<f64>;
+    async fn list_fixed_interval_prices(
+        &self,
+        pagination: Option<FixedIntervalPricePagination>,
+    ) -> Result<Vec<ListFixedIntervalPrice>>;
+    async fn list_latest_raw_prices(
         &self,
         price_feed: Option<OraclePriceFeed>,
+        pagination: Option<OraclePagination>,
     ) -> Result<Vec<OracleRawPrice>>;
-    fn list_oracles(&self) -> Result<()>;
-    fn list_prices(
+    async fn list_oracles(&self, pagination: Option<OraclePagination>) -> Result<Vec<String>>;
+    async fn list_prices(
         &self,
-        start_index: Option<u64>,
-        including_start: Option<bool>,
-        limit: Option<u64>,
+        pagination: Option<ListPricesPagination>,
     ) -> Result<Vec<ListPricesData>>;
-    fn remove_oracle(&self, oracle_id: String, utxos: Option<UTXO>) -> Result<String>;
-    fn set_oracle_data(&self, oracle_id: String, timestamp: u64) -> Result<String>;
-    fn update_oracle(&self, oracle_id: String, address: String) -> Result<String>;
+    async fn remove_oracle(&self, oracle_id: String, utxos: Option<UTXO>) -> Result<String>;
+    async fn set_oracle_data(
+        &self,
+        oracle_id: String,
+        timestamp: u64,
+        prices: Vec<OraclePrice>,
+        utxos: Option<UTXO>,
+    ) -> Result<String>;
+    async fn update_oracle(
+        &self,
+        oracle_id: String,
+        address: String,
+        price_feeds: Vec<OraclePriceFeed>,
+        weightage: u8,
+        utxos: Option<UTXO>,
+    ) -> Result<String>;
+}
+
+#[async_trait]
+impl<T: RpcApi + Sync> OracleRPC for T {
+    async fn appoint_oracle(
+        &self,
+        address: String,
+        price_feeds: Vec<OraclePriceFeed>,
+        weightage: u8,
+        utxos: Option<UTXO>,
+    ) -> Result<String> {
+        self.request(&req::AppointOracle {
+            address,
+            price_feeds,
+            weightage,
+            utxos,
+        })
+        .await
+    }
+    async fn get_fixed_interval_price(&self, id: String) -> Result<FixedIntervalPrice> {
+        self.request(&req::GetFixedIntervalPrice {
+            id,
+        })
+        .await
+    }
+    async fn get_future_swap_block(&self) -> Result<u64> {
+        self.request(&req::GetFutureSwapBlock {}).await
+    }
+    async fn get_oracle_data(&self, oracle_id: String) -> Result<OracleData> {
+        self.request(&req::GetOracleData {
+            oracle_id,
+        })
+        .await
+    }
+    async fn get_price(&self, price_feed: OraclePriceFeed) -> Result<f64> {
+        self.request(&req::GetPrice {
+            price_feed,
+        })
+        .await
+    }
+    async fn list_fixed_interval_prices(
+        &self,
+        pagination: Option<FixedIntervalPricePagination>,
+    ) -> Result<Vec<ListFixedIntervalPrice>> {
+        self.request(&req::ListFixedIntervalPrices {
+            pagination,
+        })
+        .await
+    }
+    async fn list_latest_raw_prices(
+        &self,
+        price_feed: Option<OraclePriceFeed>,
+        pagination: Option<OraclePagination>,
+    ) -> Result<Vec<OracleRawPrice>> {
+        self.request(&req::ListLatestRawPrices {
+            price_feed,
+            pagination,
+        })
+        .await
+    }
+    async fn list_oracles(&self, pagination: Option<OraclePagination>) -> Result<Vec<String>> {
+        self.request(&req::ListOracles {
+            pagination,
+        })
+        .await
+    }
+    async fn list_prices(
+        &self,
+        pagination: Option<ListPricesPagination>,
+    ) -> Result<Vec<ListPricesData>> {
+        self.request(&req::ListPrices {
+            pagination,
+        })
+        .await
+    }
+    async fn remove_oracle(&self, oracle_id: String, utxos: Option<UTXO>) -> Result<String> {
+        self.request(&req::RemoveOracle {
+            oracle_id,
+            utxos,
+        })
+        .await
+    }
+    async fn set_oracle_data(
+        &self,
+        oracle_id: String,
+        timestamp: u64,
+        prices: Vec<OraclePrice>,
+        utxos: Option<UTXO>,
+    ) -> Result<String> {
+        self.request(&req::SetOracleData {
+            oracle_id,
+            timestamp,
+            prices,
+            utxos,
+        })
+        .await
+    }
+    async fn update_oracle(
+        &self,
+        oracle_id: String,
+        address: String,
+        price_feeds: Vec<OraclePriceFeed>,
+        weightage: u8,
+        utxos: Option<UTXO>,
+    ) -> Result<String> {
+        self.request(&req::UpdateOracle {
+            oracle_id,
+            address,
+            price_feeds,
+            weightage,
+            utxos,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{from_json, MockNode};
+    use crate::Error;
+    use serde_json::json;
+
+    fn feed(token: &str) -> OraclePriceFeed {
+        OraclePriceFeed {
+            token: token.into(),
+            currency: "USD".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+        let utxos = json!({"txid": txid, "vout": 0});
+        let feeds = json!([{"token": "TSLA", "currency": "USD"}]);
+
+        let _ = node.appoint_oracle("addr".into(), vec![feed("TSLA")], 10, None).await;
+        node.assert_last_request("appointoracle", json!(["addr", feeds, 10, null]));
+        let _ = node.update_oracle(txid.into(), "addr".into(), vec![feed("TSLA")], 20, None).await;
+        node.assert_last_request("updateoracle", json!([txid, "addr", feeds, 20, null]));
+        let _ = node.remove_oracle(txid.into(), Some(from_json(utxos.clone()))).await;
+        node.assert_last_request("removeoracle", json!([txid, utxos]));
+        let price = OraclePrice {
+            token_amount: "100@TSLA".into(),
+            currency: "USD".into(),
+        };
+        let _ = node.set_oracle_data(txid.into(), 1_650_000_000, vec![price], None).await;
+        node.assert_last_request(
+            "setoracledata",
+            json!([txid, 1_650_000_000, [{"tokenAmount": "100@TSLA", "currency": "USD"}], null]),
+        );
+
+        let _ = node.get_oracle_data(txid.into()).await;
+        node.assert_last_request("getoracledata", json!([txid]));
+        let _ = node.get_price(feed("TSLA")).await;
+        node.assert_last_request("getprice", json!([{"token": "TSLA", "currency": "USD"}]));
+        let _ = node.get_fixed_interval_price("TSLA/USD".into()).await;
+        node.assert_last_request("getfixedintervalprice", json!(["TSLA/USD"]));
+        let _ = node.get_future_swap_block().await;
+        node.assert_last_request("getfutureswapblock", json!([]));
+
+        let _ = node.list_oracles(None).await;
+        node.assert_last_request("listoracles", json!([{}]));
+        let pagination = OraclePagination {
+            start: Some(txid.into()),
+            limit: Some(10),
+            ..Default::default()
+        };
+        let _ = node.list_latest_raw_prices(None, Some(pagination)).await;
+        node.assert_last_request(
+            "listlatestrawprices",
+            json!([null, {"start": txid, "limit": 10}]),
+        );
+        let _ = node.list_latest_raw_prices(Some(feed("TSLA")), None).await;
+        node.assert_last_request(
+            "listlatestrawprices",
+            json!([{"token": "TSLA", "currency": "USD"}, {}]),
+        );
+        let pagination = ListPricesPagination {
+            start: Some(2),
+            including_start: Some(true),
+            limit: None,
+        };
+        let _ = node.list_prices(Some(pagination)).await;
+        node.assert_last_request("listprices", json!([{"start": 2, "including_start": true}]));
+        let _ = node.list_fixed_interval_prices(None).await;
+        node.assert_last_request("listfixedintervalprices", json!([{}]));
+
+        // Invalid weightages are rejected before anything is sent.
+        let err = node.appoint_oracle("other".into(), vec![], 101, None).await.unwrap_err();
+        assert!(matches!(err, Error::Custom(_)));
+        node.assert_last_request("listfixedintervalprices", json!([{}]));
+    }
+
+    #[tokio::test]
+    async fn parses_results() {
+        let node = MockNode::new()
+            .respond(
+                "getoracledata",
+                json!({
+                    "weightage": 10,
+                    "oracleid": "oracle",
+                    "address": "addr",
+                    "priceFeeds": [{"token": "TSLA", "currency": "USD"}],
+                    "tokenPrices": [
+                        {"token": "TSLA", "currency": "USD", "amount": 1.5, "timestamp": 1650000000},
+                    ],
+                }),
+            )
+            .respond(
+                "listlatestrawprices",
+                json!([{
+                    "priceFeeds": {"token": "TSLA", "currency": "USD"},
+                    "oracleid": "oracle",
+                    "weightage": 10,
+                    "timestamp": 1650000000,
+                    "rawprice": 1.5,
+                    "state": "expired",
+                }]),
+            )
+            .respond(
+                "listprices",
+                json!([
+                    {"token": "TSLA", "currency": "USD", "price": 1.5, "ok": true},
+                    {"token": "AAPL", "currency": "USD", "ok": "no live oracles for specified request"},
+                ]),
+            )
+            .respond(
+                "getfixedintervalprice",
+                json!({
+                    "fixedIntervalPriceId": "TSLA/USD",
+                    "activePrice": 1.5,
+                    "nextPrice": 1.6,
+                    "activePriceBlock": 120,
+                    "nextPriceBlock": 126,
+                    "timestamp": 1650000000,
+                    "isLive": true,
+                }),
+            );
+
+        let data = node.get_oracle_data("oracle".into()).await.unwrap();
+        assert_eq!(data.price_feeds, vec![feed("TSLA")]);
+        assert_eq!(data.token_prices[0].amount, 1.5);
+        let prices = node.list_latest_raw_prices(None, None).await.unwrap();
+        assert_eq!(prices[0].state, OracleRawPriceState::Expired);
+        let prices = node.list_prices(None).await.unwrap();
+        assert_eq!(prices[0].ok, PriceStatus::Valid(true));
+        assert_eq!(prices[1].price, None);
+        assert!(matches!(prices[1].ok, PriceStatus::Invalid(_)));
+        let price = node.get_fixed_interval_price("TSLA/USD".into()).await.unwrap();
+        assert!(price.is_live);
+        assert_eq!(price.next_price_block, 126);
+    }
 }
@@ -2,7 +2,8 @@ use async_trait::async_trait;
 use defichain_rpc_json::common::UTXO;
 use defichain_rpc_json::poolpair::*;
 
-use crate::{into_json, obj_into_json, Client, Result, RpcApi};
+use crate::requests::poolpair as req;
+use crate::{Result, RpcApi};
 
 #[async_trait]
 pub trait PoolPairRPC: RpcApi {
@@ -51,17 +52,18 @@ pub trait PoolPairRPC: RpcApi {
 }
 
 #[async_trait]
-impl PoolPairRPC for Client {
+impl<T: RpcApi + Sync> PoolPairRPC for T {
     async fn add_pool_liquidity(
         &self,
         from: AddPoolLiquiditySource,
         share_address: String,
         options: PoolLiquidityOptions,
     ) -> Result<String> {
-        self.call(
-            "addpoolliquidity",
-            &[into_json(from)?, into_json(share_address)?, into_json(options)?],
-        )
+        self.request(&req::AddPoolLiquidity {
+            from,
+            share_address,
+            options,
+        })
         .await
     }
     async fn composite_swap(
@@ -69,28 +71,44 @@ impl PoolPairRPC for Client {
         metadata: PoolSwapMetadata,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("compositeswap", &[into_json(metadata)?, into_json(utxos)?]).await
+        self.request(&req::CompositeSwap {
+            metadata,
+            utxos,
+        })
+        .await
     }
     async fn create_pool_pair(
         &self,
         metadata: CreatePoolPairMetadata,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("createpoolpair", &[into_json(metadata)?, into_json(utxos)?]).await
+        self.request(&req::CreatePoolPair {
+            metadata,
+            utxos,
+        })
+        .await
     }
     async fn get_pool_pair(
         &self,
         symbol: String,
         verbose: Option<bool>,
     ) -> Result<PoolPairsResult> {
-        self.call("getpoolpair", &[into_json(symbol)?, into_json(verbose)?]).await
+        self.request(&req::GetPoolPair {
+            symbol,
+            verbose,
+        })
+        .await
     }
     async fn list_pool_pairs(
         &self,
         pagination: Option<PoolPairPagination>,
         verbose: Option<bool>,
     ) -> Result<PoolPairsResult> {
-        self.call("listpoolpairs", &[obj_into_json(pagination)?, into_json(verbose)?]).await
+        self.request(&req::ListPoolPairs {
+            pagination,
+            verbose,
+        })
+        .await
     }
     async fn list_pool_shares(
         &self,
@@ -98,14 +116,19 @@ impl PoolPairRPC for Client {
         verbose: Option<bool>,
         options: PoolShareOptions,
     ) -> Result<PoolSharesResult> {
-        self.call(
-            "listpoolshares",
-            &[into_json(pagination)?, into_json(verbose)?, into_json(options)?],
-        )
+        self.request(&req::ListPoolShares {
+            pagination,
+            verbose,
+            options,
+        })
         .await
     }
     async fn pool_swap(&self, metadata: PoolSwapMetadata, utxos: Option<UTXO>) -> Result<String> {
-        self.call("poolswap", &[into_json(metadata)?, into_json(utxos)?]).await
+        self.request(&req::PoolSwap {
+            metadata,
+            utxos,
+        })
+        .await
     }
     async fn remove_pool_liquidity(
         &self,
@@ -113,20 +136,116 @@ impl PoolPairRPC for Client {
         pool_account: String,
         options: PoolLiquidityOptions,
     ) -> Result<String> {
-        self.call(
-            "removepoolliquidity",
-            &[into_json(address)?, into_json(pool_account)?, into_json(options)?],
-        )
+        self.request(&req::RemovePoolLiquidity {
+            address,
+            pool_account,
+            options,
+        })
         .await
     }
     async fn test_pool_swap(&self, metadata: PoolSwapMetadata) -> Result<String> {
-        self.call("testpoolswap", &[into_json(metadata)?]).await
+        self.request(&req::TestPoolSwap {
+            metadata,
+        })
+        .await
     }
     async fn update_pool_pair(
         &self,
         metadata: UpdatePoolPairMetadata,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("updatepoolpair", &[into_json(metadata)?, into_json(utxos)?]).await
+        self.request(&req::UpdatePoolPair {
+            metadata,
+            utxos,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{from_json, MockNode};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+        let utxos = json!({"txid": txid, "vout": 0});
+        let options = json!({"utxos": [utxos]});
+
+        let from = json!({"alice": ["1@DFI", "1@BTC"]});
+        let _ = node
+            .add_pool_liquidity(from_json(from.clone()), "alice".into(), from_json(json!({})))
+            .await;
+        node.assert_last_request("addpoolliquidity", json!([from, "alice", {"utxos": null}]));
+        let _ = node
+            .remove_pool_liquidity("alice".into(), "1@BTC-DFI".into(), from_json(options.clone()))
+            .await;
+        node.assert_last_request("removepoolliquidity", json!(["alice", "1@BTC-DFI", options]));
+
+        let swap = json!({
+            "from": "alice",
+            "tokenFrom": "DFI",
+            "amountFrom": 1,
+            "to": "bob",
+            "tokenTo": "BTC",
+            "maxPrice": null,
+        });
+        let _ = node.pool_swap(from_json(swap.clone()), None).await;
+        node.assert_last_request("poolswap", json!([swap, null]));
+        let _ = node.composite_swap(from_json(swap.clone()), Some(from_json(utxos.clone()))).await;
+        node.assert_last_request("compositeswap", json!([swap, utxos]));
+        let _ = node.test_pool_swap(from_json(swap.clone())).await;
+        node.assert_last_request("testpoolswap", json!([swap]));
+
+        let metadata = json!({
+            "tokenA": "BTC",
+            "tokenB": "DFI",
+            "commission": 0,
+            "status": true,
+            "ownerAddress": "alice",
+            "customRewards": null,
+            "pairSymbol": "BTC-DFI",
+        });
+        let _ = node.create_pool_pair(from_json(metadata.clone()), None).await;
+        node.assert_last_request("createpoolpair", json!([metadata, null]));
+        let metadata = json!({
+            "pool": "BTC-DFI",
+            "status": false,
+            "commission": 0,
+            "ownerAddress": "alice",
+            "customRewards": ["1@DFI"],
+        });
+        let _ = node.update_pool_pair(from_json(metadata.clone()), None).await;
+        node.assert_last_request("updatepoolpair", json!([metadata, null]));
+
+        let _ = node.get_pool_pair("BTC-DFI".into(), None).await;
+        node.assert_last_request("getpoolpair", json!(["BTC-DFI", null]));
+        let _ = node.list_pool_pairs(None, Some(false)).await;
+        node.assert_last_request("listpoolpairs", json!([{}, false]));
+        let pagination = PoolPairPagination {
+            start: 2,
+            including_start: true,
+            limit: 100,
+        };
+        let _ = node.list_pool_pairs(Some(pagination), None).await;
+        node.assert_last_request(
+            "listpoolpairs",
+            json!([{"start": 2, "including_start": true, "limit": 100}, null]),
+        );
+        let pagination = PoolPairPagination {
+            start: 0,
+            including_start: false,
+            limit: 10,
+        };
+        let _ = node
+            .list_pool_shares(pagination, Some(true), from_json(json!({"isMineOnly": true})))
+            .await;
+        node.assert_last_request(
+            "listpoolshares",
+            json!([{"start": 0, "including_start": false, "limit": 10}, true, {"isMineOnly": true}]),
+        );
     }
 }
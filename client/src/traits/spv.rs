@@ -1,41 +1,401 @@
+use async_trait::async_trait;
+use defichain_rpc_json::spv::*;
+
+use crate::requests::spv as req;
+use crate::{Result, RpcApi};
+
+#[async_trait]
 pub trait SpvRPC: RpcApi {
-    fn claim_htlc(
+    async fn claim_htlc(
         &self,
         script_address: String,
         destination_address: String,
         options: ClaimHtlcOptions,
     ) -> Result<SendMessageResult>;
-    fn create_anchor(&self, reward_address: String) -> Result<CreateAnchorResult>;
-    fn create_htlc(
+    async fn create_anchor(
+        &self,
+        inputs: Vec<CreateAnchorInput>,
+        reward_address: String,
+        options: Option<CreateAnchorOptions>,
+    ) -> Result<CreateAnchorResult>;
+    async fn create_htlc(
         &self,
         receiver_pub_key: String,
         owner_pub_key: String,
         options: CreateHtlcOptions,
     ) -> Result<CreateHtlcResult>;
-    fn decode_htlc_script(&self, redeem_script: String) -> Result<DecodeHtlcResult>;
-    fn get_address_pub_key(&self, address: String) -> Result<String>;
-    fn get_htlc_seed(&self, address: String) -> Result<String>;
-    fn get_new_address(&self) -> Result<String>;
-    fn list_anchor_auths(&self) -> Result<Vec<ListAnchorAuthsResult>>;
-    fn list_anchor_reward_confirms(&self) -> Result<Vec<ListAnchorRewardConfirmsResult>>;
-    fn list_anchor_rewards(&self) -> Result<Vec<ListAnchorRewardsResult>>;
-    fn list_anchors(&self) -> Result<Vec<ListAnchorsResult>>;
-    fn list_anchors_pending(&self) -> Result<Vec<ListAnchorsResult>>;
-    fn list_anchors_unrewarded(&self) -> Result<Vec<ListAnchorsResult>>;
-    fn list_htlc_outputs(
+    async fn decode_htlc_script(&self, redeem_script: String) -> Result<DecodeHtlcResult>;
+    async fn get_address_pub_key(&self, address: String) -> Result<String>;
+    async fn get_htlc_seed(&self, address: String) -> Result<String>;
+    async fn get_new_address(&self) -> Result<String>;
+    async fn list_anchor_auths(&self) -> Result<Vec<ListAnchorAuthsResult>>;
+    async fn list_anchor_reward_confirms(&self) -> Result<Vec<ListAnchorRewardConfirmsResult>>;
+    async fn list_anchor_rewards(&self) -> Result<Vec<ListAnchorRewardsResult>>;
+    async fn list_anchors(
+        &self,
+        options: Option<ListAnchorsOptions>,
+    ) -> Result<Vec<ListAnchorsResult>>;
+    async fn list_anchors_pending(&self) -> Result<Vec<ListAnchorsResult>>;
+    async fn list_anchors_unrewarded(&self) -> Result<Vec<ListAnchorsResult>>;
+    async fn list_htlc_outputs(
         &self,
         script_address: Option<String>,
     ) -> Result<Vec<ListHtlcsOutputsResult>>;
-    fn list_received_by_address(
+    async fn list_received_by_address(
         &self,
+        min_confirmations: Option<u32>,
         address: Option<String>,
     ) -> Result<Vec<ReceivedByAddressInfo>>;
-    fn refund_htlc(
+    async fn refund_htlc(
         &self,
         script_address: String,
         destination_address: String,
+        fee_rate: Option<u64>,
     ) -> Result<SendMessageResult>;
-    fn refund_htlc_all(&self, destination_address: String) -> Result<()>;
-    fn send_to_address(&self, address: String, amount: BigNumber) -> Result<SendMessageResult>;
-    fn set_last_height(&self, height: u64) -> Result<()>;
+    async fn refund_htlc_all(
+        &self,
+        destination_address: String,
+        fee_rate: Option<u64>,
+    ) -> Result<Vec<String>>;
+    async fn send_to_address(
+        &self,
+        address: String,
+        amount: f64,
+        fee_rate: Option<u64>,
+    ) -> Result<SendMessageResult>;
+    async fn set_last_height(&self, height: u32) -> Result<()>;
+}
+
+#[async_trait]
+impl<T: RpcApi + Sync> SpvRPC for T {
+    async fn claim_htlc(
+        &self,
+        script_address: String,
+        destination_address: String,
+        options: ClaimHtlcOptions,
+    ) -> Result<SendMessageResult> {
+        self.request(&req::ClaimHtlc {
+            script_address,
+            destination_address,
+            options,
+        })
+        .await
+    }
+    async fn create_anchor(
+        &self,
+        inputs: Vec<CreateAnchorInput>,
+        reward_address: String,
+        options: Option<CreateAnchorOptions>,
+    ) -> Result<CreateAnchorResult> {
+        self.request(&req::CreateAnchor {
+            inputs,
+            reward_address,
+            options,
+        })
+        .await
+    }
+    async fn create_htlc(
+        &self,
+        receiver_pub_key: String,
+        owner_pub_key: String,
+        options: CreateHtlcOptions,
+    ) -> Result<CreateHtlcResult> {
+        self.request(&req::CreateHtlc {
+            receiver_pub_key,
+            owner_pub_key,
+            options,
+        })
+        .await
+    }
+    async fn decode_htlc_script(&self, redeem_script: String) -> Result<DecodeHtlcResult> {
+        self.request(&req::DecodeHtlcScript {
+            redeem_script,
+        })
+        .await
+    }
+    async fn get_address_pub_key(&self, address: String) -> Result<String> {
+        self.request(&req::GetAddressPubKey {
+            address,
+        })
+        .await
+    }
+    async fn get_htlc_seed(&self, address: String) -> Result<String> {
+        self.request(&req::GetHtlcSeed {
+            address,
+        })
+        .await
+    }
+    async fn get_new_address(&self) -> Result<String> {
+        self.request(&req::GetNewAddress {}).await
+    }
+    async fn list_anchor_auths(&self) -> Result<Vec<ListAnchorAuthsResult>> {
+        self.request(&req::ListAnchorAuths {}).await
+    }
+    async fn list_anchor_reward_confirms(&self) -> Result<Vec<ListAnchorRewardConfirmsResult>> {
+        self.request(&req::ListAnchorRewardConfirms {}).await
+    }
+    async fn list_anchor_rewards(&self) -> Result<Vec<ListAnchorRewardsResult>> {
+        self.request(&req::ListAnchorRewards {}).await
+    }
+    async fn list_anchors(
+        &self,
+        options: Option<ListAnchorsOptions>,
+    ) -> Result<Vec<ListAnchorsResult>> {
+        self.request(&req::ListAnchors {
+            options,
+        })
+        .await
+    }
+    async fn list_anchors_pending(&self) -> Result<Vec<ListAnchorsResult>> {
+        self.request(&req::ListAnchorsPending {}).await
+    }
+    async fn list_anchors_unrewarded(&self) -> Result<Vec<ListAnchorsResult>> {
+        self.request(&req::ListAnchorsUnrewarded {}).await
+    }
+    async fn list_htlc_outputs(
+        &self,
+        script_address: Option<String>,
+    ) -> Result<Vec<ListHtlcsOutputsResult>> {
+        self.request(&req::ListHtlcOutputs {
+            script_address,
+        })
+        .await
+    }
+    async fn list_received_by_address(
+        &self,
+        min_confirmations: Option<u32>,
+        address: Option<String>,
+    ) -> Result<Vec<ReceivedByAddressInfo>> {
+        self.request(&req::ListReceivedByAddress {
+            min_confirmations,
+            address,
+        })
+        .await
+    }
+    async fn refund_htlc(
+        &self,
+        script_address: String,
+        destination_address: String,
+        fee_rate: Option<u64>,
+    ) -> Result<SendMessageResult> {
+        self.request(&req::RefundHtlc {
+            script_address,
+            destination_address,
+            fee_rate,
+        })
+        .await
+    }
+    async fn refund_htlc_all(
+        &self,
+        destination_address: String,
+        fee_rate: Option<u64>,
+    ) -> Result<Vec<String>> {
+        self.request(&req::RefundHtlcAll {
+            destination_address,
+            fee_rate,
+        })
+        .await
+    }
+    async fn send_to_address(
+        &self,
+        address: String,
+        amount: f64,
+        fee_rate: Option<u64>,
+    ) -> Result<SendMessageResult> {
+        self.request(&req::SendToAddress {
+            address,
+            amount,
+            fee_rate,
+        })
+        .await
+    }
+    async fn set_last_height(&self, height: u32) -> Result<()> {
+        self.request(&req::SetLastHeight {
+            height,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockNode;
+    use crate::Error;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+
+        let options = CreateHtlcOptions {
+            timeout: 10,
+            seed: None,
+        };
+        let _ = SpvRPC::create_htlc(&node, "receiver".into(), "owner".into(), options).await;
+        node.assert_last_request("spv_createhtlc", json!(["receiver", "owner", "10", null]));
+        let options = CreateHtlcOptions {
+            timeout: 8,
+            seed: Some("seed".into()),
+        };
+        let err = node.create_htlc("receiver".into(), "owner".into(), options).await.unwrap_err();
+        assert!(matches!(err, Error::Custom(_)));
+        let options = ClaimHtlcOptions {
+            seed: "seed".into(),
+            fee_rate: Some(1000),
+        };
+        let _ = node.claim_htlc("script".into(), "dest".into(), options).await;
+        node.assert_last_request("spv_claimhtlc", json!(["script", "dest", "seed", 1000]));
+        let _ = node.refund_htlc("script".into(), "dest".into(), None).await;
+        node.assert_last_request("spv_refundhtlc", json!(["script", "dest", null]));
+        let _ = node.refund_htlc_all("dest".into(), Some(2000)).await;
+        node.assert_last_request("spv_refundhtlcall", json!(["dest", 2000]));
+        let _ = node.decode_htlc_script("script".into()).await;
+        node.assert_last_request("spv_decodehtlcscript", json!(["script"]));
+        let _ = node.get_htlc_seed("addr".into()).await;
+        node.assert_last_request("spv_gethtlcseed", json!(["addr"]));
+        let _ = node.list_htlc_outputs(None).await;
+        node.assert_last_request("spv_listhtlcoutputs", json!([null]));
+
+        let _ = SpvRPC::get_new_address(&node).await;
+        node.assert_last_request("spv_getnewaddress", json!([]));
+        let _ = node.get_address_pub_key("addr".into()).await;
+        node.assert_last_request("spv_getaddresspubkey", json!(["addr"]));
+        let _ = SpvRPC::list_received_by_address(&node, None, Some("addr".into())).await;
+        node.assert_last_request("spv_listreceivedbyaddress", json!([1, "addr"]));
+        let _ = SpvRPC::send_to_address(&node, "addr".into(), 0.1, None).await;
+        node.assert_last_request("spv_sendtoaddress", json!(["addr", 0.1, null]));
+        let _ = node.set_last_height(100).await;
+        node.assert_last_request("spv_setlastheight", json!([100]));
+
+        let input = CreateAnchorInput {
+            txid: "txid".into(),
+            vout: 1,
+            amount: 2262303,
+            privkey: "privkey".into(),
+        };
+        let options = CreateAnchorOptions {
+            send: Some(false),
+            fee_rate: None,
+        };
+        let _ = node.create_anchor(vec![input], "reward".into(), Some(options)).await;
+        node.assert_last_request(
+            "spv_createanchor",
+            json!([
+                [{"txid": "txid", "vout": 1, "amount": 2262303, "privkey": "privkey"}],
+                "reward",
+                false,
+                null,
+            ]),
+        );
+        let _ = node.list_anchors(None).await;
+        node.assert_last_request("spv_listanchors", json!([-1, -1, -1, -1]));
+        let options = ListAnchorsOptions {
+            min_confs: Some(6),
+            ..Default::default()
+        };
+        let _ = node.list_anchors(Some(options)).await;
+        node.assert_last_request("spv_listanchors", json!([-1, -1, 6, -1]));
+        let _ = node.list_anchors_pending().await;
+        node.assert_last_request("spv_listanchorspending", json!([]));
+        let _ = node.list_anchors_unrewarded().await;
+        node.assert_last_request("spv_listanchorsunrewarded", json!([]));
+        let _ = node.list_anchor_auths().await;
+        node.assert_last_request("spv_listanchorauths", json!([]));
+        let _ = node.list_anchor_rewards().await;
+        node.assert_last_request("spv_listanchorrewards", json!([]));
+        let _ = node.list_anchor_reward_confirms().await;
+        node.assert_last_request("spv_listanchorrewardconfirms", json!([]));
+    }
+
+    #[tokio::test]
+    async fn parses_results() {
+        let node = MockNode::new()
+            .respond(
+                "spv_createhtlc",
+                json!({
+                    "address": "2N1WoHKzHY59uNpXouLQc32h9k5Y3hXK4Rx",
+                    "redeemScript": "63a820",
+                    "seed": "aba5f7e9aecf6ec4372c8a1e49562680d066da4655ee8b4bb01640479fffeaa8",
+                    "seedhash": "df95183883789f237977543885e1f82ddc045a3ba90c8f25b43a5b797a35d20e",
+                }),
+            )
+            .respond(
+                "spv_listanchors",
+                json!([{
+                    "btcBlockHeight": 15,
+                    "btcBlockHash": "btcblock",
+                    "btcTxHash": "btctx",
+                    "previousAnchor": "prev",
+                    "defiBlockHeight": 120,
+                    "defiBlockHash": "defiblock",
+                    "rewardAddress": "reward",
+                    "confirmations": 6,
+                    "signatures": 2,
+                    "active": true,
+                    "anchorCreationHeight": 105,
+                }]),
+            )
+            .respond(
+                "spv_listanchorrewards",
+                json!([{"AnchorTxHash": "anchor", "RewardTxHash": "reward"}]),
+            )
+            .respond(
+                "spv_listhtlcoutputs",
+                json!([{
+                    "txid": "txid",
+                    "vout": 0,
+                    "amount": 0.1,
+                    "address": "addr",
+                    "confirms": 3,
+                    "spent": {"txid": "spend", "confirms": 1},
+                }]),
+            )
+            .respond("spv_setlastheight", json!(null));
+
+        let options = CreateHtlcOptions {
+            timeout: 10,
+            seed: None,
+        };
+        let htlc = SpvRPC::create_htlc(&node, "receiver".into(), "owner".into(), options).await;
+        assert_eq!(htlc.unwrap().redeem_script, "63a820");
+        let anchors = node.list_anchors(None).await.unwrap();
+        assert_eq!(anchors[0].active, Some(true));
+        assert_eq!(anchors[0].confirmations, 6);
+        let rewards = node.list_anchor_rewards().await.unwrap();
+        assert_eq!(rewards[0].anchor_tx_hash, "anchor");
+        let outputs = node.list_htlc_outputs(Some("script".into())).await.unwrap();
+        assert_eq!(outputs[0].spent.as_ref().unwrap().txid, "spend");
+        node.set_last_height(100).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn parses_anchors_that_were_not_sent() {
+        let node = MockNode::new().respond(
+            "spv_createanchor",
+            json!({
+                "txHex": "0200",
+                "txHash": "anchor",
+                "defiHash": "defiblock",
+                "defiHeight": 120,
+                "estimatedReward": 0.1,
+                "cost": 0.0001,
+            }),
+        );
+
+        let input = CreateAnchorInput {
+            txid: "txid".into(),
+            vout: 1,
+            amount: 2262303,
+            privkey: "privkey".into(),
+        };
+        let options = CreateAnchorOptions {
+            send: Some(false),
+            fee_rate: None,
+        };
+        let anchor = node.create_anchor(vec![input], "reward".into(), Some(options)).await.unwrap();
+        assert_eq!(anchor.tx_hash, "anchor");
+        assert_eq!(anchor.send_result, None);
+        assert_eq!(anchor.send_message, None);
+    }
 }
@@ -1,4 +1,5 @@
-use crate::{into_json, obj_into_json, Client, Result, RpcApi};
+use crate::requests::token as req;
+use crate::{Result, RpcApi};
 use async_trait::async_trait;
 use defichain_rpc_json::{common::UTXO, token::*};
 
@@ -33,7 +34,7 @@ pub trait TokenRPC: RpcApi {
 }
 
 #[async_trait]
-impl TokenRPC for Client {
+impl<T: RpcApi + Sync> TokenRPC for T {
     async fn burn_tokens(
         &self,
         amounts: String,
@@ -41,10 +42,12 @@ impl TokenRPC for Client {
         context: Option<String>,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call(
-            "burntokens",
-            &[into_json(amounts)?, into_json(from)?, into_json(context)?, into_json(utxos)?],
-        )
+        self.request(&req::BurnTokens {
+            amounts,
+            from,
+            context,
+            utxos,
+        })
         .await
     }
     async fn create_token(
@@ -52,32 +55,126 @@ impl TokenRPC for Client {
         metadata: CreateTokenMetadata,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("createtoken", &[into_json(metadata)?, into_json(utxos)?]).await
+        self.request(&req::CreateToken {
+            metadata,
+            utxos,
+        })
+        .await
     }
     async fn decode_custom_tx(&self, hexstring: String, iswitness: Option<bool>) -> Result<()> {
-        self.call("decodecustomtx", &[into_json(hexstring)?, into_json(iswitness)?]).await
+        self.request(&req::DecodeCustomTx {
+            hexstring,
+            iswitness,
+        })
+        .await
     }
     async fn get_custom_tx(&self, txid: String, blockhash: Option<String>) -> Result<()> {
-        self.call("getcustomtx", &[into_json(txid)?, into_json(blockhash)?]).await
+        self.request(&req::GetCustomTx {
+            txid,
+            blockhash,
+        })
+        .await
     }
     async fn get_token(&self, symbol_key: &str) -> Result<TokenResult> {
-        self.call("gettoken", &[into_json(symbol_key)?]).await
+        self.request(&req::GetToken {
+            symbol_key: symbol_key.to_owned(),
+        })
+        .await
     }
     async fn list_tokens(
         &self,
         pagination: Option<TokenPagination>,
         verbose: Option<bool>,
     ) -> Result<TokenResult> {
-        self.call("listtokens", &[obj_into_json(pagination)?, into_json(verbose)?]).await
+        self.request(&req::ListTokens {
+            pagination,
+            verbose,
+        })
+        .await
     }
     async fn mint_tokens(&self, options: MintTokensOptions) -> Result<String> {
-        self.call("minttokens", &[into_json(options)?]).await
+        self.request(&req::MintTokens {
+            options,
+        })
+        .await
     }
     async fn update_token(
         &self,
         token: String,
         metadata: Option<UpdateTokenMetadata>,
     ) -> Result<String> {
-        self.call("updatetoken", &[into_json(token)?, into_json(metadata)?]).await
+        self.request(&req::UpdateToken {
+            token,
+            metadata,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{from_json, MockNode};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+        let utxos = json!({"txid": txid, "vout": 0});
+
+        let _ = node.burn_tokens("1@DFI".into(), None, None, None).await;
+        node.assert_last_request("burntokens", json!(["1@DFI", null, null, null]));
+        let _ = node
+            .burn_tokens(
+                "1@DFI".into(),
+                Some("alice".into()),
+                Some("ctx".into()),
+                Some(from_json(utxos.clone())),
+            )
+            .await;
+        node.assert_last_request("burntokens", json!(["1@DFI", "alice", "ctx", utxos]));
+
+        let metadata = json!({
+            "symbol": "BTC",
+            "name": "Bitcoin",
+            "isDAT": true,
+            "mintable": true,
+            "tradeable": true,
+            "collateralAddress": "alice",
+        });
+        let _ = node.create_token(from_json(metadata.clone()), None).await;
+        node.assert_last_request("createtoken", json!([metadata, null]));
+        let metadata = json!({
+            "symbol": null,
+            "name": "Bitcoin",
+            "isDAT": null,
+            "mintable": false,
+            "tradeable": null,
+            "finalize": null,
+        });
+        let _ = node.update_token("BTC".into(), Some(from_json(metadata.clone()))).await;
+        node.assert_last_request("updatetoken", json!(["BTC", metadata]));
+        let _ = node.update_token("BTC".into(), None).await;
+        node.assert_last_request("updatetoken", json!(["BTC", null]));
+
+        let _ = node.decode_custom_tx("00".into(), Some(true)).await;
+        node.assert_last_request("decodecustomtx", json!(["00", true]));
+        let _ = node.get_custom_tx(txid.into(), None).await;
+        node.assert_last_request("getcustomtx", json!([txid, null]));
+
+        let _ = node.get_token("DFI").await;
+        node.assert_last_request("gettoken", json!(["DFI"]));
+        let _ = node.list_tokens(None, None).await;
+        node.assert_last_request("listtokens", json!([{}, null]));
+        let _ = node.list_tokens(Some(TokenPagination::default()), Some(false)).await;
+        node.assert_last_request(
+            "listtokens",
+            json!([{"start": 0, "including_start": false, "limit": 0}, false]),
+        );
+
+        let options = json!({"amounts": ["1@BTC"], "utxos": null, "to": "alice"});
+        let _ = node.mint_tokens(from_json(options.clone())).await;
+        node.assert_last_request("minttokens", json!([options]));
     }
 }
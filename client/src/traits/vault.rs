@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 use defichain_rpc_json::{common::UTXO, vault::*};
 
-use crate::{into_json, obj_into_json, Client, Result, RpcApi};
+use crate::requests::vault as req;
+use crate::{Result, RpcApi};
 
 #[async_trait]
 pub trait VaultRPC: RpcApi {
@@ -55,26 +56,42 @@ pub trait VaultRPC: RpcApi {
 }
 
 #[async_trait]
-impl VaultRPC for Client {
+impl<T: RpcApi + Sync> VaultRPC for T {
     async fn close_vault(&self, close_vault: CloseVault, utxos: Option<UTXO>) -> Result<String> {
-        self.call("closevault", &[into_json(close_vault)?, into_json(utxos)?]).await
+        self.request(&req::CloseVault {
+            close_vault,
+            utxos,
+        })
+        .await
     }
     async fn create_vault(&self, vault: CreateVault, utxos: Option<UTXO>) -> Result<String> {
-        self.call("createvault", &[into_json(vault)?, into_json(utxos)?]).await
+        self.request(&req::CreateVault {
+            vault,
+            utxos,
+        })
+        .await
     }
     async fn deposit_to_vault(
         &self,
         deposit_vault: DepositVault,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("deposittovault", &[into_json(deposit_vault)?, into_json(utxos)?]).await
+        self.request(&req::DepositToVault {
+            deposit_vault,
+            utxos,
+        })
+        .await
     }
     async fn estimate_collateral(
         &self,
         target_ratio: u64,
         token_split: TokenPercentageSplit,
     ) -> Result<()> {
-        self.call("estimatecollateral", &[into_json(target_ratio)?, into_json(token_split)?]).await
+        self.request(&req::EstimateCollateral {
+            target_ratio,
+            token_split,
+        })
+        .await
     }
     async fn estimate_loan(
         &self,
@@ -82,45 +99,64 @@ impl VaultRPC for Client {
         token_split: TokenPercentageSplit,
         target_ratio: Option<u64>,
     ) -> Result<()> {
-        self.call(
-            "estimateloan",
-            &[into_json(vault_id)?, into_json(token_split)?, into_json(target_ratio)?],
-        )
+        self.request(&req::EstimateLoan {
+            vault_id,
+            token_split,
+            target_ratio,
+        })
         .await
     }
     async fn estimate_vault(&self) -> Result<VaultEstimation> {
-        self.call("estimatevault", &[]).await
+        self.request(&req::EstimateVault {}).await
     }
     async fn get_vault(&self, vault_id: String, verbose: Option<bool>) -> Result<VaultResult> {
-        self.call("getvault", &[into_json(vault_id)?, into_json(verbose.unwrap_or_default())?])
-            .await
+        self.request(&req::GetVault {
+            vault_id,
+            verbose,
+        })
+        .await
     }
     async fn list_auction_history(
         &self,
         owner: Option<String>,
         pagination: Option<ListAuctionHistoryPagination>,
     ) -> Result<Vec<ListAuctionHistoryDetail>> {
-        self.call("listauctionhistory", &[into_json(owner)?, into_json(pagination)?]).await
+        self.request(&req::ListAuctionHistory {
+            owner,
+            pagination,
+        })
+        .await
     }
     async fn list_auctions(
         &self,
         pagination: Option<AuctionPagination>
     ) -> Result<Vec<VaultLiquidation>> {
-        self.call("listauctions", &[obj_into_json(pagination)?]).await
+        self.request(&req::ListAuctions {
+            pagination,
+        })
+        .await
     }
     async fn list_vaults(
         &self,
         options: ListVaultOptions,
         pagination: VaultPagination,
     ) -> Result<Vec<VaultResult>> {
-        self.call("listvaults", &[into_json(options)?, into_json(pagination)?]).await
+        self.request(&req::ListVaults {
+            options,
+            pagination,
+        })
+        .await
     }
     async fn place_auction_bid(
         &self,
         place_auction_bid: PlaceAuctionBid,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("placeauctionbid", &[into_json(place_auction_bid)?, into_json(utxos)?]).await
+        self.request(&req::PlaceAuctionBid {
+            place_auction_bid,
+            utxos,
+        })
+        .await
     }
     async fn update_vault(
         &self,
@@ -128,14 +164,105 @@ impl VaultRPC for Client {
         vault: UpdateVault,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("updatevault", &[into_json(vault_id)?, into_json(vault)?, into_json(utxos)?])
-            .await
+        self.request(&req::UpdateVault {
+            vault_id,
+            vault,
+            utxos,
+        })
+        .await
     }
     async fn withdraw_from_vault(
         &self,
         withdraw_vault: WithdrawVault,
         utxos: Option<UTXO>,
     ) -> Result<String> {
-        self.call("withdrawfromvault", &[into_json(withdraw_vault)?, into_json(utxos)?]).await
+        self.request(&req::WithdrawFromVault {
+            withdraw_vault,
+            utxos,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{from_json, MockNode};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn serializes_arguments() {
+        let node = MockNode::new();
+        let txid = "2a7b3b0e4c6b1f1aaad4a2d8f1e4d1d7b2d0b9f53c6a3f2b7e8a9c1d2e3f4a5b";
+        let utxos = json!({"txid": txid, "vout": 0});
+
+        let vault = json!({"ownerAddress": "alice", "loanSchemeId": null});
+        let _ = node.create_vault(from_json(vault.clone()), None).await;
+        node.assert_last_request("createvault", json!([vault, null]));
+        let vault = json!({"ownerAddress": null, "loanSchemeId": "C150"});
+        let _ = node.update_vault("vault".into(), from_json(vault.clone()), None).await;
+        node.assert_last_request("updatevault", json!(["vault", vault, null]));
+        let close = json!({"vaultId": "vault", "to": "alice"});
+        let _ = node.close_vault(from_json(close.clone()), Some(from_json(utxos.clone()))).await;
+        node.assert_last_request("closevault", json!([close, utxos]));
+
+        let deposit = json!({"vaultId": "vault", "from": "alice", "amount": "1@DFI"});
+        let _ = node.deposit_to_vault(from_json(deposit.clone()), None).await;
+        node.assert_last_request("deposittovault", json!([deposit, null]));
+        let withdraw = json!({"vaultId": "vault", "to": "alice", "amount": "1@DFI"});
+        let _ = node.withdraw_from_vault(from_json(withdraw.clone()), None).await;
+        node.assert_last_request("withdrawfromvault", json!([withdraw, null]));
+
+        let split = json!({"DFI": 1});
+        let _ = node.estimate_collateral(150, from_json(split.clone())).await;
+        node.assert_last_request("estimatecollateral", json!([150, split]));
+        let _ = node.estimate_loan("vault".into(), from_json(split.clone()), None).await;
+        node.assert_last_request("estimateloan", json!(["vault", split, null]));
+        let _ = node.estimate_vault().await;
+        node.assert_last_request("estimatevault", json!([]));
+
+        let _ = node.get_vault("vault".into(), None).await;
+        node.assert_last_request("getvault", json!(["vault", false]));
+        let options = ListVaultOptions {
+            state: Some(VaultState::InLiquidation),
+            ..Default::default()
+        };
+        let _ = node.list_vaults(options, VaultPagination::default()).await;
+        node.assert_last_request(
+            "listvaults",
+            json!([
+                {"ownerAddress": null, "loanSchemeId": null, "state": "inLiquidation", "verbose": null},
+                {"start": null, "includingStart": null, "limit": null},
+            ]),
+        );
+
+        let _ = node.list_auctions(None).await;
+        node.assert_last_request("listauctions", json!([{}]));
+        let pagination = AuctionPagination {
+            start: Some(AuctionPaginationStart {
+                vault_id: "vault".into(),
+                height: 100,
+            }),
+            including_start: Some(true),
+            limit: Some(10),
+        };
+        let _ = node.list_auctions(Some(pagination)).await;
+        node.assert_last_request(
+            "listauctions",
+            json!([{
+                "start": {"vaultId": "vault", "height": 100},
+                "includingStart": true,
+                "limit": 10,
+            }]),
+        );
+        let pagination = json!({"maxBlockHeight": 100, "vaultId": null, "index": 0, "limit": 10});
+        let _ = node
+            .list_auction_history(Some("mine".into()), Some(from_json(pagination.clone())))
+            .await;
+        node.assert_last_request("listauctionhistory", json!(["mine", pagination]));
+
+        let bid = json!({"vaultId": "vault", "index": 0, "from": "alice", "amount": "1@DUSD"});
+        let _ = node.place_auction_bid(from_json(bid.clone()), None).await;
+        node.assert_last_request("placeauctionbid", json!([bid, null]));
     }
 }
@@ -0,0 +1,210 @@
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Pluggable transports.
+//!
+//! [Client](crate::Client) sends its requests through a [Transport], so all
+//! domain traits can be used over any HTTP stack, or without a network at all.
+//! There are two ways to plug one in:
+//!
+//! - [Client::with_transport](crate::Client::with_transport) takes a single
+//!   [Transport]. The timeout of the client is enforced on top of it, but the
+//!   client can't be scoped to a wallet.
+//! - [Client::with_connector](crate::Client::with_connector) takes a
+//!   [Connector], which creates transports for a given timeout and wallet, so
+//!   the client supports [per-call timeouts](crate::timeout) and
+//!   [wallets](crate::Client::wallet) like one created with
+//!   [Client::new](crate::Client::new).
+//!
+//! An in-process transport that answers every request with the height of the
+//! genesis block:
+//!
+//! ```
+//! # async fn example() -> defichain_rpc::Result<()> {
+//! use std::fmt;
+//!
+//! use async_trait::async_trait;
+//! use defichain_rpc::jsonrpc_async::Error;
+//! use defichain_rpc::transport::{Request, Response, Transport};
+//! use defichain_rpc::{BlockchainRPC, Client};
+//! use serde_json::value::to_raw_value;
+//!
+//! struct Genesis;
+//!
+//! #[async_trait]
+//! impl Transport for Genesis {
+//!     async fn send_request(&self, r: Request<'_>) -> Result<Response, Error> {
+//!         Ok(Response {
+//!             result: Some(to_raw_value(&0)?),
+//!             error: None,
+//!             id: r.id,
+//!             jsonrpc: r.jsonrpc.map(str::to_owned),
+//!         })
+//!     }
+//!
+//!     async fn send_batch(&self, rs: &[Request<'_>]) -> Result<Vec<Response>, Error> {
+//!         let mut resps = Vec::new();
+//!         for r in rs {
+//!             resps.push(self.send_request(r.clone()).await?);
+//!         }
+//!         Ok(resps)
+//!     }
+//!
+//!     fn fmt_target(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//!         f.write_str("genesis")
+//!     }
+//! }
+//!
+//! let client = Client::with_transport(Genesis);
+//! assert_eq!(BlockchainRPC::get_block_count(&client).await?, 0);
+//! # Ok(())
+//! # }
+//! # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+pub use jsonrpc_async::{Request, Response, Transport};
+
+/// Creates the transports of a [Client](crate::Client) and the clients
+/// derived from it.
+///
+/// Transports are created when the client is, and again for calls with a
+/// different timeout and for [wallet](crate::Client::wallet) clients.
+/// Connection pools and credentials that should be shared between them belong
+/// in the connector.
+pub trait Connector: Send + Sync + 'static {
+    /// A transport that aborts requests after `timeout`, and sends them to the
+    /// endpoint of `wallet` if given, which is `/wallet/<name>` for HTTP.
+    fn transport(&self, timeout: Duration, wallet: Option<&str>) -> Box<dyn Transport>;
+}
+
+/// A boxed [Transport], which jsonrpc_async can't use directly.
+struct Boxed(Box<dyn Transport>);
+
+#[async_trait]
+impl Transport for Boxed {
+    async fn send_request(
+        &self,
+        r: Request<'_>,
+    ) -> std::result::Result<Response, jsonrpc_async::Error> {
+        self.0.send_request(r).await
+    }
+
+    async fn send_batch(
+        &self,
+        rs: &[Request<'_>],
+    ) -> std::result::Result<Vec<Response>, jsonrpc_async::Error> {
+        self.0.send_batch(rs).await
+    }
+
+    fn fmt_target(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_target(f)
+    }
+}
+
+/// A JSON-RPC client using a transport of `connector`.
+pub(crate) fn jsonrpc_client(
+    connector: &dyn Connector,
+    timeout: Duration,
+    wallet: Option<&str>,
+) -> jsonrpc_async::client::Client {
+    jsonrpc_async::client::Client::with_transport(Boxed(connector.transport(timeout, wallet)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, RpcApi};
+    use serde_json::value::to_raw_value;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers every request with the wallet and timeout of its transport.
+    struct Echo {
+        wallet: Option<String>,
+        timeout: Duration,
+    }
+
+    #[async_trait]
+    impl Transport for Echo {
+        async fn send_request(
+            &self,
+            r: Request<'_>,
+        ) -> std::result::Result<Response, jsonrpc_async::Error> {
+            Ok(Response {
+                result: Some(to_raw_value(&(&self.wallet, self.timeout.as_secs()))?),
+                error: None,
+                id: r.id,
+                jsonrpc: r.jsonrpc.map(str::to_owned),
+            })
+        }
+
+        async fn send_batch(
+            &self,
+            rs: &[Request<'_>],
+        ) -> std::result::Result<Vec<Response>, jsonrpc_async::Error> {
+            let mut resps = Vec::new();
+            for r in rs {
+                resps.push(self.send_request(r.clone()).await?);
+            }
+            Ok(resps)
+        }
+
+        fn fmt_target(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("echo")
+        }
+    }
+
+    #[derive(Default)]
+    struct EchoConnector(Mutex<usize>);
+
+    impl Connector for Arc<EchoConnector> {
+        fn transport(&self, timeout: Duration, wallet: Option<&str>) -> Box<dyn Transport> {
+            *self.0.lock().unwrap() += 1;
+            Box::new(Echo {
+                wallet: wallet.map(str::to_owned),
+                timeout,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn uses_transports_of_connector() {
+        let connector = Arc::new(EchoConnector::default());
+        let client = Client::with_connector(connector.clone());
+        let res: (Option<String>, u64) = client.call("getbalance", &[]).await.unwrap();
+        assert_eq!(res, (None, 15));
+        let res: (Option<String>, u64) =
+            client.wallet("savings").unwrap().call("getbalance", &[]).await.unwrap();
+        assert_eq!(res, (Some("savings".into()), 15));
+        let res: (Option<String>, u64) =
+            client.with_timeout(Duration::from_secs(60)).call("getbalance", &[]).await.unwrap();
+        assert_eq!(res, (None, 60));
+        assert_eq!(*connector.0.lock().unwrap(), 3);
+
+        let client = Client::with_transport(Echo {
+            wallet: None,
+            timeout: Duration::from_secs(1),
+        });
+        let res: (Option<String>, u64) = client.call("getbalance", &[]).await.unwrap();
+        assert_eq!(res, (None, 1));
+        assert!(client.wallet("savings").is_err());
+
+        let mut batch = client.batch();
+        let first = batch.queue::<(Option<String>, u64)>("getbalance", vec![]);
+        let second = batch.queue::<(Option<String>, u64)>("getbalance", vec![]);
+        let mut results = batch.send().await.unwrap();
+        assert_eq!(results.take(first).unwrap(), (None, 1));
+        assert_eq!(results.take(second).unwrap(), (None, 1));
+    }
+}
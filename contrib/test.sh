@@ -33,7 +33,19 @@ if [ -n "$BITCOINVERSION" ]; then
     exit 0
 else
   # Regular build/unit test.
-  cargo build --verbose
-  cargo test --verbose
+  if cargo --version | grep ${MSRV}; then
+    # The command-line tool needs a newer compiler.
+    cargo build --verbose --workspace --exclude defichain-rpc-cli
+    cargo test --verbose --workspace --exclude defichain-rpc-cli
+  else
+    cargo build --verbose
+    cargo test --verbose
+  fi
+  if cargo --version | grep ${MSRV}; then
+    # The reqwest feature needs a newer compiler.
+    cargo test --verbose --features blocking
+  else
+    cargo test --verbose --all-features
+  fi
   cargo build --verbose --examples
 fi
@@ -31,8 +31,7 @@ use defichain_rpc::defichain_rpc_json::{
     GetBlockTemplateModes, GetBlockTemplateRules, ScanTxOutRequest,
 };
 use defichain_rpc::json;
-use defichain_rpc::jsonrpc_async::error::Error as JsonRpcError;
-use defichain_rpc::{Auth, Client, Error, RpcApi};
+use defichain_rpc::{Auth, Client, RpcApi, RpcErrorCode};
 
 lazy_static! {
     static ref SECP: secp256k1::Secp256k1<secp256k1::All> = secp256k1::Secp256k1::new();
@@ -65,7 +64,7 @@ static LOGGER: StdLogger = StdLogger;
 macro_rules! assert_deprecated {
     ($call:expr) => {
         match $call.await.unwrap_err() {
-            Error::JsonRpc(JsonRpcError::Rpc(ref e)) if e.code == -32 => {}
+            ref e if e.rpc_code() == Some(RpcErrorCode::MethodDeprecated) => {}
             e => panic!("expected deprecated error for {}, got: {}", stringify!($call), e),
         }
     };
@@ -75,7 +74,7 @@ macro_rules! assert_deprecated {
 macro_rules! assert_not_found {
     ($call:expr) => {
         match $call.await.unwrap_err() {
-            Error::JsonRpc(JsonRpcError::Rpc(ref e)) if e.code == -32601 => {}
+            ref e if e.rpc_code() == Some(RpcErrorCode::MethodNotFound) => {}
             e => panic!("expected method not found error for {}, got: {}", stringify!($call), e),
         }
     };
@@ -85,8 +84,7 @@ macro_rules! assert_not_found {
 macro_rules! assert_error_message {
     ($call:expr, $code:expr, $msg:expr) => {
         match $call.await.unwrap_err() {
-            Error::JsonRpc(JsonRpcError::Rpc(ref e))
-                if e.code == $code && e.message.contains($msg) => {}
+            ref e if matches!(e.rpc_error(), Some(r) if r.code == $code && r.message.contains($msg)) => {}
             e => panic!("expected '{}' error for {}, got: {}", $msg, stringify!($call), e),
         }
     };
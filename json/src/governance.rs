@@ -1,4 +1,8 @@
+use std::collections::HashMap;
+
+use bitcoin::hashes::{sha256, Hash};
 use bitcoin::Txid;
+use serde::de::Error as SerdeError;
 use serde_with::skip_serializing_none;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -14,7 +18,7 @@ pub enum ProposalStatus {
     Completed,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ListProposalsType {
     Cfp,
@@ -23,7 +27,7 @@ pub enum ListProposalsType {
     All,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ListProposalsStatus {
     Voting,
@@ -33,7 +37,7 @@ pub enum ListProposalsStatus {
     All,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum VoteDecision {
     Yes,
@@ -58,24 +62,135 @@ pub enum MasternodeType {
     All,
 }
 
+/// A community fund proposal, paying `amount` DFI to `payout_address` in
+/// each of its `cycles`.
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CFPData {
-    title: String,
-    context: String,
-    context_hash: Option<String>,
-    amount: i64,
-    payout_address: String,
-    cycles: Option<u64>,
+    pub title: String,
+    pub context: String,
+    pub context_hash: Option<String>,
+    pub amount: f64,
+    pub payout_address: String,
+    pub cycles: Option<u64>,
 }
 
+impl CFPData {
+    /// A proposal for a single cycle.
+    pub fn new(title: String, context: String, amount: f64, payout_address: String) -> Self {
+        Self {
+            title,
+            context,
+            amount,
+            payout_address,
+            context_hash: None,
+            cycles: None,
+        }
+    }
+}
+
+/// A vote of confidence.
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VOCData {
-    title: String,
-    context: String,
-    context_hash: Option<String>,
-    emergency: Option<bool>,
+    pub title: String,
+    pub context: String,
+    pub context_hash: Option<String>,
+    pub emergency: Option<bool>,
+}
+
+impl VOCData {
+    /// A non-emergency vote.
+    pub fn new(title: String, context: String) -> Self {
+        Self {
+            title,
+            context,
+            context_hash: None,
+            emergency: None,
+        }
+    }
+}
+
+/// The `context_hash` of a proposal, the hex SHA-256 of the content of the
+/// document its context refers to, e.g. the proposal's text at its URL.
+pub fn context_hash(content: &[u8]) -> String {
+    sha256::Hash::hash(content).to_string()
+}
+
+/// The fees of creating proposals, which are burnt.
+///
+/// The defaults are the fees of mainnet, which the governance attributes
+/// `v0/gov/proposals/cfp_fee`, `voc_fee` and `voc_emergency_fee` override.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProposalFees {
+    /// The share of a community fund proposal's amount.
+    pub cfp_fee: f64,
+    /// The minimum fee of a community fund proposal.
+    pub cfp_minimum_fee: f64,
+    pub voc_fee: f64,
+    pub voc_emergency_fee: f64,
+}
+
+impl Default for ProposalFees {
+    fn default() -> Self {
+        Self {
+            cfp_fee: 0.01,
+            cfp_minimum_fee: 10.0,
+            voc_fee: 100.0,
+            voc_emergency_fee: 10000.0,
+        }
+    }
+}
+
+impl ProposalFees {
+    /// The fees with the overrides in `attributes`, the `ATTRIBUTES` of
+    /// `getgov`. Fails if an override is not an amount.
+    pub fn from_attributes(
+        attributes: &HashMap<String, serde_json::Value>,
+    ) -> Result<Self, serde_json::Error> {
+        let attribute = |key: &str, default: f64| {
+            let key = format!("v0/gov/proposals/{}", key);
+            // The node returns amounts as numbers or strings.
+            let fee = match attributes.get(&key) {
+                None => return Ok(default),
+                Some(serde_json::Value::Number(n)) => n.as_f64(),
+                Some(serde_json::Value::String(s)) => s.parse().ok(),
+                Some(_) => None,
+            };
+            match fee {
+                Some(fee) if fee.is_finite() && fee >= 0.0 => Ok(fee),
+                _ => {
+                    Err(serde_json::Error::custom(format!("invalid {}: {}", key, attributes[&key])))
+                }
+            }
+        };
+        let defaults = Self::default();
+        Ok(Self {
+            cfp_fee: attribute("cfp_fee", defaults.cfp_fee)?,
+            cfp_minimum_fee: defaults.cfp_minimum_fee,
+            voc_fee: attribute("voc_fee", defaults.voc_fee)?,
+            voc_emergency_fee: attribute("voc_emergency_fee", defaults.voc_emergency_fee)?,
+        })
+    }
+
+    /// The fee of creating `data`, in DFI.
+    pub fn cfp(&self, data: &CFPData) -> f64 {
+        // The node computes the fee in satoshis and rounds it down.
+        let amount = (data.amount * 1e8).round() as i128;
+        let share = (self.cfp_fee * 1e8).round() as i128;
+        let fee = (amount * share / 100_000_000) as f64 / 1e8;
+        fee.max(self.cfp_minimum_fee)
+    }
+
+    /// The fee of creating `data`, in DFI.
+    pub fn voc(&self, data: &VOCData) -> f64 {
+        match data.emergency {
+            Some(true) => self.voc_emergency_fee,
+            _ => self.voc_fee,
+        }
+    }
 }
 
 #[skip_serializing_none]
@@ -112,12 +227,23 @@ pub struct ProposalInfo {
     pub fee_redistribution_total: Option<f64>,
 }
 
+/// A vote of a masternode, given by its id, owner or operator address.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VoteData {
-    proposal_id: String,
-    masternode_id: String,
-    decision: VoteDecision,
+    pub proposal_id: Txid,
+    pub masternode_id: String,
+    pub decision: VoteDecision,
+}
+
+impl VoteData {
+    pub fn new(proposal_id: Txid, masternode_id: String, decision: VoteDecision) -> Self {
+        Self {
+            proposal_id,
+            masternode_id,
+            decision,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+
+use serde_with::skip_serializing_none;
+
+/// A new order, either selling a DFC token for BTC (`token_from` and
+/// `chain_to`) or buying a DFC token with BTC (`chain_from` and `token_to`).
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXOrder {
+    pub token_from: Option<String>,
+    pub chain_from: Option<String>,
+    pub chain_to: Option<String>,
+    pub token_to: Option<String>,
+    pub owner_address: Option<String>,
+    pub receive_pubkey: Option<String>,
+    pub amount_from: f64,
+    pub order_price: f64,
+    pub expiry: Option<u64>,
+}
+
+/// An offer to fill an order.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXOffer {
+    pub order_tx: String,
+    pub amount: f64,
+    pub owner_address: String,
+    pub receive_pubkey: Option<String>,
+    pub expiry: Option<u64>,
+}
+
+/// An HTLC on DeFiChain for an offer.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HTLC {
+    pub offer_tx: String,
+    pub amount: f64,
+    pub hash: String,
+    pub timeout: Option<u64>,
+}
+
+/// An HTLC on the external chain for an offer.
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtHTLC {
+    pub offer_tx: String,
+    pub amount: f64,
+    pub hash: String,
+    pub htlc_script_address: String,
+    pub owner_pubkey: String,
+    pub timeout: u64,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXListOrderOptions {
+    pub token: Option<String>,
+    pub chain: Option<String>,
+    /// List the offers of this order instead of orders.
+    pub order_tx: Option<String>,
+    pub limit: Option<u64>,
+    pub closed: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXListHTLCOptions {
+    pub offer_tx: String,
+    pub limit: Option<u64>,
+    pub closed: Option<bool>,
+}
+
+/// The result of the ICX transactions.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ICXGenericResult {
+    /// The node's warning that ICX is experimental.
+    #[serde(rename = "WARNING")]
+    pub warning: Option<String>,
+    pub txid: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ICXOrderStatus {
+    Open,
+    Closed,
+    Filled,
+    Expired,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ICXOrderType {
+    /// Sells a DFC token.
+    Internal,
+    /// Sells an asset of the external chain.
+    External,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ICXHTLCStatus {
+    Open,
+    Claimed,
+    Refunded,
+    Expired,
+    Closed,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXOrderInfo {
+    pub status: ICXOrderStatus,
+    pub r#type: ICXOrderType,
+    pub token_from: Option<String>,
+    pub chain_from: Option<String>,
+    pub chain_to: Option<String>,
+    pub token_to: Option<String>,
+    pub receive_pubkey: Option<String>,
+    pub owner_address: String,
+    pub amount_from: f64,
+    pub amount_to_fill: f64,
+    pub order_price: f64,
+    pub amount_to_fill_in_to_asset: f64,
+    pub height: u64,
+    pub expire_height: u64,
+    pub close_height: Option<u64>,
+    pub close_order_tx: Option<String>,
+    pub expired: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXOfferInfo {
+    pub order_tx: String,
+    pub status: ICXOrderStatus,
+    pub amount: f64,
+    pub amount_in_from_asset: f64,
+    pub owner_address: String,
+    pub receive_pubkey: Option<String>,
+    pub taker_fee: f64,
+    pub expire_height: u64,
+}
+
+/// An entry of `icx_getorder` and `icx_listorders`, which list offers
+/// instead of orders when given an order.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ICXOrderEntry {
+    Order(ICXOrderInfo),
+    Offer(ICXOfferInfo),
+}
+
+/// The orders or offers by transaction id.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ICXOrders {
+    #[serde(rename = "WARNING")]
+    pub warning: Option<String>,
+    #[serde(flatten)]
+    pub entries: BTreeMap<String, ICXOrderEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXDFCHTLCInfo {
+    pub status: ICXHTLCStatus,
+    pub offer_tx: String,
+    pub amount: f64,
+    #[serde(rename = "amountInEXTAsset")]
+    pub amount_in_ext_asset: f64,
+    pub hash: String,
+    pub timeout: u64,
+    pub height: u64,
+    pub refund_height: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXEXTHTLCInfo {
+    pub status: ICXHTLCStatus,
+    pub offer_tx: String,
+    pub amount: f64,
+    #[serde(rename = "amountInDFCAsset")]
+    pub amount_in_dfc_asset: f64,
+    pub hash: String,
+    pub htlc_script_address: String,
+    pub owner_pubkey: String,
+    pub timeout: u64,
+    pub height: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ICXClaimDFCHTLCInfo {
+    pub dfchtlc_tx: String,
+    pub seed: String,
+    pub height: u64,
+}
+
+/// An HTLC of an offer, or the claim of one.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ICXHTLCInfo {
+    #[serde(rename = "DFC")]
+    DFC(ICXDFCHTLCInfo),
+    #[serde(rename = "EXTERNAL")]
+    External(ICXEXTHTLCInfo),
+    #[serde(rename = "CLAIM DFC")]
+    ClaimDFC(ICXClaimDFCHTLCInfo),
+}
+
+/// The HTLCs of an offer by transaction id.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ICXHTLCs {
+    #[serde(rename = "WARNING")]
+    pub warning: Option<String>,
+    #[serde(flatten)]
+    pub htlcs: BTreeMap<String, ICXHTLCInfo>,
+}
@@ -28,9 +28,13 @@ pub mod account;
 pub mod blockchain;
 pub mod common;
 pub mod governance;
+pub mod icxorderbook;
 pub mod loan;
+pub mod masternode;
 pub mod mining;
+pub mod oracle;
 pub mod poolpair;
+pub mod spv;
 pub mod token;
 pub mod vault;
 
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use serde_with::skip_serializing_none;
+
+/// The timelock of a new masternode's collateral, which raises its rewards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MasternodeTimeLock {
+    #[serde(rename = "FIVEYEARTIMELOCK")]
+    FiveYears,
+    #[serde(rename = "TENYEARTIMELOCK")]
+    TenYears,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MasternodeState {
+    PreEnabled,
+    Enabled,
+    PreResigned,
+    Resigned,
+    Transferring,
+    #[serde(other)]
+    Unknown,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MasternodeInfo {
+    pub owner_auth_address: String,
+    pub operator_auth_address: String,
+    pub reward_address: String,
+    pub creation_height: i64,
+    /// -1 unless the masternode resigned.
+    pub resign_height: i64,
+    pub resign_tx: String,
+    pub collateral_tx: String,
+    pub state: MasternodeState,
+    pub minted_blocks: u64,
+    pub owner_is_mine: bool,
+    pub operator_is_mine: bool,
+    pub local_masternode: bool,
+    pub target_multipliers: Option<Vec<u8>>,
+    /// The timelock of the collateral, e.g. `5 years`.
+    pub timelock: Option<String>,
+}
+
+/// The masternodes by id.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MasternodeResult(pub BTreeMap<String, MasternodeInfo>);
+
+/// The result of `listmasternodes`, which only lists the states of the
+/// masternodes unless verbose.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListMasternodesResult {
+    Verbose(MasternodeResult),
+    States(BTreeMap<String, MasternodeState>),
+}
+
+/// The pagination of `listmasternodes`, starting from a masternode id.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct MasternodePagination {
+    pub start: Option<String>,
+    pub including_start: Option<bool>,
+    pub limit: Option<u64>,
+}
+
+/// The masternode of `getmasternodeblocks`, by any of its identifiers.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MasternodeBlock {
+    pub id: Option<String>,
+    pub owner_address: Option<String>,
+    pub operator_address: Option<String>,
+}
+
+/// The new values of `updatemasternode`. An empty reward address removes
+/// the reward address.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMasternodeValues {
+    pub owner_address: Option<String>,
+    pub operator_address: Option<String>,
+    pub reward_address: Option<String>,
+}
+
+/// The masternodes of the anchor teams.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnchorTeamResult {
+    pub auth: Vec<String>,
+    pub confirm: Vec<String>,
+}
+
+/// A governance variable as listed by `listgovs`.
+///
+/// The node lists each variable as its current value, `{"NAME": value}`,
+/// followed by the values set to apply at later heights, `{"height": value}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GovVariable {
+    /// The name of the variable, e.g. `ORACLE_BLOCK_INTERVAL`.
+    pub name: String,
+    /// The current value, whose shape depends on the variable.
+    pub value: Value,
+    /// The values that apply from later block heights, by height.
+    pub pending: BTreeMap<u64, Value>,
+}
+
+impl Serialize for GovVariable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries = vec![BTreeMap::new()];
+        entries[0].insert(self.name.clone(), &self.value);
+        for (height, value) in self.pending.iter() {
+            let mut entry = BTreeMap::new();
+            entry.insert(height.to_string(), value);
+            entries.push(entry);
+        }
+        entries.serialize(serializer)
+    }
+}
+
+impl<'a> Deserialize<'a> for GovVariable {
+    fn deserialize<D: Deserializer<'a>>(deserializer: D) -> Result<GovVariable, D::Error> {
+        let mut entries = Vec::<BTreeMap<String, Value>>::deserialize(deserializer)?.into_iter();
+        let current = entries.next().unwrap_or_default();
+        if current.len() != 1 {
+            return Err(D::Error::custom("expected the name and value of a variable"));
+        }
+        let (name, value) = current.into_iter().next().unwrap();
+        let mut pending = BTreeMap::new();
+        for (height, value) in entries.flatten() {
+            let height = height.parse().map_err(|_| {
+                D::Error::custom(format!("expected a block height, got {:?}", height))
+            })?;
+            pending.insert(height, value);
+        }
+        Ok(GovVariable {
+            name,
+            value,
+            pending,
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use serde_with::skip_serializing_none;
+
+/// A price feed of an oracle, the price of `token` in `currency`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OraclePriceFeed {
+    pub token: String,
+    pub currency: String,
+}
+
+/// A price set with `setoracledata`, e.g. `100@TSLA` in `USD`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OraclePrice {
+    pub token_amount: String,
+    pub currency: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleTokenPrice {
+    pub token: String,
+    pub currency: String,
+    pub amount: f64,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleData {
+    pub weightage: u8,
+    pub oracleid: String,
+    pub address: String,
+    pub price_feeds: Vec<OraclePriceFeed>,
+    pub token_prices: Vec<OracleTokenPrice>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OracleRawPriceState {
+    Live,
+    Expired,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleRawPrice {
+    pub oracleid: String,
+    pub price_feeds: OraclePriceFeed,
+    pub rawprice: f64,
+    pub weightage: u8,
+    pub state: OracleRawPriceState,
+    pub timestamp: u64,
+}
+
+/// Whether a price is valid, or why it isn't.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PriceStatus {
+    Valid(bool),
+    Invalid(String),
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListPricesData {
+    pub token: String,
+    pub currency: String,
+    pub price: Option<f64>,
+    pub ok: PriceStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixedIntervalPrice {
+    pub fixed_interval_price_id: String,
+    pub active_price: f64,
+    pub next_price: f64,
+    pub active_price_block: u64,
+    pub next_price_block: u64,
+    pub timestamp: u64,
+    pub is_live: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFixedIntervalPrice {
+    pub price_feed_id: String,
+    pub active_price: f64,
+    pub next_price: f64,
+    pub timestamp: u64,
+    pub is_live: bool,
+}
+
+/// The pagination of `listoracles` and `listlatestrawprices`, starting from
+/// an oracle id.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct OraclePagination {
+    pub start: Option<String>,
+    pub including_start: Option<bool>,
+    pub limit: Option<u64>,
+}
+
+/// The pagination of `listprices`, starting from an index.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ListPricesPagination {
+    pub start: Option<u64>,
+    pub including_start: Option<bool>,
+    pub limit: Option<u64>,
+}
+
+/// The pagination of `listfixedintervalprices`, starting from a price feed
+/// id like `TSLA/USD`.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct FixedIntervalPricePagination {
+    pub start: Option<String>,
+    pub limit: Option<u64>,
+}
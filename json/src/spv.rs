@@ -0,0 +1,162 @@
+use serde_with::skip_serializing_none;
+
+/// The result of the SPV RPCs that send a Bitcoin transaction.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SendMessageResult {
+    pub txid: String,
+    pub sendmessage: String,
+}
+
+/// An output funding an anchor.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateAnchorInput {
+    pub txid: String,
+    pub vout: u32,
+    /// The amount of the output in satoshis.
+    pub amount: u64,
+    pub privkey: String,
+}
+
+/// The options of `spv_createanchor`.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct CreateAnchorOptions {
+    /// Whether to send the anchor, which the node does by default.
+    pub send: Option<bool>,
+    /// The fee rate in satoshis per KB.
+    pub fee_rate: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAnchorResult {
+    pub tx_hex: String,
+    pub tx_hash: String,
+    pub defi_hash: String,
+    pub defi_height: u64,
+    pub estimated_reward: f64,
+    pub cost: f64,
+    /// Only returned if the anchor was sent.
+    pub send_result: Option<i64>,
+    /// Only returned if the anchor was sent.
+    pub send_message: Option<String>,
+}
+
+/// The options of `spv_createhtlc`.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct CreateHtlcOptions {
+    /// The number of Bitcoin blocks until the owner can refund.
+    pub timeout: u32,
+    /// The seed, which the node generates if none is given.
+    pub seed: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateHtlcResult {
+    pub address: String,
+    pub redeem_script: String,
+    pub seed: Option<String>,
+    pub seedhash: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DecodeHtlcResult {
+    pub receiverpubkey: String,
+    pub ownerpubkey: String,
+    pub timeout: u32,
+    pub seedhash: String,
+}
+
+/// The options of `spv_claimhtlc`.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ClaimHtlcOptions {
+    pub seed: String,
+    /// The fee rate in satoshis per KB.
+    pub fee_rate: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HtlcOutputSpent {
+    pub txid: String,
+    pub confirms: u64,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListHtlcsOutputsResult {
+    pub txid: String,
+    pub vout: u32,
+    pub amount: f64,
+    pub address: String,
+    pub confirms: u64,
+    pub spent: Option<HtlcOutputSpent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReceivedByAddressInfo {
+    pub address: String,
+    pub r#type: String,
+    pub amount: f64,
+    pub confirmations: u64,
+    pub txids: Vec<String>,
+}
+
+/// The filters of `spv_listanchors`, all unbounded by default.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ListAnchorsOptions {
+    pub min_btc_height: Option<i64>,
+    pub max_btc_height: Option<i64>,
+    pub min_confs: Option<i64>,
+    pub max_confs: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAnchorsResult {
+    pub btc_block_height: u64,
+    pub btc_block_hash: String,
+    pub btc_tx_hash: String,
+    pub previous_anchor: String,
+    pub defi_block_height: u64,
+    pub defi_block_hash: String,
+    pub reward_address: String,
+    pub confirmations: u64,
+    pub signatures: u64,
+    pub active: Option<bool>,
+    pub anchor_creation_height: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAnchorAuthsResult {
+    pub previous_anchor: String,
+    pub block_height: u64,
+    pub block_hash: String,
+    pub creation_height: u64,
+    pub signers: u64,
+    pub signees: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAnchorRewardConfirmsResult {
+    pub btc_tx_height: u64,
+    pub btc_tx_hash: String,
+    pub anchor_height: u64,
+    pub dfi_block_hash: String,
+    pub prev_anchor_height: u64,
+    pub reward_address: String,
+    pub confirm_sign_hash: String,
+    pub signers: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ListAnchorRewardsResult {
+    pub anchor_tx_hash: String,
+    pub reward_tx_hash: String,
+}